@@ -10,6 +10,9 @@ pub struct AppError {
     pub kind: ErrorKind,
     /// 人間向けの詳細メッセージ
     pub message: String,
+    /// ブランチ切り替えを妨げたファイルのパス（該当する場合のみ）
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicting_paths: Vec<String>,
 }
 
 /// エラーの分類。フロントエンドはこの値でエラーハンドリングを分岐できる。
@@ -37,9 +40,14 @@ impl std::fmt::Display for AppError {
 
 impl AppError {
     pub fn git(err: anyhow::Error) -> Self {
+        let conflicting_paths = err
+            .downcast_ref::<reown::git::branch::SwitchConflict>()
+            .map(|c| c.conflicting_paths.clone())
+            .unwrap_or_default();
         Self {
             kind: ErrorKind::Git,
             message: format!("{err:#}"),
+            conflicting_paths,
         }
     }
 
@@ -47,6 +55,7 @@ impl AppError {
         Self {
             kind: ErrorKind::GitHub,
             message: format!("{err:#}"),
+            conflicting_paths: Vec::new(),
         }
     }
 
@@ -54,6 +63,7 @@ impl AppError {
         Self {
             kind: ErrorKind::Storage,
             message: format!("{err:#}"),
+            conflicting_paths: Vec::new(),
         }
     }
 
@@ -61,6 +71,7 @@ impl AppError {
         Self {
             kind: ErrorKind::Analysis,
             message: format!("{err:#}"),
+            conflicting_paths: Vec::new(),
         }
     }
 
@@ -68,6 +79,7 @@ impl AppError {
         Self {
             kind: ErrorKind::Llm,
             message: format!("{err:#}"),
+            conflicting_paths: Vec::new(),
         }
     }
 }
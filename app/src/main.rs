@@ -19,8 +19,13 @@ fn create_branch(repo_path: String, name: String) -> Result<(), AppError> {
 }
 
 #[tauri::command]
-fn switch_branch(repo_path: String, name: String) -> Result<(), AppError> {
-    reown::git::branch::switch_branch(&repo_path, &name).map_err(AppError::git)
+fn switch_branch(
+    repo_path: String,
+    name: String,
+    options: Option<reown::git::branch::SwitchOptions>,
+) -> Result<reown::git::branch::SwitchResult, AppError> {
+    reown::git::branch::switch_branch_with(&repo_path, &name, &options.unwrap_or_default())
+        .map_err(AppError::git)
 }
 
 #[tauri::command]
@@ -36,6 +41,68 @@ fn list_enriched_branches(
     reown::git::branch::list_enriched_branches(&repo_path, &pull_requests).map_err(AppError::git)
 }
 
+#[tauri::command]
+fn cleanup_merged_branches(
+    repo_path: String,
+    pull_requests: Vec<reown::github::PrInfo>,
+) -> Result<Vec<String>, AppError> {
+    reown::git::branch::cleanup_merged_branches(&repo_path, &pull_requests).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn list_operations(repo_path: String) -> Result<Vec<reown::git::journal::JournalEntry>, AppError> {
+    reown::git::journal::list_operations(&repo_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn undo_last_operation(
+    repo_path: String,
+) -> Result<Option<reown::git::journal::JournalEntry>, AppError> {
+    reown::git::journal::undo_last_operation(&repo_path).map_err(AppError::git)
+}
+
+// ── Stash commands ──────────────────────────────────────────────────────────
+
+#[tauri::command]
+fn stash_save(
+    repo_path: String,
+    message: Option<String>,
+    include_untracked: Option<bool>,
+) -> Result<Option<String>, AppError> {
+    reown::git::stash::stash_save(
+        &repo_path,
+        message.as_deref(),
+        include_untracked.unwrap_or_default(),
+    )
+    .map_err(AppError::git)
+}
+
+#[tauri::command]
+fn list_stashes(repo_path: String) -> Result<Vec<reown::git::stash::StashEntry>, AppError> {
+    reown::git::stash::list_stashes(&repo_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn stash_apply(
+    repo_path: String,
+    index: usize,
+) -> Result<reown::git::stash::StashApplyResult, AppError> {
+    reown::git::stash::stash_apply(&repo_path, index).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn stash_pop(
+    repo_path: String,
+    index: usize,
+) -> Result<reown::git::stash::StashApplyResult, AppError> {
+    reown::git::stash::stash_pop(&repo_path, index).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn stash_drop(repo_path: String, index: usize) -> Result<(), AppError> {
+    reown::git::stash::stash_drop(&repo_path, index).map_err(AppError::git)
+}
+
 // ── Worktree commands ───────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -43,24 +110,93 @@ fn list_worktrees(repo_path: String) -> Result<Vec<reown::git::worktree::Worktre
     reown::git::worktree::list_worktrees(&repo_path).map_err(AppError::git)
 }
 
+#[tauri::command]
+fn list_worktree_statuses(
+    repo_path: String,
+) -> Result<Vec<reown::git::status::WorktreeStatus>, AppError> {
+    reown::git::status::list_worktree_statuses(&repo_path).map_err(AppError::git)
+}
+
 #[tauri::command]
 fn add_worktree(repo_path: String, worktree_path: String, branch: String) -> Result<(), AppError> {
     reown::git::worktree::add_worktree(&repo_path, &worktree_path, &branch).map_err(AppError::git)
 }
 
+#[tauri::command]
+fn remove_worktree(
+    repo_path: String,
+    name: String,
+    options: Option<reown::git::worktree::RemoveWorktreeOptions>,
+) -> Result<(), AppError> {
+    reown::git::worktree::remove_worktree(&repo_path, &name, &options.unwrap_or_default())
+        .map_err(AppError::git)
+}
+
+#[tauri::command]
+fn prune_worktrees(repo_path: String) -> Result<Vec<String>, AppError> {
+    reown::git::worktree::prune_worktrees(&repo_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn lock_worktree(repo_path: String, name: String, reason: Option<String>) -> Result<(), AppError> {
+    reown::git::worktree::lock_worktree(&repo_path, &name, reason.as_deref()).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn unlock_worktree(repo_path: String, name: String) -> Result<(), AppError> {
+    reown::git::worktree::unlock_worktree(&repo_path, &name).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn move_worktree(repo_path: String, name: String, new_path: String) -> Result<(), AppError> {
+    reown::git::worktree::move_worktree(&repo_path, &name, &new_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn rebase_worktree(
+    worktree_path: String,
+    onto: String,
+) -> Result<reown::git::rebase::RebaseResult, AppError> {
+    reown::git::rebase::rebase_onto(&worktree_path, &onto).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn continue_rebase(worktree_path: String) -> Result<reown::git::rebase::RebaseResult, AppError> {
+    reown::git::rebase::continue_rebase(&worktree_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn abort_rebase(worktree_path: String) -> Result<(), AppError> {
+    reown::git::rebase::abort_rebase(&worktree_path).map_err(AppError::git)
+}
+
 // ── Diff commands ───────────────────────────────────────────────────────────
 
 #[tauri::command]
-fn diff_workdir(repo_path: String) -> Result<Vec<reown::git::diff::FileDiff>, AppError> {
-    reown::git::diff::diff_workdir(&repo_path).map_err(AppError::git)
+fn diff_workdir(
+    repo_path: String,
+    options: Option<reown::git::diff::DiffRequest>,
+) -> Result<Vec<reown::git::diff::FileDiff>, AppError> {
+    reown::git::diff::diff_workdir(&repo_path, &options.unwrap_or_default()).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn diff_workdir_grouped(
+    repo_path: String,
+    options: Option<reown::git::diff::DiffRequest>,
+) -> Result<reown::git::diff::WorkdirChanges, AppError> {
+    reown::git::diff::diff_workdir_grouped(&repo_path, &options.unwrap_or_default())
+        .map_err(AppError::git)
 }
 
 #[tauri::command]
 fn diff_commit(
     repo_path: String,
     commit_sha: String,
+    options: Option<reown::git::diff::DiffRequest>,
 ) -> Result<Vec<reown::git::diff::FileDiff>, AppError> {
-    reown::git::diff::diff_commit(&repo_path, &commit_sha).map_err(AppError::git)
+    reown::git::diff::diff_commit(&repo_path, &commit_sha, &options.unwrap_or_default())
+        .map_err(AppError::git)
 }
 
 #[tauri::command]
@@ -68,8 +204,160 @@ fn diff_branches(
     repo_path: String,
     base_ref: String,
     head_ref: String,
+    options: Option<reown::git::diff::DiffRequest>,
 ) -> Result<Vec<reown::git::diff::FileDiff>, AppError> {
-    reown::git::diff::diff_branches(&repo_path, &base_ref, &head_ref).map_err(AppError::git)
+    reown::git::diff::diff_branches(
+        &repo_path,
+        &base_ref,
+        &head_ref,
+        &options.unwrap_or_default(),
+    )
+    .map_err(AppError::git)
+}
+
+#[tauri::command]
+fn compare_branches(
+    repo_path: String,
+    base_ref: String,
+    head_ref: String,
+    mode: Option<reown::git::diff::CompareMode>,
+    options: Option<reown::git::diff::DiffRequest>,
+) -> Result<reown::git::diff::BranchComparison, AppError> {
+    reown::git::diff::compare_branches(
+        &repo_path,
+        &base_ref,
+        &head_ref,
+        mode.unwrap_or_default(),
+        &options.unwrap_or_default(),
+    )
+    .map_err(AppError::git)
+}
+
+// ── Stage commands ──────────────────────────────────────────────────────────
+
+#[tauri::command]
+fn stage_hunks(
+    repo_path: String,
+    selections: Vec<reown::git::stage::HunkSelection>,
+) -> Result<(), AppError> {
+    reown::git::stage::stage_hunks(&repo_path, &selections).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn unstage_hunks(
+    repo_path: String,
+    selections: Vec<reown::git::stage::HunkSelection>,
+) -> Result<(), AppError> {
+    reown::git::stage::unstage_hunks(&repo_path, &selections).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn discard_hunks(
+    repo_path: String,
+    selections: Vec<reown::git::stage::HunkSelection>,
+) -> Result<(), AppError> {
+    reown::git::stage::discard_hunks(&repo_path, &selections).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn stage_files(repo_path: String, paths: Vec<String>) -> Result<(), AppError> {
+    reown::git::stage::stage_files(&repo_path, &paths).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn unstage_files(repo_path: String, paths: Vec<String>) -> Result<(), AppError> {
+    reown::git::stage::unstage_files(&repo_path, &paths).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn discard_files(repo_path: String, paths: Vec<String>) -> Result<(), AppError> {
+    reown::git::stage::discard_files(&repo_path, &paths).map_err(AppError::git)
+}
+
+// ── Commit commands ─────────────────────────────────────────────────────────
+
+#[tauri::command]
+fn create_commit(
+    worktree_path: String,
+    request: reown::git::commit::CommitRequest,
+) -> Result<reown::git::commit::CommitResult, AppError> {
+    reown::git::commit::create_commit(&worktree_path, &request).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn list_local_commits(
+    repo_path: String,
+    filter: Option<reown::git::log::LogFilter>,
+) -> Result<Vec<reown::git::log::LogEntry>, AppError> {
+    reown::git::log::log(&repo_path, &filter.unwrap_or_default()).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn draft_commit_message(worktree_path: String) -> Result<String, AppError> {
+    reown::git::commit::draft_commit_message(&worktree_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn cherry_pick_commit(
+    worktree_path: String,
+    rev: String,
+    options: Option<reown::git::cherry_pick::PickOptions>,
+) -> Result<reown::git::cherry_pick::PickResult, AppError> {
+    reown::git::cherry_pick::cherry_pick(&worktree_path, &rev, &options.unwrap_or_default())
+        .map_err(AppError::git)
+}
+
+#[tauri::command]
+fn revert_commit(
+    worktree_path: String,
+    rev: String,
+    options: Option<reown::git::cherry_pick::PickOptions>,
+) -> Result<reown::git::cherry_pick::PickResult, AppError> {
+    reown::git::cherry_pick::revert(&worktree_path, &rev, &options.unwrap_or_default())
+        .map_err(AppError::git)
+}
+
+#[tauri::command]
+fn continue_pick(worktree_path: String) -> Result<reown::git::cherry_pick::PickResult, AppError> {
+    reown::git::cherry_pick::continue_pick(&worktree_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn abort_pick(worktree_path: String) -> Result<(), AppError> {
+    reown::git::cherry_pick::abort_pick(&worktree_path).map_err(AppError::git)
+}
+
+// ── Tag commands ────────────────────────────────────────────────────────────
+
+#[tauri::command]
+fn list_tags(repo_path: String) -> Result<Vec<reown::git::tag::TagInfo>, AppError> {
+    reown::git::tag::list_tags(&repo_path).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn create_tag(
+    repo_path: String,
+    name: String,
+    target: String,
+    message: String,
+) -> Result<reown::git::tag::TagInfo, AppError> {
+    reown::git::tag::create_tag(&repo_path, &name, &target, &message).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn delete_tag(repo_path: String, name: String) -> Result<(), AppError> {
+    reown::git::tag::delete_tag(&repo_path, &name).map_err(AppError::git)
+}
+
+#[tauri::command]
+fn get_release_range(
+    repo_path: String,
+    from: String,
+    to: String,
+    options: Option<reown::git::diff::DiffRequest>,
+) -> Result<reown::git::tag::ReleaseRange, AppError> {
+    reown::git::tag::release_range(&repo_path, &from, &to, &options.unwrap_or_default())
+        .map_err(AppError::git)
 }
 
 // ── GitHub commands ─────────────────────────────────────────────────────────
@@ -153,6 +441,27 @@ async fn enable_pr_auto_merge(
         .map_err(AppError::github)
 }
 
+#[tauri::command]
+async fn update_pr_branch(
+    owner: String,
+    repo: String,
+    pr_number: u64,
+    expected_head_sha: Option<String>,
+    client: tauri::State<'_, reown::github::GitHubClient>,
+) -> Result<String, AppError> {
+    let token = load_github_token()?;
+    client
+        .update_branch(
+            &owner,
+            &repo,
+            pr_number,
+            expected_head_sha.as_deref(),
+            &token,
+        )
+        .await
+        .map_err(AppError::github)
+}
+
 // ── Git info commands ──────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -160,6 +469,11 @@ fn get_repo_info(repo_path: String) -> Result<reown::git::RepoInfo, AppError> {
     reown::git::get_repo_info(&repo_path).map_err(AppError::git)
 }
 
+#[tauri::command]
+fn get_repo_remotes(repo_path: String) -> Result<reown::git::RepoRemotes, AppError> {
+    reown::git::get_repo_remotes(&repo_path).map_err(AppError::git)
+}
+
 // ── Repository commands ────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -199,11 +513,61 @@ fn remove_repository(app_handle: tauri::AppHandle, path: String) -> Result<(), A
 
 // ── Analysis commands ───────────────────────────────────────────────────────
 
+/// fetch を伴うマージ可否チェックを blocking スレッドで実行する
+async fn run_merge_check(
+    repo_path: String,
+    pr: reown::github::PrInfo,
+) -> anyhow::Result<reown::git::merge::MergeCheck> {
+    tauri::async_runtime::spawn_blocking(move || {
+        reown::git::merge::check_pr_mergeability(&repo_path, &pr, &mut |_| {})
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!("{e}"))?
+}
+
+/// ローカルで PR のマージ可否をチェックして分析結果に反映する。
+/// チェックに失敗しても他の PR の分析を続けられるよう、失敗はその PR の分析結果に記録する。
+async fn attach_merge_check(
+    repo_path: &str,
+    pr: &reown::github::PrInfo,
+    analysis: &mut reown::analysis::AnalysisResult,
+) {
+    match run_merge_check(repo_path.to_string(), pr.clone()).await {
+        Ok(check) => reown::analysis::apply_merge_check(analysis, check),
+        Err(e) => reown::analysis::apply_merge_check_error(analysis, &e),
+    }
+}
+
+/// PR のコミット一覧を取得し、署名状態を分析結果に反映する
+async fn attach_commit_signatures(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    client: &reown::github::GitHubClient,
+    analysis: &mut reown::analysis::AnalysisResult,
+) -> Result<(), AppError> {
+    let commits = client
+        .list_pr_commits(owner, repo, analysis.pr_number, token)
+        .await
+        .map_err(AppError::github)?;
+    reown::analysis::apply_commit_signatures(analysis, &commits);
+    Ok(())
+}
+
+#[tauri::command]
+async fn check_pr_mergeability(
+    repo_path: String,
+    pr: reown::github::PrInfo,
+) -> Result<reown::git::merge::MergeCheck, AppError> {
+    run_merge_check(repo_path, pr).await.map_err(AppError::git)
+}
+
 #[tauri::command]
 async fn analyze_pr_risk(
     owner: String,
     repo: String,
     pr_number: u64,
+    repo_path: Option<String>,
     client: tauri::State<'_, reown::github::GitHubClient>,
 ) -> Result<reown::analysis::AnalysisResult, AppError> {
     let token = load_github_token()?;
@@ -222,7 +586,55 @@ async fn analyze_pr_risk(
         .await
         .map_err(AppError::github)?;
 
-    Ok(reown::analysis::analyze_pr_risk(&pr, &diffs))
+    let mut analysis = reown::analysis::analyze_pr_risk(&pr, &diffs);
+    if let Some(repo_path) = repo_path.as_deref() {
+        attach_merge_check(repo_path, &pr, &mut analysis).await;
+    }
+    Ok(analysis)
+}
+
+#[tauri::command]
+fn analyze_release_risk(
+    repo_path: String,
+    from: String,
+    to: String,
+) -> Result<reown::analysis::AnalysisResult, AppError> {
+    let range = reown::git::tag::release_range(&repo_path, &from, &to, &Default::default())
+        .map_err(AppError::git)?;
+    Ok(reown::analysis::analyze_release_risk(
+        &range,
+        &reown::config::RiskConfig::default(),
+    ))
+}
+
+#[tauri::command]
+async fn compute_pr_overlap(
+    owner: String,
+    repo: String,
+    repo_path: Option<String>,
+    client: tauri::State<'_, reown::github::GitHubClient>,
+) -> Result<reown::analysis::OverlapMatrix, AppError> {
+    let token = load_github_token()?;
+    let prs = client
+        .list_pull_requests(&owner, &repo, &token)
+        .await
+        .map_err(AppError::github)?;
+
+    let mut pr_files = Vec::new();
+    for pr in prs.into_iter().filter(|pr| pr.state == "open") {
+        let diffs = client
+            .get_pull_request_files(&owner, &repo, pr.number, &token)
+            .await
+            .map_err(AppError::github)?;
+        pr_files.push((pr, diffs));
+    }
+
+    let mut matrix = reown::analysis::compute_overlap_matrix(&pr_files);
+    if let Some(repo_path) = repo_path {
+        reown::analysis::refine_with_fetched_prs(&mut matrix, &repo_path, &mut |_| {})
+            .map_err(AppError::git)?;
+    }
+    Ok(matrix)
 }
 
 #[tauri::command]
@@ -300,6 +712,21 @@ async fn summarize_pull_request(
         .map_err(AppError::llm)
 }
 
+#[tauri::command]
+async fn summarize_release(
+    repo_path: String,
+    from: String,
+    to: String,
+    app_handle: tauri::AppHandle,
+) -> Result<reown::llm::summary::PrSummary, AppError> {
+    let range = reown::git::tag::release_range(&repo_path, &from, &to, &Default::default())
+        .map_err(AppError::git)?;
+    let llm_client = build_llm_client(&app_handle)?;
+    reown::llm::summary::summarize_release(&range, &llm_client)
+        .await
+        .map_err(AppError::llm)
+}
+
 #[tauri::command]
 async fn check_pr_consistency(
     owner: String,
@@ -551,10 +978,49 @@ fn complete_onboarding(app_handle: tauri::AppHandle) -> Result<(), AppError> {
 
 // ── Automation commands ───────────────────────────────────────────────────────
 
+/// 自動 approve の判定用にオープンな PR を分析する。
+/// マージ可否チェックは必須で、`repo_path` が無い場合はチェック失敗として記録し、
+/// その PR が approve されないようにする。
+async fn analyze_auto_approve_prs(
+    owner: &str,
+    repo: &str,
+    repo_path: Option<&str>,
+    token: &str,
+    client: &reown::github::GitHubClient,
+    automation_config: &reown::config::AutomationConfig,
+) -> Result<Vec<reown::analysis::AnalysisResult>, AppError> {
+    let prs = client
+        .list_pull_requests(owner, repo, token)
+        .await
+        .map_err(AppError::github)?;
+
+    let mut analyses = Vec::new();
+    for pr in prs.iter().filter(|pr| pr.state == "open") {
+        let diffs = client
+            .get_pull_request_files(owner, repo, pr.number, token)
+            .await
+            .map_err(AppError::github)?;
+        let mut analysis = reown::analysis::analyze_pr_risk(pr, &diffs);
+        match repo_path {
+            Some(repo_path) => attach_merge_check(repo_path, pr, &mut analysis).await,
+            None => reown::analysis::apply_merge_check_error(
+                &mut analysis,
+                &anyhow::anyhow!("ローカルリポジトリが未指定のためマージ可否をチェックできません"),
+            ),
+        }
+        if automation_config.require_signed_commits {
+            attach_commit_signatures(owner, repo, token, client, &mut analysis).await?;
+        }
+        analyses.push(analysis);
+    }
+    Ok(analyses)
+}
+
 #[tauri::command]
 async fn evaluate_auto_approve_candidates(
     owner: String,
     repo: String,
+    repo_path: Option<String>,
     app_handle: tauri::AppHandle,
     client: tauri::State<'_, reown::github::GitHubClient>,
 ) -> Result<Vec<reown::automation::AutoApproveCandidate>, AppError> {
@@ -568,19 +1034,15 @@ async fn evaluate_auto_approve_candidates(
     let repo_id = format!("{owner}/{repo}");
     let automation_config = config.get_automation_config(&repo_id);
 
-    let prs = client
-        .list_pull_requests(&owner, &repo, &token)
-        .await
-        .map_err(AppError::github)?;
-
-    let mut analyses = Vec::new();
-    for pr in &prs {
-        let diffs = client
-            .get_pull_request_files(&owner, &repo, pr.number, &token)
-            .await
-            .map_err(AppError::github)?;
-        analyses.push(reown::analysis::analyze_pr_risk(pr, &diffs));
-    }
+    let analyses = analyze_auto_approve_prs(
+        &owner,
+        &repo,
+        repo_path.as_deref(),
+        &token,
+        &client,
+        automation_config,
+    )
+    .await?;
 
     Ok(reown::automation::evaluate_auto_approve(
         &analyses,
@@ -592,6 +1054,7 @@ async fn evaluate_auto_approve_candidates(
 async fn run_auto_approve(
     owner: String,
     repo: String,
+    repo_path: Option<String>,
     app_handle: tauri::AppHandle,
     client: tauri::State<'_, reown::github::GitHubClient>,
 ) -> Result<reown::automation::AutoApproveResult, AppError> {
@@ -605,19 +1068,15 @@ async fn run_auto_approve(
     let repo_id = format!("{owner}/{repo}");
     let automation_config = config.get_automation_config(&repo_id);
 
-    let prs = client
-        .list_pull_requests(&owner, &repo, &token)
-        .await
-        .map_err(AppError::github)?;
-
-    let mut analyses = Vec::new();
-    for pr in &prs {
-        let diffs = client
-            .get_pull_request_files(&owner, &repo, pr.number, &token)
-            .await
-            .map_err(AppError::github)?;
-        analyses.push(reown::analysis::analyze_pr_risk(pr, &diffs));
-    }
+    let analyses = analyze_auto_approve_prs(
+        &owner,
+        &repo,
+        repo_path.as_deref(),
+        &token,
+        &client,
+        automation_config,
+    )
+    .await?;
 
     let candidates = reown::automation::evaluate_auto_approve(&analyses, automation_config);
     Ok(reown::automation::execute_auto_approve(
@@ -651,6 +1110,20 @@ async fn run_auto_approve_with_merge(
     .await)
 }
 
+#[tauri::command]
+async fn create_revert_pr(
+    repo_path: String,
+    owner: String,
+    repo: String,
+    target: reown::automation::RevertTarget,
+    client: tauri::State<'_, reown::github::GitHubClient>,
+) -> Result<reown::automation::RevertPrResult, AppError> {
+    let token = load_github_token()?;
+    reown::automation::create_revert_pr(&repo_path, &owner, &repo, &target, &token, &client)
+        .await
+        .map_err(AppError::git)
+}
+
 // ── GitHub Auth commands ─────────────────────────────────────────────────────
 
 /// GitHub OAuth App の Client ID
@@ -703,6 +1176,15 @@ fn create_worktree_for_todo(
         .map_err(AppError::git)
 }
 
+#[tauri::command]
+fn create_worktree_for_pr(
+    repo_path: String,
+    pr_number: u64,
+) -> Result<reown::git::worktree::WorktreeInfo, AppError> {
+    reown::git::worktree::add_worktree_for_pr(&repo_path, pr_number, &mut |_| {})
+        .map_err(AppError::git)
+}
+
 // ── Review Pattern commands ──────────────────────────────────────────────────
 
 #[tauri::command]
@@ -800,24 +1282,66 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             list_branches,
             list_enriched_branches,
+            cleanup_merged_branches,
+            list_operations,
+            undo_last_operation,
             create_branch,
             switch_branch,
             delete_branch,
+            stash_save,
+            list_stashes,
+            stash_apply,
+            stash_pop,
+            stash_drop,
             list_worktrees,
+            list_worktree_statuses,
             add_worktree,
+            remove_worktree,
+            prune_worktrees,
+            lock_worktree,
+            unlock_worktree,
+            move_worktree,
+            rebase_worktree,
+            continue_rebase,
+            abort_rebase,
             diff_workdir,
+            diff_workdir_grouped,
+            stage_hunks,
+            unstage_hunks,
+            discard_hunks,
+            stage_files,
+            unstage_files,
+            discard_files,
             diff_commit,
             diff_branches,
+            compare_branches,
+            create_commit,
+            draft_commit_message,
+            cherry_pick_commit,
+            revert_commit,
+            continue_pick,
+            abort_pick,
+            list_tags,
+            create_tag,
+            delete_tag,
+            get_release_range,
+            list_local_commits,
             list_pull_requests,
             get_pull_request_files,
             list_pr_commits,
             submit_pr_review,
             enable_pr_auto_merge,
+            update_pr_branch,
+            check_pr_mergeability,
             analyze_pr_risk,
+            analyze_release_risk,
+            compute_pr_overlap,
             analyze_pr_risk_with_llm,
             summarize_pull_request,
+            summarize_release,
             check_pr_consistency,
             get_repo_info,
+            get_repo_remotes,
             add_repository,
             list_repositories,
             remove_repository,
@@ -836,8 +1360,10 @@ fn main() {
             evaluate_auto_approve_candidates,
             run_auto_approve,
             run_auto_approve_with_merge,
+            create_revert_pr,
             extract_todos,
             create_worktree_for_todo,
+            create_worktree_for_pr,
             suggest_review_comments,
             list_review_history,
             add_review_record,
@@ -884,12 +1410,14 @@ mod tests {
             branch: Some("feature-branch".to_string()),
             is_main: false,
             is_locked: false,
+            lock_reason: None,
         };
         let json = serde_json::to_value(&info).unwrap();
         assert_eq!(json["name"], "feature");
         assert_eq!(json["branch"], "feature-branch");
         assert_eq!(json["is_main"], false);
         assert_eq!(json["is_locked"], false);
+        assert!(json["lock_reason"].is_null());
     }
 
     #[test]
@@ -898,6 +1426,11 @@ mod tests {
             old_path: Some("a.rs".to_string()),
             new_path: Some("a.rs".to_string()),
             status: FileStatus::Modified,
+            similarity: None,
+            kind: reown::git::diff::FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -1,3 +1,4 @@".to_string(),
                 lines: vec![DiffLineInfo {
@@ -905,6 +1438,7 @@ mod tests {
                     old_lineno: None,
                     new_lineno: Some(4),
                     content: "new line\n".to_string(),
+                    highlights: Vec::new(),
                 }],
             }],
         };
@@ -924,7 +1458,9 @@ mod tests {
             title: "Add feature".to_string(),
             author: "alice".to_string(),
             state: "open".to_string(),
+            merge_commit_sha: None,
             head_branch: "feature-x".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-15T10:30:00Z".to_string(),
             additions: 100,
@@ -954,6 +1490,7 @@ mod tests {
             (FileStatus::Deleted, "Deleted"),
             (FileStatus::Modified, "Modified"),
             (FileStatus::Renamed, "Renamed"),
+            (FileStatus::Copied, "Copied"),
             (FileStatus::Other, "Other"),
         ] {
             let json = serde_json::to_value(&status).unwrap();
@@ -981,6 +1518,23 @@ mod tests {
         assert_eq!(json["message"], "repo not found");
     }
 
+    #[test]
+    fn test_app_error_git_includes_conflicting_paths() {
+        let err = AppError::git(
+            reown::git::branch::SwitchConflict {
+                branch: "feature".to_string(),
+                conflicting_paths: vec!["hello.txt".to_string()],
+            }
+            .into(),
+        );
+        let json = serde_json::to_value(&err).unwrap();
+        assert_eq!(json["kind"], "git");
+        assert_eq!(json["conflicting_paths"][0], "hello.txt");
+
+        let json = serde_json::to_value(AppError::git(anyhow::anyhow!("boom"))).unwrap();
+        assert!(json.get("conflicting_paths").is_none());
+    }
+
     #[test]
     fn test_app_error_github_serializes() {
         let err = AppError::github(anyhow::anyhow!("GitHub API returned 401: Unauthorized"));
@@ -1202,7 +1756,7 @@ mod tests {
         let (dir, _repo) = init_test_repo();
         let path = dir.path().to_str().unwrap().to_string();
         super::create_branch(path.clone(), "feature".to_string()).unwrap();
-        super::switch_branch(path.clone(), "feature".to_string()).unwrap();
+        super::switch_branch(path.clone(), "feature".to_string(), None).unwrap();
         let branches = super::list_branches(path).unwrap();
         let feature = branches.iter().find(|b| b.name == "feature").unwrap();
         assert!(feature.is_head);
@@ -1212,12 +1766,46 @@ mod tests {
     fn test_cmd_switch_branch_nonexistent() {
         let (dir, _repo) = init_test_repo();
         let path = dir.path().to_str().unwrap().to_string();
-        let result = super::switch_branch(path, "nonexistent".to_string());
+        let result = super::switch_branch(path, "nonexistent".to_string(), None);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err.kind, ErrorKind::Git));
     }
 
+    #[test]
+    fn test_cmd_switch_branch_auto_stash() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        super::create_branch(path.clone(), "feature".to_string()).unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+        let options = reown::git::branch::SwitchOptions { auto_stash: true };
+        let result = super::switch_branch(path, "feature".to_string(), Some(options)).unwrap();
+        assert!(result.stashed);
+        assert!(result.restored);
+    }
+
+    // ── Stash コマンドテスト ─────────────────────────────────────────────
+
+    #[test]
+    fn test_cmd_stash_roundtrip() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        let oid = super::stash_save(path.clone(), Some("wip".to_string()), None).unwrap();
+        assert!(oid.is_some());
+        assert_eq!(super::list_stashes(path.clone()).unwrap().len(), 1);
+        assert!(super::stash_pop(path.clone(), 0).unwrap().applied);
+        assert!(super::list_stashes(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cmd_stash_drop_invalid_index() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let result = super::stash_drop(path, 0);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
     #[test]
     fn test_cmd_delete_branch_ok() {
         let (dir, _repo) = init_test_repo();
@@ -1238,6 +1826,21 @@ mod tests {
         assert!(matches!(err.kind, ErrorKind::Git));
     }
 
+    #[test]
+    fn test_cmd_undo_delete_branch() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        super::create_branch(path.clone(), "to-delete".to_string()).unwrap();
+        super::delete_branch(path.clone(), "to-delete".to_string()).unwrap();
+        assert_eq!(super::list_operations(path.clone()).unwrap().len(), 1);
+
+        let undone = super::undo_last_operation(path.clone()).unwrap();
+        assert!(undone.is_some());
+        let branches = super::list_branches(path.clone()).unwrap();
+        assert!(branches.iter().any(|b| b.name == "to-delete"));
+        assert!(super::undo_last_operation(path).unwrap().is_none());
+    }
+
     // ── list_enriched_branches コマンドテスト ────────────────────────────
 
     #[test]
@@ -1253,6 +1856,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cmd_cleanup_merged_branches() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        super::create_branch(path.clone(), "fresh".to_string()).unwrap();
+        // デフォルトブランチの先端にあるだけのブランチは削除しない
+        let deleted = super::cleanup_merged_branches(path.clone(), vec![]).unwrap();
+        assert!(deleted.is_empty());
+        let branches = super::list_branches(path).unwrap();
+        assert!(branches.iter().any(|b| b.name == "fresh"));
+    }
+
+    #[test]
+    fn test_cmd_cleanup_merged_branches_invalid_path() {
+        let result = super::cleanup_merged_branches("/nonexistent/path/xyz".to_string(), vec![]);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
     #[test]
     fn test_cmd_list_enriched_branches_with_prs() {
         let (dir, _repo) = init_test_repo();
@@ -1264,7 +1885,9 @@ mod tests {
             title: "Add feature X".to_string(),
             author: "alice".to_string(),
             state: "open".to_string(),
+            merge_commit_sha: None,
             head_branch: "feature-x".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-15T10:30:00Z".to_string(),
             additions: 10,
@@ -1291,6 +1914,22 @@ mod tests {
         assert!(wts[0].is_main);
     }
 
+    #[test]
+    fn test_cmd_list_worktree_statuses_ok() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let statuses = super::list_worktree_statuses(path).unwrap();
+        assert_eq!(statuses.len(), 1);
+        assert!(statuses[0].worktree.is_main);
+        assert_eq!(statuses[0].dirty, 0);
+    }
+
+    #[test]
+    fn test_cmd_list_worktree_statuses_invalid_path() {
+        let result = super::list_worktree_statuses("/nonexistent/path/xyz".to_string());
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
     #[test]
     fn test_cmd_list_worktrees_invalid_path() {
         let result = super::list_worktrees("/nonexistent/path/xyz".to_string());
@@ -1315,6 +1954,59 @@ mod tests {
         assert!(wts.iter().any(|w| w.name == "feature"));
     }
 
+    #[test]
+    fn test_cmd_worktree_lifecycle() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let wt_path = dir.path().join("wt-feature");
+        super::add_worktree(
+            path.clone(),
+            wt_path.to_str().unwrap().to_string(),
+            "feature".to_string(),
+        )
+        .unwrap();
+
+        super::lock_worktree(
+            path.clone(),
+            "feature".to_string(),
+            Some("busy".to_string()),
+        )
+        .unwrap();
+        let wts = super::list_worktrees(path.clone()).unwrap();
+        let wt = wts.iter().find(|w| w.name == "feature").unwrap();
+        assert_eq!(wt.lock_reason.as_deref(), Some("busy"));
+        assert!(super::remove_worktree(path.clone(), "feature".to_string(), None).is_err());
+
+        super::unlock_worktree(path.clone(), "feature".to_string()).unwrap();
+        super::remove_worktree(path.clone(), "feature".to_string(), None).unwrap();
+        assert!(super::prune_worktrees(path.clone()).unwrap().is_empty());
+        assert_eq!(super::list_worktrees(path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cmd_move_worktree() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let wt_path = dir.path().join("wt-feature");
+        let moved = dir.path().join("wt-moved");
+        super::add_worktree(
+            path.clone(),
+            wt_path.to_str().unwrap().to_string(),
+            "feature".to_string(),
+        )
+        .unwrap();
+        super::move_worktree(
+            path.clone(),
+            "feature".to_string(),
+            moved.to_str().unwrap().to_string(),
+        )
+        .unwrap();
+        assert!(!wt_path.exists());
+        let wts = super::list_worktrees(path).unwrap();
+        let wt = wts.iter().find(|w| w.name == "feature").unwrap();
+        assert!(wt.path.ends_with("wt-moved"));
+    }
+
     #[test]
     fn test_cmd_add_worktree_invalid_repo_path() {
         let result = super::add_worktree(
@@ -1327,13 +2019,30 @@ mod tests {
         assert!(matches!(err.kind, ErrorKind::Git));
     }
 
+    #[test]
+    fn test_cmd_rebase_worktree_up_to_date() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let result = super::rebase_worktree(path, "main".to_string()).unwrap();
+        assert_eq!(result.status, reown::git::rebase::RebaseStatus::UpToDate);
+    }
+
+    #[test]
+    fn test_cmd_continue_rebase_without_rebase() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let err = super::continue_rebase(path.clone()).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Git));
+        assert!(super::abort_rebase(path).is_err());
+    }
+
     // ── Diff コマンドテスト ──────────────────────────────────────────────
 
     #[test]
     fn test_cmd_diff_workdir_no_changes() {
         let (dir, _repo) = init_test_repo();
         let path = dir.path().to_str().unwrap().to_string();
-        let diffs = super::diff_workdir(path).unwrap();
+        let diffs = super::diff_workdir(path, None).unwrap();
         assert!(diffs.is_empty());
     }
 
@@ -1342,14 +2051,181 @@ mod tests {
         let (dir, _repo) = init_test_repo();
         let path = dir.path().to_str().unwrap().to_string();
         std::fs::write(dir.path().join("hello.txt"), "hello\nworld\n").unwrap();
-        let diffs = super::diff_workdir(path).unwrap();
+        let diffs = super::diff_workdir(path, None).unwrap();
         assert_eq!(diffs.len(), 1);
         assert_eq!(diffs[0].status, reown::git::diff::FileStatus::Modified);
     }
 
+    #[test]
+    fn test_cmd_diff_workdir_with_options() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("hello.txt"), "hello  \n").unwrap();
+        let options = reown::git::diff::DiffRequest {
+            whitespace: reown::git::diff::WhitespaceMode::IgnoreAll,
+            ..Default::default()
+        };
+        let diffs = super::diff_workdir(path, Some(options)).unwrap();
+        assert!(diffs.iter().all(|d| d.chunks.is_empty()));
+    }
+
+    #[test]
+    fn test_cmd_diff_workdir_grouped() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("hello.txt"), "hello\nworld\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        let changes = super::diff_workdir_grouped(path, None).unwrap();
+        assert!(changes.staged.is_empty());
+        assert_eq!(changes.unstaged.len(), 1);
+        assert_eq!(changes.untracked.len(), 1);
+    }
+
+    #[test]
+    fn test_cmd_stage_and_unstage_files() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        let paths = vec!["new.txt".to_string()];
+
+        super::stage_files(path.clone(), paths.clone()).unwrap();
+        let changes = super::diff_workdir_grouped(path.clone(), None).unwrap();
+        assert_eq!(changes.staged.len(), 1);
+
+        super::unstage_files(path.clone(), paths.clone()).unwrap();
+        super::discard_files(path.clone(), paths).unwrap();
+        let changes = super::diff_workdir_grouped(path, None).unwrap();
+        assert!(changes.staged.is_empty());
+        assert!(changes.untracked.is_empty());
+    }
+
+    #[test]
+    fn test_cmd_stage_hunks_unknown_path() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let selection = reown::git::stage::HunkSelection {
+            path: "missing.txt".to_string(),
+            header: "@@ -1 +1 @@".to_string(),
+            lines: None,
+        };
+        let result = super::stage_hunks(path, vec![selection]);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
+    #[test]
+    fn test_cmd_create_commit() {
+        let (dir, repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("hello.txt"), "hello\nworld\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+
+        let draft = super::draft_commit_message(path.clone()).unwrap();
+        assert_eq!(draft, "Update hello.txt\n");
+        let request = reown::git::commit::CommitRequest {
+            message: draft,
+            ..Default::default()
+        };
+        let result = super::create_commit(path, request).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.sha, head.id().to_string());
+    }
+
+    #[test]
+    fn test_cmd_create_commit_nothing_staged() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let request = reown::git::commit::CommitRequest {
+            message: "Empty".to_string(),
+            ..Default::default()
+        };
+        let result = super::create_commit(path, request);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
+    #[test]
+    fn test_cmd_revert_commit() {
+        let (dir, repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        std::fs::write(dir.path().join("hello.txt"), "hello\nworld\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+        let request = reown::git::commit::CommitRequest {
+            message: "Add world".to_string(),
+            ..Default::default()
+        };
+        super::create_commit(path.clone(), request).unwrap();
+
+        let result = super::revert_commit(path, "HEAD".to_string(), None).unwrap();
+        assert!(result.commit.is_some());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "hello\n"
+        );
+    }
+
+    #[test]
+    fn test_cmd_abort_pick_without_operation() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let result = super::abort_pick(path);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
+    #[test]
+    fn test_cmd_list_local_commits() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let commits = super::list_local_commits(path.clone(), None).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary, "init");
+
+        let filter = reown::git::log::LogFilter {
+            author: Some("nobody".to_string()),
+            ..Default::default()
+        };
+        assert!(super::list_local_commits(path, Some(filter))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_cmd_create_worktree_for_pr_without_origin() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let result = super::create_worktree_for_pr(path, 1);
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
+    #[test]
+    fn test_cmd_check_pr_mergeability_without_origin() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let pr = reown::github::PrInfo {
+            number: 1,
+            title: "PR".to_string(),
+            author: "alice".to_string(),
+            state: "open".to_string(),
+            merge_commit_sha: None,
+            head_branch: "feature".to_string(),
+            head_repo: None,
+            base_branch: "main".to_string(),
+            updated_at: String::new(),
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: String::new(),
+            html_url: String::new(),
+        };
+        let result = tauri::async_runtime::block_on(super::check_pr_mergeability(path, pr));
+        assert!(matches!(result.unwrap_err().kind, ErrorKind::Git));
+    }
+
     #[test]
     fn test_cmd_diff_workdir_invalid_path() {
-        let result = super::diff_workdir("/nonexistent/path/xyz".to_string());
+        let result = super::diff_workdir("/nonexistent/path/xyz".to_string(), None);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err.kind, ErrorKind::Git));
@@ -1373,7 +2249,7 @@ mod tests {
             .commit(Some("HEAD"), &sig, &sig, "update", &tree, &[&parent])
             .unwrap();
 
-        let diffs = super::diff_commit(path, oid.to_string()).unwrap();
+        let diffs = super::diff_commit(path, oid.to_string(), None).unwrap();
         assert_eq!(diffs.len(), 1);
         assert_eq!(diffs[0].status, reown::git::diff::FileStatus::Modified);
     }
@@ -1382,12 +2258,44 @@ mod tests {
     fn test_cmd_diff_commit_invalid_sha() {
         let (dir, _repo) = init_test_repo();
         let path = dir.path().to_str().unwrap().to_string();
-        let result = super::diff_commit(path, "invalid_sha_value".to_string());
+        let result = super::diff_commit(path, "invalid_sha_value".to_string(), None);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(matches!(err.kind, ErrorKind::Git));
     }
 
+    // ── Tag コマンドテスト ───────────────────────────────────────────────
+
+    #[test]
+    fn test_cmd_create_list_delete_tag() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let tag = super::create_tag(
+            path.clone(),
+            "v1.0.0".to_string(),
+            "main".to_string(),
+            "Release 1.0.0".to_string(),
+        )
+        .unwrap();
+        assert!(tag.annotated);
+
+        let tags = super::list_tags(path.clone()).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "v1.0.0");
+
+        super::delete_tag(path.clone(), "v1.0.0".to_string()).unwrap();
+        assert!(super::list_tags(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_cmd_get_release_range_unknown_tag() {
+        let (dir, _repo) = init_test_repo();
+        let path = dir.path().to_str().unwrap().to_string();
+        let err =
+            super::get_release_range(path, "v1".to_string(), "v2".to_string(), None).unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::Git));
+    }
+
     // ── get_repo_info コマンドテスト ──────────────────────────────────────
 
     #[test]
@@ -1407,6 +2315,20 @@ mod tests {
         assert!(matches!(err.kind, ErrorKind::Git));
     }
 
+    #[test]
+    fn test_cmd_get_repo_remotes_fork() {
+        let (dir, repo) = init_test_repo();
+        repo.remote("origin", "git@github.com:me/reown.git")
+            .unwrap();
+        repo.remote("upstream", "https://github.com/example/reown.git")
+            .unwrap();
+        let path = dir.path().to_str().unwrap().to_string();
+        let remotes = super::get_repo_remotes(path).unwrap();
+        assert_eq!(remotes.remotes.len(), 2);
+        assert_eq!(remotes.upstream.as_deref(), Some("upstream"));
+        assert!(remotes.is_fork);
+    }
+
     // ── extract_todos コマンドテスト ──────────────────────────────────────
 
     #[test]
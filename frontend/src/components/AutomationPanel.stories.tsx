@@ -8,6 +8,7 @@ const meta = {
   args: {
     owner: "example",
     repo: "reown",
+    repoPath: fixtures.repoInfo.path,
   },
   decorators: [
     (Story) => {
@@ -19,9 +19,14 @@ type Phase = "idle" | "evaluating" | "confirm" | "executing" | "done";
 interface AutomationPanelProps {
   owner: string;
   repo: string;
+  repoPath?: string | null;
 }
 
-export function AutomationPanel({ owner, repo }: AutomationPanelProps) {
+export function AutomationPanel({
+  owner,
+  repo,
+  repoPath,
+}: AutomationPanelProps) {
   const { t } = useTranslation();
   const [phase, setPhase] = useState<Phase>("idle");
   const [candidates, setCandidates] = useState<AutoApproveCandidate[]>([]);
@@ -48,6 +53,7 @@ export function AutomationPanel({ owner, repo }: AutomationPanelProps) {
       const result = await invoke("evaluate_auto_approve_candidates", {
         owner,
         repo,
+        repoPath: repoPath ?? undefined,
       });
       setCandidates(result);
       setEvaluated(true);
@@ -60,7 +66,7 @@ export function AutomationPanel({ owner, repo }: AutomationPanelProps) {
       setError(String(err));
       setPhase("idle");
     }
-  }, [owner, repo]);
+  }, [owner, repo, repoPath]);
 
   const handleExecute = useCallback(async () => {
     if (!automationConfig || candidates.length === 0) return;
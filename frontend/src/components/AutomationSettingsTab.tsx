@@ -21,6 +21,8 @@ const ALL_CATEGORIES: ChangeCategory[] = [
   "Documentation",
   "CI",
   "Dependency",
+  "Submodule",
+  "Asset",
   "Other",
 ];
 
@@ -32,6 +34,8 @@ const CATEGORY_LABEL_KEYS: Record<ChangeCategory, string> = {
   Documentation: "pr.categoryDocumentation",
   Dependency: "pr.categoryDependency",
   Refactor: "pr.categoryRefactor",
+  Submodule: "pr.categorySubmodule",
+  Asset: "pr.categoryAsset",
   Other: "pr.categoryOther",
 };
 
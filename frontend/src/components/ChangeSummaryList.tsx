@@ -21,6 +21,8 @@ const categoryIcons: Record<ChangeCategory, string> = {
   Documentation: "\u{1F4DD}",
   CI: "\u{1F6E0}\uFE0F",
   Dependency: "\u{1F4E6}",
+  Submodule: "\u{1F517}",
+  Asset: "\u{1F5BC}\uFE0F",
   Other: "\u{1F4C4}",
 };
 
@@ -32,6 +34,8 @@ const categoryLabelKeys: Record<ChangeCategory, string> = {
   Documentation: "pr.categoryDocumentation",
   CI: "pr.categoryCI",
   Dependency: "pr.categoryDependency",
+  Submodule: "pr.categorySubmodule",
+  Asset: "pr.categoryAsset",
   Other: "pr.categoryOther",
 };
 
@@ -44,6 +48,8 @@ const allCategories: ChangeCategory[] = [
   "Documentation",
   "CI",
   "Dependency",
+  "Submodule",
+  "Asset",
   "Other",
 ];
 
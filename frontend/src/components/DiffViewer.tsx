@@ -1,5 +1,6 @@
+import type { ReactNode } from "react";
 import { useTranslation } from "react-i18next";
-import type { FileDiff, DiffChunk } from "../types";
+import type { FileDiff, DiffChunk, DiffLineInfo } from "../types";
 import { Badge } from "./Badge";
 
 interface DiffViewerProps {
@@ -15,6 +16,7 @@ const statusBadgeVariant: Record<
   Deleted: "danger",
   Modified: "default",
   Renamed: "info",
+  Copied: "info",
   Other: "default",
 };
 
@@ -25,6 +27,27 @@ function getOriginString(
   return "Other";
 }
 
+function renderLineContent(line: DiffLineInfo): ReactNode {
+  const ranges = line.highlights ?? [];
+  if (ranges.length === 0) return line.content;
+
+  // highlights はコードポイント単位のオフセットなので Array.from で分割する
+  const chars = Array.from(line.content);
+  const parts: ReactNode[] = [];
+  let pos = 0;
+  ranges.forEach((range, i) => {
+    if (range.start > pos) parts.push(chars.slice(pos, range.start).join(""));
+    parts.push(
+      <mark key={i} className="diff-word-highlight">
+        {chars.slice(range.start, range.end).join("")}
+      </mark>
+    );
+    pos = range.end;
+  });
+  if (pos < chars.length) parts.push(chars.slice(pos).join(""));
+  return parts;
+}
+
 function ChunkView({ chunk }: { chunk: DiffChunk }) {
   return (
     <div>
@@ -57,7 +80,7 @@ function ChunkView({ chunk }: { chunk: DiffChunk }) {
             </span>
             <span className={`flex-1 px-2 ${textColor}`}>
               {prefix}
-              {line.content}
+              {renderLineContent(line)}
             </span>
           </div>
         );
@@ -83,6 +106,7 @@ function filePath(diff: FileDiff): string {
 export function DiffViewer({ diff, className = "" }: DiffViewerProps) {
   const { t } = useTranslation();
   const path = filePath(diff);
+  const hasSourcePath = diff.status === "Renamed" || diff.status === "Copied";
 
   return (
     <div
@@ -94,7 +118,7 @@ export function DiffViewer({ diff, className = "" }: DiffViewerProps) {
         <span className="truncate font-mono text-[0.8rem] font-semibold text-text-heading">
           {path}
         </span>
-        {diff.status === "Renamed" && diff.old_path && (
+        {hasSourcePath && diff.old_path && (
           <span className="truncate text-[0.75rem] text-text-muted">
             ← {diff.old_path}
           </span>
@@ -15,6 +15,8 @@ function statusLabel(status: string): string {
       return "M";
     case "Renamed":
       return "R";
+    case "Copied":
+      return "C";
     default:
       return "?";
   }
@@ -30,6 +32,8 @@ function statusAriaLabel(status: string): string {
       return "Modified";
     case "Renamed":
       return "Renamed";
+    case "Copied":
+      return "Copied";
     default:
       return "Unknown";
   }
@@ -46,6 +50,7 @@ function statusVariant(
     case "Modified":
       return "warning";
     case "Renamed":
+    case "Copied":
       return "info";
     default:
       return "default";
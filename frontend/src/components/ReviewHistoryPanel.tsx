@@ -19,6 +19,8 @@ const categoryBadgeVariant: Record<
   Documentation: "default",
   CI: "accent",
   Dependency: "default",
+  Submodule: "default",
+  Asset: "default",
   Other: "default",
 };
 
@@ -40,6 +40,8 @@ const categoryBadgeVariant: Record<
   Documentation: "default",
   CI: "accent",
   Dependency: "default",
+  Submodule: "default",
+  Asset: "default",
   Other: "default",
 };
 
@@ -177,6 +179,7 @@ export function ReviewTab({
       owner,
       repo,
       prNumber: matchedPr.number,
+      repoPath: repoPath ?? undefined,
     })
       .then(setAnalysisResult)
       .catch(() => {});
@@ -189,7 +192,7 @@ export function ReviewTab({
     })
       .then(setHybridResult)
       .catch(() => {});
-  }, [matchedPr, repoInfo]);
+  }, [matchedPr, repoInfo, repoPath]);
 
   const selectedDiff = selectedIndex >= 0 ? diffs[selectedIndex] : null;
   const selectedPrDiff =
@@ -493,7 +496,7 @@ function PrAnalysisSection({
       />
 
       {/* Automation panel */}
-      <AutomationPanel owner={owner} repo={repo} />
+      <AutomationPanel owner={owner} repo={repo} repoPath={repoPath} />
     </div>
   );
 }
@@ -1,9 +1,30 @@
 import { invoke as tauriInvoke } from "@tauri-apps/api/core";
 import type {
   WorktreeInfo,
+  WorktreeStatus,
+  RemoveWorktreeOptions,
   BranchInfo,
   EnrichedBranchInfo,
+  SwitchOptions,
+  SwitchResult,
+  StashEntry,
+  StashApplyResult,
+  JournalEntry,
+  RebaseResult,
   FileDiff,
+  DiffRequest,
+  CompareMode,
+  BranchComparison,
+  WorkdirChanges,
+  HunkSelection,
+  CommitRequest,
+  CommitResult,
+  PickOptions,
+  PickResult,
+  LogFilter,
+  LogEntry,
+  TagInfo,
+  ReleaseRange,
   CategorizedFileDiff,
   PrInfo,
   CommitInfo,
@@ -13,9 +34,12 @@ import type {
   AutomationConfig,
   RiskConfig,
   RepoInfo,
+  RepoRemotes,
   PrSummary,
   ConsistencyResult,
   AnalysisResult,
+  MergeCheck,
+  OverlapMatrix,
   HybridAnalysisResult,
   ReviewEvent,
   ReviewRecord,
@@ -23,33 +47,159 @@ import type {
   ReviewSuggestion,
   AutoApproveCandidate,
   AutoApproveResult,
+  RevertTarget,
+  RevertPrResult,
   AutoApproveWithMergeResult,
   DeviceFlowResponse,
 } from "./types";
 
 export type Commands = {
   list_worktrees: { args: { repoPath: string }; ret: WorktreeInfo[] };
+  list_worktree_statuses: {
+    args: { repoPath: string };
+    ret: WorktreeStatus[];
+  };
   add_worktree: {
     args: { repoPath: string; worktreePath: string; branch: string };
     ret: void;
   };
+  remove_worktree: {
+    args: { repoPath: string; name: string; options?: RemoveWorktreeOptions };
+    ret: void;
+  };
+  prune_worktrees: { args: { repoPath: string }; ret: string[] };
+  lock_worktree: {
+    args: { repoPath: string; name: string; reason?: string };
+    ret: void;
+  };
+  unlock_worktree: { args: { repoPath: string; name: string }; ret: void };
+  move_worktree: {
+    args: { repoPath: string; name: string; newPath: string };
+    ret: void;
+  };
+  rebase_worktree: {
+    args: { worktreePath: string; onto: string };
+    ret: RebaseResult;
+  };
+  continue_rebase: { args: { worktreePath: string }; ret: RebaseResult };
+  abort_rebase: { args: { worktreePath: string }; ret: void };
   list_branches: { args: { repoPath: string }; ret: BranchInfo[] };
   list_enriched_branches: {
     args: { repoPath: string; pullRequests: PrInfo[] };
     ret: EnrichedBranchInfo[];
   };
+  cleanup_merged_branches: {
+    args: { repoPath: string; pullRequests: PrInfo[] };
+    ret: string[];
+  };
+  list_operations: { args: { repoPath: string }; ret: JournalEntry[] };
+  undo_last_operation: {
+    args: { repoPath: string };
+    ret: JournalEntry | null;
+  };
   create_branch: { args: { repoPath: string; name: string }; ret: void };
-  switch_branch: { args: { repoPath: string; name: string }; ret: void };
+  switch_branch: {
+    args: { repoPath: string; name: string; options?: SwitchOptions };
+    ret: SwitchResult;
+  };
   delete_branch: { args: { repoPath: string; name: string }; ret: void };
-  diff_workdir: { args: { repoPath: string }; ret: FileDiff[] };
+  stash_save: {
+    args: { repoPath: string; message?: string; includeUntracked?: boolean };
+    ret: string | null;
+  };
+  list_stashes: { args: { repoPath: string }; ret: StashEntry[] };
+  stash_apply: {
+    args: { repoPath: string; index: number };
+    ret: StashApplyResult;
+  };
+  stash_pop: {
+    args: { repoPath: string; index: number };
+    ret: StashApplyResult;
+  };
+  stash_drop: { args: { repoPath: string; index: number }; ret: void };
+  diff_workdir: {
+    args: { repoPath: string; options?: DiffRequest };
+    ret: FileDiff[];
+  };
+  diff_workdir_grouped: {
+    args: { repoPath: string; options?: DiffRequest };
+    ret: WorkdirChanges;
+  };
   diff_commit: {
-    args: { repoPath: string; commitSha: string };
+    args: { repoPath: string; commitSha: string; options?: DiffRequest };
     ret: FileDiff[];
   };
   diff_branches: {
-    args: { repoPath: string; baseRef: string; headRef: string };
+    args: {
+      repoPath: string;
+      baseRef: string;
+      headRef: string;
+      options?: DiffRequest;
+    };
     ret: FileDiff[];
   };
+  compare_branches: {
+    args: {
+      repoPath: string;
+      baseRef: string;
+      headRef: string;
+      mode?: CompareMode;
+      options?: DiffRequest;
+    };
+    ret: BranchComparison;
+  };
+  stage_hunks: {
+    args: { repoPath: string; selections: HunkSelection[] };
+    ret: void;
+  };
+  unstage_hunks: {
+    args: { repoPath: string; selections: HunkSelection[] };
+    ret: void;
+  };
+  discard_hunks: {
+    args: { repoPath: string; selections: HunkSelection[] };
+    ret: void;
+  };
+  stage_files: { args: { repoPath: string; paths: string[] }; ret: void };
+  unstage_files: { args: { repoPath: string; paths: string[] }; ret: void };
+  discard_files: { args: { repoPath: string; paths: string[] }; ret: void };
+  create_commit: {
+    args: { worktreePath: string; request: CommitRequest };
+    ret: CommitResult;
+  };
+  draft_commit_message: {
+    args: { worktreePath: string };
+    ret: string;
+  };
+  cherry_pick_commit: {
+    args: { worktreePath: string; rev: string; options?: PickOptions };
+    ret: PickResult;
+  };
+  revert_commit: {
+    args: { worktreePath: string; rev: string; options?: PickOptions };
+    ret: PickResult;
+  };
+  continue_pick: { args: { worktreePath: string }; ret: PickResult };
+  abort_pick: { args: { worktreePath: string }; ret: void };
+  list_local_commits: {
+    args: { repoPath: string; filter?: LogFilter };
+    ret: LogEntry[];
+  };
+  list_tags: { args: { repoPath: string }; ret: TagInfo[] };
+  create_tag: {
+    args: { repoPath: string; name: string; target: string; message: string };
+    ret: TagInfo;
+  };
+  delete_tag: { args: { repoPath: string; name: string }; ret: void };
+  get_release_range: {
+    args: {
+      repoPath: string;
+      from: string;
+      to: string;
+      options?: DiffRequest;
+    };
+    ret: ReleaseRange;
+  };
   list_pull_requests: {
     args: { owner: string; repo: string };
     ret: PrInfo[];
@@ -72,10 +222,20 @@ export type Commands = {
     };
     ret: void;
   };
+  update_pr_branch: {
+    args: {
+      owner: string;
+      repo: string;
+      prNumber: number;
+      expectedHeadSha?: string;
+    };
+    ret: string;
+  };
   get_repo_info: {
     args: { repoPath: string };
     ret: RepoInfo;
   };
+  get_repo_remotes: { args: { repoPath: string }; ret: RepoRemotes };
   add_repository: {
     args: { path: string };
     ret: RepositoryEntry;
@@ -94,6 +254,10 @@ export type Commands = {
     args: { owner: string; repo: string; prNumber: number };
     ret: PrSummary;
   };
+  summarize_release: {
+    args: { repoPath: string; from: string; to: string };
+    ret: PrSummary;
+  };
   check_pr_consistency: {
     args: { owner: string; repo: string; prNumber: number };
     ret: ConsistencyResult;
@@ -112,10 +276,27 @@ export type Commands = {
     args: { endpoint: string; model: string; apiKey?: string };
     ret: void;
   };
+  check_pr_mergeability: {
+    args: { repoPath: string; pr: PrInfo };
+    ret: MergeCheck;
+  };
   analyze_pr_risk: {
-    args: { owner: string; repo: string; prNumber: number };
+    args: {
+      owner: string;
+      repo: string;
+      prNumber: number;
+      repoPath?: string;
+    };
     ret: AnalysisResult;
   };
+  analyze_release_risk: {
+    args: { repoPath: string; from: string; to: string };
+    ret: AnalysisResult;
+  };
+  compute_pr_overlap: {
+    args: { owner: string; repo: string; repoPath?: string };
+    ret: OverlapMatrix;
+  };
   analyze_pr_risk_with_llm: {
     args: { owner: string; repo: string; prNumber: number };
     ret: HybridAnalysisResult;
@@ -146,11 +327,11 @@ export type Commands = {
   };
   load_default_risk_config: { args?: Record<string, unknown>; ret: RiskConfig };
   evaluate_auto_approve_candidates: {
-    args: { owner: string; repo: string };
+    args: { owner: string; repo: string; repoPath?: string };
     ret: AutoApproveCandidate[];
   };
   run_auto_approve: {
-    args: { owner: string; repo: string };
+    args: { owner: string; repo: string; repoPath?: string };
     ret: AutoApproveResult;
   };
   run_auto_approve_with_merge: {
@@ -162,11 +343,24 @@ export type Commands = {
     };
     ret: AutoApproveWithMergeResult;
   };
+  create_revert_pr: {
+    args: {
+      repoPath: string;
+      owner: string;
+      repo: string;
+      target: RevertTarget;
+    };
+    ret: RevertPrResult;
+  };
   extract_todos: { args: { repoPath: string }; ret: TodoItem[] };
   create_worktree_for_todo: {
     args: { repoPath: string; filePath: string; lineNumber: number };
     ret: WorktreeInfo;
   };
+  create_worktree_for_pr: {
+    args: { repoPath: string; prNumber: number };
+    ret: WorktreeInfo;
+  };
   suggest_review_comments: {
     args: { owner: string; repo: string; prNumber: number };
     ret: ReviewSuggestion[];
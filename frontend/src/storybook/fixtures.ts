@@ -11,6 +11,7 @@ import type {
   CategorizedFileDiff,
   PrInfo,
   CommitInfo,
+  TagInfo,
   RepositoryEntry,
   RepoInfo,
   AppConfig,
@@ -316,6 +317,27 @@ const commits: CommitInfo[] = [
   },
 ];
 
+const tags: TagInfo[] = [
+  {
+    name: "v0.2.0",
+    target: "abc1234567890abcdef1234567890abcdef123456",
+    annotated: true,
+    message: "Release 0.2.0",
+    tagger: "dev-user",
+    date: "2025-01-15T12:00:00Z",
+    time: 1736942400,
+  },
+  {
+    name: "v0.1.0",
+    target: "mno3456abcdef7890123abcdef4567890abcdef12",
+    annotated: false,
+    message: null,
+    tagger: null,
+    date: "2025-01-15T08:00:00Z",
+    time: 1736928000,
+  },
+];
+
 const repositories: RepositoryEntry[] = [
   { name: "reown", path: "/Users/dev/project" },
   { name: "other-project", path: "/Users/dev/other-project" },
@@ -664,6 +686,7 @@ export const fixtures = {
   diverseCategorizedFileDiffs,
   pullRequests,
   commits,
+  tags,
   repositories,
   repoInfo,
   llmConfig,
@@ -15,42 +15,170 @@ type CommandHandlers = {
 
 const defaultHandlers: CommandHandlers = {
   list_worktrees: () => fixtures.worktrees,
+  list_worktree_statuses: () =>
+    fixtures.worktrees.map((wt) => ({
+      ...wt,
+      missing: false,
+      staged: 0,
+      dirty: 0,
+      untracked: 0,
+      conflicted: 0,
+      upstream: null,
+      default_branch: "origin/main",
+      against_default: { ahead: 0, behind: 0 },
+      last_commit_time: null,
+      merge_in_progress: false,
+      rebase_in_progress: false,
+    })),
   add_worktree: () => undefined as never,
+  remove_worktree: () => undefined as never,
+  prune_worktrees: () => [],
+  lock_worktree: () => undefined as never,
+  unlock_worktree: () => undefined as never,
+  move_worktree: () => undefined as never,
+  rebase_worktree: () => ({
+    status: "Completed",
+    applied: 0,
+    total: 0,
+    stopped_at: null,
+    conflicting_paths: [],
+    head: null,
+  }),
+  continue_rebase: () => ({
+    status: "Completed",
+    applied: 0,
+    total: 0,
+    stopped_at: null,
+    conflicting_paths: [],
+    head: null,
+  }),
+  abort_rebase: () => undefined as never,
   list_branches: () => fixtures.branches,
   list_enriched_branches: () => fixtures.enrichedBranches,
+  cleanup_merged_branches: () => [],
+  list_operations: () => [],
+  undo_last_operation: () => null,
   create_branch: () => undefined as never,
-  switch_branch: () => undefined as never,
+  switch_branch: () => ({
+    stashed: false,
+    restored: false,
+    conflicting_paths: [],
+  }),
   delete_branch: () => undefined as never,
+  stash_save: () => null,
+  list_stashes: () => [],
+  stash_apply: () => ({ applied: true, conflicting_paths: [] }),
+  stash_pop: () => ({ applied: true, conflicting_paths: [] }),
+  stash_drop: () => undefined as never,
   diff_workdir: () => fixtures.fileDiffs,
+  diff_workdir_grouped: () => ({
+    staged: [],
+    unstaged: fixtures.fileDiffs,
+    untracked: [],
+  }),
   diff_commit: () => fixtures.fileDiffs,
   diff_branches: () => fixtures.fileDiffs,
+  compare_branches: () => ({
+    merge_base: null,
+    ahead: 0,
+    behind: 0,
+    files: fixtures.fileDiffs,
+  }),
+  stage_hunks: () => undefined as never,
+  unstage_hunks: () => undefined as never,
+  discard_hunks: () => undefined as never,
+  stage_files: () => undefined as never,
+  unstage_files: () => undefined as never,
+  discard_files: () => undefined as never,
+  create_commit: () => ({
+    sha: "abc1234def5678",
+    summary: "Update hello.txt",
+  }),
+  draft_commit_message: () => "Update hello.txt\n",
+  cherry_pick_commit: () => ({ commit: "abc1234", conflicting_paths: [] }),
+  revert_commit: () => ({ commit: "abc1234", conflicting_paths: [] }),
+  continue_pick: () => ({ commit: "abc1234", conflicting_paths: [] }),
+  abort_pick: () => undefined as never,
+  list_local_commits: () => [],
+  list_tags: () => fixtures.tags,
+  create_tag: () => fixtures.tags[0],
+  delete_tag: () => undefined as never,
+  get_release_range: () => ({
+    from: fixtures.tags[1],
+    to: fixtures.tags[0],
+    commits: [],
+    files: fixtures.fileDiffs,
+  }),
   list_pull_requests: () => fixtures.pullRequests,
   get_pull_request_files: () => fixtures.categorizedFileDiffs,
   list_pr_commits: () => fixtures.commits,
   submit_pr_review: () => undefined as never,
+  update_pr_branch: () => "Updating pull request branch.",
   get_repo_info: () => fixtures.repoInfo,
+  get_repo_remotes: () => ({
+    ...fixtures.repoInfo,
+    remotes: [
+      {
+        name: "origin",
+        url: "https://github.com/example/reown.git",
+        parsed: {
+          host: "github.com",
+          port: null,
+          owner: "example",
+          repo: "reown",
+          protocol: "https",
+        },
+      },
+    ],
+    upstream: "origin",
+    is_fork: false,
+  }),
   add_repository: () => fixtures.repositories[0],
   list_repositories: () => fixtures.repositories,
   remove_repository: () => undefined as never,
   save_app_config: () => undefined as never,
   load_app_config: () => fixtures.appConfig,
   summarize_pull_request: () => fixtures.prSummary,
+  summarize_release: () => fixtures.prSummary,
   check_pr_consistency: () => fixtures.consistencyResult,
   save_llm_config: () => undefined as never,
   load_llm_config: () => fixtures.llmConfig,
   save_llm_api_key: () => undefined as never,
   delete_llm_api_key: () => undefined as never,
   test_llm_connection: () => undefined as never,
+  check_pr_mergeability: () => ({
+    base: "",
+    head: "",
+    merge_base: null,
+    mergeable: true,
+    conflicts: [],
+  }),
   analyze_pr_risk: () => fixtures.analysisResult,
+  analyze_release_risk: () => fixtures.analysisResult,
+  compute_pr_overlap: () => ({ pr_numbers: [], pairs: [], merge_order: [] }),
   analyze_pr_risk_with_llm: () => fixtures.hybridAnalysisResult,
   save_automation_config: () => undefined as never,
   load_automation_config: () => fixtures.automationConfig,
   extract_todos: () => fixtures.todoItems,
   create_worktree_for_todo: () => fixtures.worktrees[1],
+  create_worktree_for_pr: () => fixtures.worktrees[1],
   suggest_review_comments: () => fixtures.reviewSuggestions,
   evaluate_auto_approve_candidates: () => [],
   run_auto_approve: () => ({ outcomes: [] }),
   run_auto_approve_with_merge: () => fixtures.autoApproveWithMergeResult,
+  create_revert_pr: () => ({
+    branch: {
+      branch: "revert/pr-1",
+      worktree: fixtures.worktrees[1],
+      base_branch: "main",
+      reverted_sha: "abc1234",
+      reverted_commits: ["abc1234"],
+      revert_commit: "def5678",
+      title: `Revert "${fixtures.pullRequests[0].title}"`,
+      body: "Reverts #1",
+    },
+    pr: fixtures.pullRequests[0],
+  }),
   load_risk_config: () => fixtures.automationConfig.risk_config,
   save_risk_config: () => undefined as never,
   load_default_risk_config: () => fixtures.automationConfig.risk_config,
@@ -4,6 +4,31 @@ export interface WorktreeInfo {
   branch: string | null;
   is_main: boolean;
   is_locked: boolean;
+  lock_reason?: string | null;
+}
+
+export interface AheadBehind {
+  ahead: number;
+  behind: number;
+}
+
+export interface WorktreeStatus extends WorktreeInfo {
+  missing: boolean;
+  staged: number;
+  dirty: number;
+  untracked: number;
+  conflicted: number;
+  upstream: AheadBehind | null;
+  default_branch: string | null;
+  against_default: AheadBehind | null;
+  last_commit_time: number | null;
+  merge_in_progress: boolean;
+  rebase_in_progress: boolean;
+}
+
+export interface RemoveWorktreeOptions {
+  force?: boolean;
+  delete_branch?: boolean;
 }
 
 export interface BranchInfo {
@@ -18,17 +43,75 @@ export interface EnrichedBranchInfo {
   upstream: string | null;
   is_local: boolean;
   is_remote: boolean;
+  remotes?: string[];
+  upstream_remote?: string | null;
   has_worktree: boolean;
   worktree_path: string | null;
   pr_number: number | null;
   pr_title: string | null;
+  upstream_ahead_behind?: AheadBehind | null;
+  base_branch?: string | null;
+  base_ahead_behind?: AheadBehind | null;
+  last_commit_sha?: string | null;
+  last_commit_author?: string | null;
+  last_commit_time?: number | null;
+  is_merged?: boolean;
+}
+
+export interface SwitchOptions {
+  auto_stash?: boolean;
+}
+
+export interface SwitchResult {
+  stashed: boolean;
+  restored: boolean;
+  conflicting_paths: string[];
+}
+
+export interface StashEntry {
+  index: number;
+  message: string;
+  oid: string;
+  time: number;
 }
 
+export interface StashApplyResult {
+  applied: boolean;
+  conflicting_paths: string[];
+}
+
+export type RebaseStatus = "Completed" | "UpToDate" | "Conflicts";
+
+export interface RebaseResult {
+  status: RebaseStatus;
+  applied: number;
+  total: number;
+  stopped_at: string | null;
+  conflicting_paths: string[];
+  head: string | null;
+}
+
+export type Operation =
+  | { op: "delete_branch"; branch: string; upstream: string | null }
+  | { op: "switch_branch"; from: string | null; to: string };
+
+export type JournalEntry = Operation & {
+  target: string;
+  time: number;
+};
+
 export interface DiffLineInfo {
   origin: "Addition" | "Deletion" | "Context" | { Other: string };
   old_lineno: number | null;
   new_lineno: number | null;
   content: string;
+  highlights?: HighlightRange[];
+}
+
+/** 行内で変更された範囲（コードポイント単位、end は含まない） */
+export interface HighlightRange {
+  start: number;
+  end: number;
 }
 
 export interface DiffChunk {
@@ -39,10 +122,94 @@ export interface DiffChunk {
 export interface FileDiff {
   old_path: string | null;
   new_path: string | null;
-  status: "Added" | "Deleted" | "Modified" | "Renamed" | "Other";
+  status: "Added" | "Deleted" | "Modified" | "Renamed" | "Copied" | "Other";
+  similarity?: number | null;
+  kind?: FileKind;
+  old_blob?: BlobInfo | null;
+  new_blob?: BlobInfo | null;
+  submodule?: SubmoduleChange | null;
   chunks: DiffChunk[];
 }
 
+export type FileKind = "Text" | "Binary" | "LfsPointer" | "Submodule";
+
+export interface BlobInfo {
+  oid: string;
+  size: number;
+}
+
+export interface SubmoduleChange {
+  old_commit: string | null;
+  new_commit: string | null;
+}
+
+export interface RenameDetection {
+  copies: boolean;
+  threshold: number;
+}
+
+export type WhitespaceMode =
+  | "Show"
+  | "IgnoreAll"
+  | "IgnoreChange"
+  | "IgnoreEol";
+
+/** diff_* コマンド共通のオプション（省略したフィールドはデフォルト値） */
+export interface DiffRequest {
+  context_lines?: number | null;
+  whitespace?: WhitespaceMode;
+  pathspecs?: string[];
+  include_untracked?: boolean;
+  max_file_size?: number | null;
+  renames?: RenameDetection | null;
+}
+
+export type CompareMode = "Direct" | "MergeBase";
+
+export interface BranchComparison {
+  merge_base: string | null;
+  ahead: number;
+  behind: number;
+  files: FileDiff[];
+}
+
+export interface WorkdirChanges {
+  staged: FileDiff[];
+  unstaged: FileDiff[];
+  untracked: FileDiff[];
+}
+
+export interface LineSelection {
+  old_lineno: number | null;
+  new_lineno: number | null;
+}
+
+export interface HunkSelection {
+  path: string;
+  header: string;
+  lines?: LineSelection[] | null;
+}
+
+export interface CommitRequest {
+  message: string;
+  amend?: boolean;
+  sign_off?: boolean;
+}
+
+export interface CommitResult {
+  sha: string;
+  summary: string;
+}
+
+export interface PickOptions {
+  mainline?: number | null;
+}
+
+export interface PickResult {
+  commit: string | null;
+  conflicting_paths: string[];
+}
+
 export interface CategorizedFileDiff extends FileDiff {
   category: ChangeCategory;
 }
@@ -52,7 +219,9 @@ export interface PrInfo {
   title: string;
   author: string;
   state: string;
+  merge_commit_sha?: string | null;
   head_branch: string;
+  head_repo?: string | null;
   base_branch: string;
   updated_at: string;
   additions: number;
@@ -62,12 +231,53 @@ export interface PrInfo {
   html_url: string;
 }
 
+export type SignatureStatus = "Signed" | "Unverified" | "Unsigned" | "Invalid";
+
 export interface CommitInfo {
   sha: string;
   message: string;
   author: string;
   date: string;
   commit_url: string;
+  signature?: SignatureStatus;
+}
+
+export interface LogFilter {
+  range?: string | null;
+  path?: string | null;
+  author?: string | null;
+  since?: number | null;
+  until?: number | null;
+  first_parent?: boolean;
+  limit?: number | null;
+}
+
+/** ローカルコミット。sha/message/author/date は CommitInfo と共通 */
+export interface LogEntry extends Omit<CommitInfo, "commit_url"> {
+  summary: string;
+  author_email: string;
+  author_time: number;
+  committer: string;
+  commit_time: number;
+  parents: string[];
+}
+
+export interface TagInfo {
+  name: string;
+  target: string;
+  annotated: boolean;
+  message: string | null;
+  tagger: string | null;
+  date: string;
+  time: number;
+}
+
+/** 2つのタグ間のコミットと差分 */
+export interface ReleaseRange {
+  from: TagInfo;
+  to: TagInfo;
+  commits: LogEntry[];
+  files: FileDiff[];
 }
 
 export interface RepositoryEntry {
@@ -75,12 +285,36 @@ export interface RepositoryEntry {
   path: string;
 }
 
+export type RemoteProtocol = "https" | "http" | "ssh" | "git";
+
+export interface RemoteUrl {
+  host: string;
+  port: number | null;
+  owner: string;
+  repo: string;
+  protocol: RemoteProtocol;
+}
+
 export interface RepoInfo {
   path: string;
   name: string;
   remote_url: string | null;
   github_owner: string | null;
   github_repo: string | null;
+  remote?: RemoteUrl | null;
+}
+
+export interface RemoteInfo {
+  name: string;
+  url: string;
+  parsed: RemoteUrl | null;
+}
+
+/** 全リモートを含むリポジトリ情報。upstream は PR の向き先になるリモート名 */
+export interface RepoRemotes extends RepoInfo {
+  remotes: RemoteInfo[];
+  upstream: string | null;
+  is_fork: boolean;
 }
 
 export interface LlmConfig {
@@ -109,6 +343,7 @@ export interface RiskConfig {
   file_count_thresholds: [number, number][];
   line_count_thresholds: [number, number][];
   missing_test_penalty: number;
+  merge_conflict_penalty?: number;
   risk_thresholds: RiskThresholds;
 }
 
@@ -118,6 +353,7 @@ export interface AutomationConfig {
   enable_auto_merge: boolean;
   auto_merge_method: ConfigMergeMethod;
   risk_config: RiskConfig;
+  require_signed_commits?: boolean;
 }
 
 export interface AppConfig {
@@ -181,6 +417,8 @@ export type ChangeCategory =
   | "Documentation"
   | "CI"
   | "Dependency"
+  | "Submodule"
+  | "Asset"
   | "Other";
 
 export interface RiskFactor {
@@ -220,6 +458,53 @@ export interface AnalysisResult {
   risk: RiskScore;
   files: FileAnalysis[];
   summary: AnalysisSummary;
+  merge_check?: MergeCheck | null;
+  merge_check_error?: string | null;
+  unsigned_commits?: string[] | null;
+}
+
+export type ConflictKind = "Content" | "AddAdd" | "ModifyDelete" | "Other";
+
+export interface ConflictHunk {
+  ours_start: number;
+  ours: string[];
+  theirs_start: number;
+  theirs: string[];
+}
+
+export interface MergeConflict {
+  path: string;
+  kind: ConflictKind;
+  hunks: ConflictHunk[];
+}
+
+export interface MergeCheck {
+  base: string;
+  head: string;
+  merge_base: string | null;
+  mergeable: boolean;
+  conflicts: MergeConflict[];
+}
+
+export interface PrPairOverlap {
+  pr_a: number;
+  pr_b: number;
+  shared_files: string[];
+  overlapping_files: string[];
+  conflict: boolean | null;
+  conflicting_paths: string[];
+}
+
+export interface MergeStep {
+  pr_number: number;
+  needs_rebase: boolean;
+  conflicts_with: number[];
+}
+
+export interface OverlapMatrix {
+  pr_numbers: number[];
+  pairs: PrPairOverlap[];
+  merge_order: MergeStep[];
 }
 
 export interface AffectedModule {
@@ -288,6 +573,26 @@ export interface AutoApproveWithMergeResult {
   merge_method: ConfigMergeMethod;
 }
 
+export type RevertTarget =
+  | { type: "pr"; pr: PrInfo }
+  | { type: "commit"; sha: string; base_branch: string };
+
+export interface RevertBranch {
+  branch: string;
+  worktree: WorktreeInfo;
+  base_branch: string;
+  reverted_sha: string;
+  reverted_commits?: string[];
+  revert_commit: string;
+  title: string;
+  body: string;
+}
+
+export interface RevertPrResult {
+  branch: RevertBranch;
+  pr: PrInfo;
+}
+
 // ── Review Pattern Types ────────────────────────────────────────────────────
 
 export interface CategoryStat {
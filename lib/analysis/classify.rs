@@ -1,4 +1,4 @@
-use crate::git::diff::{FileDiff, LineOrigin};
+use crate::git::diff::{FileDiff, FileKind, FileStatus, LineOrigin};
 
 /// FileDiff にカテゴリ情報を付加した構造体
 #[derive(Debug, Clone, serde::Serialize)]
@@ -36,6 +36,10 @@ pub enum ChangeCategory {
     CI,
     /// 依存関係の変更
     Dependency,
+    /// サブモジュールの参照コミット更新
+    Submodule,
+    /// バイナリ・LFS 管理ファイルの追加・差し替え
+    Asset,
     /// その他
     Other,
 }
@@ -44,6 +48,13 @@ pub enum ChangeCategory {
 ///
 /// ファイルパスと拡張子、変更内容のパターンマッチで判定する。
 pub fn classify_file_change(diff: &FileDiff) -> ChangeCategory {
+    // 行単位の差分を持たないファイルは内容の種別で判定する
+    match diff.kind {
+        FileKind::Submodule => return ChangeCategory::Submodule,
+        FileKind::Binary | FileKind::LfsPointer => return ChangeCategory::Asset,
+        FileKind::Text => {}
+    }
+
     let path = effective_path(diff);
 
     // テストファイルの判定（パスベース）
@@ -193,8 +204,12 @@ fn is_source_file(path: &str) -> bool {
 fn classify_source_change(diff: &FileDiff) -> ChangeCategory {
     let (additions, deletions) = count_changes(diff);
 
-    // 変更がない場合（パーミッション変更など）
+    // 内容の変更を伴わないリネーム・コピー → ファイル移動のみのリファクタ
     if additions == 0 && deletions == 0 {
+        if matches!(diff.status, FileStatus::Renamed | FileStatus::Copied) {
+            return ChangeCategory::Refactor;
+        }
+        // 変更がない場合（パーミッション変更など）
         return ChangeCategory::Other;
     }
 
@@ -243,7 +258,7 @@ fn file_name(path: &str) -> &str {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::diff::{DiffChunk, DiffLineInfo, FileStatus};
+    use crate::git::diff::{DiffChunk, DiffLineInfo};
 
     fn make_diff(path: &str, status: FileStatus, additions: usize, deletions: usize) -> FileDiff {
         let mut lines = Vec::new();
@@ -253,6 +268,7 @@ mod tests {
                 old_lineno: None,
                 new_lineno: Some(1),
                 content: "added line\n".to_string(),
+                highlights: Vec::new(),
             });
         }
         for _ in 0..deletions {
@@ -261,6 +277,7 @@ mod tests {
                 old_lineno: Some(1),
                 new_lineno: None,
                 content: "removed line\n".to_string(),
+                highlights: Vec::new(),
             });
         }
 
@@ -268,6 +285,11 @@ mod tests {
             old_path: Some(path.to_string()),
             new_path: Some(path.to_string()),
             status,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -1,1 +1,1 @@".to_string(),
                 lines,
@@ -367,6 +389,11 @@ mod tests {
             old_path: Some("old_test.rs".to_string()),
             new_path: Some("src/new_module.rs".to_string()),
             status: FileStatus::Renamed,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -1,1 +1,1 @@".to_string(),
                 lines: vec![
@@ -375,12 +402,14 @@ mod tests {
                         old_lineno: None,
                         new_lineno: Some(1),
                         content: "new\n".to_string(),
+                        highlights: Vec::new(),
                     },
                     DiffLineInfo {
                         origin: LineOrigin::Deletion,
                         old_lineno: Some(1),
                         new_lineno: None,
                         content: "old\n".to_string(),
+                        highlights: Vec::new(),
                     },
                 ],
             }],
@@ -394,4 +423,45 @@ mod tests {
         let diff = make_diff("package-lock.json", FileStatus::Modified, 100, 50);
         assert_eq!(classify_file_change(&diff), ChangeCategory::Dependency);
     }
+
+    #[test]
+    fn test_classify_pure_rename_is_refactor() {
+        let diff = FileDiff {
+            old_path: Some("src/old_name.rs".to_string()),
+            new_path: Some("src/new_name.rs".to_string()),
+            status: FileStatus::Renamed,
+            similarity: Some(100),
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
+            chunks: vec![],
+        };
+        assert_eq!(classify_file_change(&diff), ChangeCategory::Refactor);
+    }
+
+    #[test]
+    fn test_classify_unchanged_modified_source_is_other() {
+        let diff = make_diff("src/main.rs", FileStatus::Modified, 0, 0);
+        assert_eq!(classify_file_change(&diff), ChangeCategory::Other);
+    }
+
+    #[test]
+    fn test_classify_submodule_bump() {
+        let mut diff = make_diff("vendor/lib", FileStatus::Modified, 0, 0);
+        diff.kind = FileKind::Submodule;
+        assert_eq!(classify_file_change(&diff), ChangeCategory::Submodule);
+    }
+
+    #[test]
+    fn test_classify_binary_and_lfs_as_asset() {
+        let mut diff = make_diff("assets/logo.png", FileStatus::Modified, 0, 0);
+        diff.kind = FileKind::Binary;
+        assert_eq!(classify_file_change(&diff), ChangeCategory::Asset);
+
+        // LFS ポインタはテキストだが、パスに関わらずアセットとして扱う
+        let mut diff = make_diff("docs/video.mp4", FileStatus::Modified, 3, 3);
+        diff.kind = FileKind::LfsPointer;
+        assert_eq!(classify_file_change(&diff), ChangeCategory::Asset);
+    }
 }
@@ -606,6 +606,9 @@ mod tests {
                 has_test_changes: false,
                 categories: vec![],
             },
+            merge_check: None,
+            merge_check_error: None,
+            unsigned_commits: None,
         };
 
         let llm_result = LlmAnalysisResult {
@@ -648,6 +651,9 @@ mod tests {
                     count: 20,
                 }],
             },
+            merge_check: None,
+            merge_check_error: None,
+            unsigned_commits: None,
         };
 
         let llm_result = LlmAnalysisResult {
@@ -708,6 +714,9 @@ mod tests {
                     has_test_changes: true,
                     categories: vec![],
                 },
+                merge_check: None,
+                merge_check_error: None,
+                unsigned_commits: None,
             },
             llm_analysis: LlmAnalysisResult {
                 affected_modules: vec![],
@@ -1,5 +1,6 @@
 mod classify;
 pub mod llm_analysis;
+mod overlap;
 pub mod review_pattern;
 mod risk;
 
@@ -8,11 +9,16 @@ pub use llm_analysis::{
     analyze_pr_with_llm, merge_analysis, AffectedModule, BreakingChange, BreakingChangeSeverity,
     HybridAnalysisResult, LlmAnalysisResult,
 };
+pub use overlap::{
+    compute_overlap_matrix, refine_with_fetched_prs, refine_with_local_refs, MergeStep,
+    OverlapMatrix, PrPairOverlap,
+};
 pub use review_pattern::{
     analyze_review_patterns, suggest_review_focus, CategoryStat, RejectPathPattern,
     ReviewPatternStats, ReviewSuggestion, RiskStat, SuggestionSeverity,
 };
 pub use risk::{
-    analyze_pr_risk, analyze_pr_risk_with_config, AnalysisResult, AnalysisSummary, CategoryCount,
-    FileAnalysis, RiskFactor, RiskLevel, RiskScore,
+    analyze_pr_risk, analyze_pr_risk_with_config, analyze_release_risk, apply_commit_signatures,
+    apply_merge_check, apply_merge_check_error, apply_merge_check_with_config, AnalysisResult,
+    AnalysisSummary, CategoryCount, FileAnalysis, RiskFactor, RiskLevel, RiskScore,
 };
@@ -0,0 +1,423 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::Result;
+use serde::Serialize;
+
+use super::classify::effective_path;
+use crate::git::diff::FileDiff;
+use crate::git::merge::check_mergeability;
+use crate::git::remote::SyncProgress;
+use crate::git::worktree::fetch_pr_head;
+use crate::github::PrInfo;
+
+/// 2つのPRの重なり
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PrPairOverlap {
+    pub pr_a: u64,
+    pub pr_b: u64,
+    /// 両方のPRが変更しているファイル
+    pub shared_files: Vec<String>,
+    /// 変更箇所（hunk の範囲）まで重なっているファイル
+    pub overlapping_files: Vec<String>,
+    /// ローカル ref で試験マージした結果コンフリクトするか（未チェックなら `None`）
+    pub conflict: Option<bool>,
+    /// 試験マージでコンフリクトしたファイル
+    pub conflicting_paths: Vec<String>,
+}
+
+impl PrPairOverlap {
+    /// コンフリクトするとみなすか。試験マージ済みならその結果、
+    /// 未チェックなら hunk の重なりで判定する。
+    pub fn likely_conflict(&self) -> bool {
+        self.conflict.unwrap_or(!self.overlapping_files.is_empty())
+    }
+}
+
+/// マージ順の1ステップ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MergeStep {
+    pub pr_number: u64,
+    /// この順番でマージする時点で rebase が必要か
+    pub needs_rebase: bool,
+    /// 先にマージされ、このPRと衝突するPR
+    pub conflicts_with: Vec<u64>,
+}
+
+/// PR間の重なり・コンフリクトの行列と推奨マージ順
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OverlapMatrix {
+    /// 対象PR番号（入力順）
+    pub pr_numbers: Vec<u64>,
+    /// 共有ファイルがあるPRの組（重なりのない組は含まない）
+    pub pairs: Vec<PrPairOverlap>,
+    /// rebase が最小になるよう並べたマージ順
+    pub merge_order: Vec<MergeStep>,
+}
+
+impl OverlapMatrix {
+    /// 2つのPRの重なりを返す（順不同）
+    pub fn pair(&self, a: u64, b: u64) -> Option<&PrPairOverlap> {
+        self.pairs
+            .iter()
+            .find(|p| (p.pr_a, p.pr_b) == (a, b) || (p.pr_a, p.pr_b) == (b, a))
+    }
+}
+
+/// PR ごとの変更ファイル一覧から重なりの行列を計算する。
+///
+/// オープンでないPR（クローズ・マージ済み）は対象外で、
+/// ベースブランチが異なるPR同士は比較しない。
+pub fn compute_overlap_matrix(prs: &[(PrInfo, Vec<FileDiff>)]) -> OverlapMatrix {
+    let prs: Vec<&(PrInfo, Vec<FileDiff>)> =
+        prs.iter().filter(|(pr, _)| pr.state == "open").collect();
+    let touched: Vec<BTreeMap<String, Vec<(u32, u32)>>> =
+        prs.iter().map(|(_, diffs)| touched_ranges(diffs)).collect();
+
+    let mut pairs = Vec::new();
+    for i in 0..prs.len() {
+        for j in (i + 1)..prs.len() {
+            if prs[i].0.base_branch != prs[j].0.base_branch {
+                continue;
+            }
+            let mut shared_files = Vec::new();
+            let mut overlapping_files = Vec::new();
+            for (path, ranges_a) in &touched[i] {
+                let Some(ranges_b) = touched[j].get(path) else {
+                    continue;
+                };
+                shared_files.push(path.clone());
+                if ranges_overlap(ranges_a, ranges_b) {
+                    overlapping_files.push(path.clone());
+                }
+            }
+            if !shared_files.is_empty() {
+                pairs.push(PrPairOverlap {
+                    pr_a: prs[i].0.number,
+                    pr_b: prs[j].0.number,
+                    shared_files,
+                    overlapping_files,
+                    conflict: None,
+                    conflicting_paths: Vec::new(),
+                });
+            }
+        }
+    }
+
+    let pr_numbers: Vec<u64> = prs.iter().map(|(pr, _)| pr.number).collect();
+    let merge_order = suggest_merge_order(&pr_numbers, &pairs);
+    OverlapMatrix {
+        pr_numbers,
+        pairs,
+        merge_order,
+    }
+}
+
+/// ローカル ref（PR番号 → リビジョン）で各組を試験マージし、コンフリクト判定を確定させる。
+///
+/// 共有ファイルのある組だけを対象にし、マージ順も再計算する。
+/// ref が無いPRを含む組は `conflict: None` のまま残す。
+pub fn refine_with_local_refs(
+    matrix: &mut OverlapMatrix,
+    repo_path: &str,
+    refs: &HashMap<u64, String>,
+) -> Result<()> {
+    for pair in &mut matrix.pairs {
+        let (Some(a), Some(b)) = (refs.get(&pair.pr_a), refs.get(&pair.pr_b)) else {
+            continue;
+        };
+        let check = check_mergeability(repo_path, a, b)?;
+        pair.conflict = Some(!check.mergeable);
+        pair.conflicting_paths = check.conflicts.into_iter().map(|c| c.path).collect();
+    }
+    matrix.merge_order = suggest_merge_order(&matrix.pr_numbers, &matrix.pairs);
+    Ok(())
+}
+
+/// 共有ファイルのある組に含まれるPRの head を origin から取得し、
+/// [`refine_with_local_refs`] で試験マージする。
+pub fn refine_with_fetched_prs(
+    matrix: &mut OverlapMatrix,
+    repo_path: &str,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<()> {
+    let numbers: BTreeSet<u64> = matrix.pairs.iter().flat_map(|p| [p.pr_a, p.pr_b]).collect();
+    let mut refs = HashMap::new();
+    for number in numbers {
+        refs.insert(number, fetch_pr_head(repo_path, number, progress)?);
+    }
+    refine_with_local_refs(matrix, repo_path, &refs)
+}
+
+/// 衝突しない（コンフリクトグラフで独立な）PRをできるだけ多く先にマージする順を返す。
+///
+/// 先にマージされたPRと衝突するPRは rebase が必要になるため、衝突の少ないPRから
+/// 貪欲に独立集合を選び、残りのPRも同じ方法で並べる。同点はPR番号の小さい順。
+fn suggest_merge_order(pr_numbers: &[u64], pairs: &[PrPairOverlap]) -> Vec<MergeStep> {
+    let mut neighbors: HashMap<u64, BTreeSet<u64>> =
+        pr_numbers.iter().map(|&n| (n, BTreeSet::new())).collect();
+    for pair in pairs.iter().filter(|p| p.likely_conflict()) {
+        neighbors.entry(pair.pr_a).or_default().insert(pair.pr_b);
+        neighbors.entry(pair.pr_b).or_default().insert(pair.pr_a);
+    }
+
+    let mut remaining: BTreeSet<u64> = pr_numbers.iter().copied().collect();
+    let mut order = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        // 1ラウンド: 残りのグラフで次数の小さいPRから独立集合を作る
+        let mut candidates: BTreeSet<u64> = remaining.clone();
+        while let Some(&next) = candidates.iter().min_by_key(|n| {
+            let degree = neighbors[*n].intersection(&candidates).count();
+            (degree, **n)
+        }) {
+            candidates.remove(&next);
+            for n in &neighbors[&next] {
+                candidates.remove(n);
+            }
+            remaining.remove(&next);
+            order.push(next);
+        }
+    }
+
+    let mut merged: Vec<u64> = Vec::with_capacity(order.len());
+    order
+        .into_iter()
+        .map(|pr_number| {
+            let conflicts_with: Vec<u64> = merged
+                .iter()
+                .copied()
+                .filter(|m| neighbors[&pr_number].contains(m))
+                .collect();
+            merged.push(pr_number);
+            MergeStep {
+                pr_number,
+                needs_rebase: !conflicts_with.is_empty(),
+                conflicts_with,
+            }
+        })
+        .collect()
+}
+
+/// ファイルごとに、変更前の行範囲（hunk ヘッダの `-start,count`）を集める。
+/// リネームは変更前のパスで数える。
+fn touched_ranges(diffs: &[FileDiff]) -> BTreeMap<String, Vec<(u32, u32)>> {
+    let mut touched = BTreeMap::new();
+    for diff in diffs {
+        let path = diff
+            .old_path
+            .as_deref()
+            .unwrap_or_else(|| effective_path(diff))
+            .to_string();
+        let ranges: Vec<(u32, u32)> = diff
+            .chunks
+            .iter()
+            .filter_map(|chunk| parse_old_range(&chunk.header))
+            .collect();
+        touched.insert(path, ranges);
+    }
+    touched
+}
+
+/// `@@ -a,b +c,d @@` から変更前の行範囲 `(a, a + b)` を取り出す
+fn parse_old_range(header: &str) -> Option<(u32, u32)> {
+    let old = header.strip_prefix("@@ -")?.split(' ').next()?;
+    let (start, count) = match old.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (old.parse::<u32>().ok()?, 1),
+    };
+    Some((start, start + count))
+}
+
+/// hunk の範囲が重なる（接する場合も含む）か。hunk 情報がない場合
+/// （バイナリなど）はファイル全体が重なるとみなす。
+fn ranges_overlap(a: &[(u32, u32)], b: &[(u32, u32)]) -> bool {
+    if a.is_empty() || b.is_empty() {
+        return true;
+    }
+    a.iter().any(|&(a_start, a_end)| {
+        b.iter()
+            .any(|&(b_start, b_end)| a_start <= b_end && b_start <= a_end)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{DiffChunk, FileKind, FileStatus};
+
+    fn make_pr(number: u64, base: &str) -> PrInfo {
+        PrInfo {
+            number,
+            title: format!("PR {number}"),
+            author: "agent".to_string(),
+            state: "open".to_string(),
+            merge_commit_sha: None,
+            head_branch: format!("agent/{number}"),
+            head_repo: None,
+            base_branch: base.to_string(),
+            updated_at: String::new(),
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: String::new(),
+            html_url: String::new(),
+        }
+    }
+
+    fn make_diff(path: &str, headers: &[&str]) -> FileDiff {
+        FileDiff {
+            old_path: Some(path.to_string()),
+            new_path: Some(path.to_string()),
+            status: FileStatus::Modified,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
+            chunks: headers
+                .iter()
+                .map(|h| DiffChunk {
+                    header: h.to_string(),
+                    lines: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_shared_and_overlapping_files() {
+        let prs = vec![
+            (
+                make_pr(1, "main"),
+                vec![
+                    make_diff("src/a.rs", &["@@ -10,5 +10,6 @@"]),
+                    make_diff("src/b.rs", &["@@ -1,3 +1,4 @@"]),
+                ],
+            ),
+            (
+                make_pr(2, "main"),
+                vec![
+                    make_diff("src/a.rs", &["@@ -12,2 +12,2 @@"]),
+                    make_diff("src/b.rs", &["@@ -100,3 +100,4 @@"]),
+                ],
+            ),
+            (make_pr(3, "main"), vec![make_diff("README.md", &[])]),
+        ];
+
+        let matrix = compute_overlap_matrix(&prs);
+        assert_eq!(matrix.pr_numbers, vec![1, 2, 3]);
+        assert_eq!(matrix.pairs.len(), 1);
+
+        let pair = matrix.pair(2, 1).unwrap();
+        assert_eq!(pair.shared_files, vec!["src/a.rs", "src/b.rs"]);
+        assert_eq!(pair.overlapping_files, vec!["src/a.rs"]);
+        assert!(pair.likely_conflict());
+        assert!(matrix.pair(1, 3).is_none());
+    }
+
+    #[test]
+    fn test_different_base_branches_not_compared() {
+        let prs = vec![
+            (
+                make_pr(1, "main"),
+                vec![make_diff("a.rs", &["@@ -1,3 +1,3 @@"])],
+            ),
+            (
+                make_pr(2, "release"),
+                vec![make_diff("a.rs", &["@@ -1,3 +1,3 @@"])],
+            ),
+        ];
+        assert!(compute_overlap_matrix(&prs).pairs.is_empty());
+    }
+
+    #[test]
+    fn test_merged_prs_left_out() {
+        let mut merged = make_pr(1, "main");
+        merged.state = "closed".to_string();
+        merged.merge_commit_sha = Some("abc123".to_string());
+        let prs = vec![
+            (merged, vec![make_diff("a.rs", &["@@ -1,3 +1,3 @@"])]),
+            (
+                make_pr(2, "main"),
+                vec![make_diff("a.rs", &["@@ -1,3 +1,3 @@"])],
+            ),
+        ];
+
+        let matrix = compute_overlap_matrix(&prs);
+        assert_eq!(matrix.pr_numbers, vec![2]);
+        assert!(matrix.pairs.is_empty());
+        assert_eq!(matrix.merge_order.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_order_puts_independent_prs_first() {
+        // 1 は 2・3 と衝突、2 と 3 は衝突しない
+        let conflicting = |path: &str| vec![make_diff(path, &["@@ -1,5 +1,5 @@"])];
+        let prs = vec![
+            (
+                make_pr(1, "main"),
+                vec![
+                    make_diff("x.rs", &["@@ -1,5 +1,5 @@"]),
+                    make_diff("y.rs", &["@@ -1,5 +1,5 @@"]),
+                ],
+            ),
+            (make_pr(2, "main"), conflicting("x.rs")),
+            (make_pr(3, "main"), conflicting("y.rs")),
+            (make_pr(4, "main"), conflicting("z.rs")),
+        ];
+
+        let matrix = compute_overlap_matrix(&prs);
+        let order: Vec<u64> = matrix.merge_order.iter().map(|s| s.pr_number).collect();
+        assert_eq!(order, vec![4, 2, 3, 1]);
+
+        let rebases: Vec<u64> = matrix
+            .merge_order
+            .iter()
+            .filter(|s| s.needs_rebase)
+            .map(|s| s.pr_number)
+            .collect();
+        assert_eq!(rebases, vec![1]);
+        assert_eq!(matrix.merge_order[3].conflicts_with, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_parse_old_range() {
+        assert_eq!(parse_old_range("@@ -10,5 +10,6 @@ fn x()"), Some((10, 15)));
+        assert_eq!(parse_old_range("@@ -3 +3 @@"), Some((3, 4)));
+        assert_eq!(parse_old_range("not a header"), None);
+    }
+
+    #[test]
+    fn test_refine_with_local_refs() {
+        use crate::git::test_utils::{commit_file, init_repo_with_commit};
+
+        let (dir, repo) = init_repo_with_commit();
+        let main = repo.head().unwrap().peel_to_commit().unwrap();
+        for (branch, content) in [("pr1", "one\n"), ("pr2", "two\n")] {
+            repo.branch(branch, &main, false).unwrap();
+            commit_file(&repo, branch, "hello.txt", content, "edit");
+        }
+
+        // ファイル一覧だけでは hunk が重ならないと判定される組
+        let prs = vec![
+            (
+                make_pr(1, "main"),
+                vec![make_diff("hello.txt", &["@@ -1,1 +1,1 @@"])],
+            ),
+            (
+                make_pr(2, "main"),
+                vec![make_diff("hello.txt", &["@@ -40,1 +40,1 @@"])],
+            ),
+        ];
+        let mut matrix = compute_overlap_matrix(&prs);
+        assert!(!matrix.pair(1, 2).unwrap().likely_conflict());
+
+        let refs: HashMap<u64, String> =
+            HashMap::from([(1, "pr1".to_string()), (2, "pr2".to_string())]);
+        refine_with_local_refs(&mut matrix, dir.path().to_str().unwrap(), &refs).unwrap();
+
+        let pair = matrix.pair(1, 2).unwrap();
+        assert_eq!(pair.conflict, Some(true));
+        assert_eq!(pair.conflicting_paths, vec!["hello.txt"]);
+        assert!(matrix.merge_order[1].needs_rebase);
+    }
+}
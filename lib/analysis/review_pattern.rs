@@ -155,7 +155,7 @@ fn build_reject_path_patterns(records: &[ReviewRecord]) -> Vec<RejectPathPattern
         .collect();
 
     // reject_count の降順でソート
-    patterns.sort_by(|a, b| b.reject_count.cmp(&a.reject_count));
+    patterns.sort_by_key(|b| std::cmp::Reverse(b.reject_count));
     patterns
 }
 
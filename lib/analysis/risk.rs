@@ -1,6 +1,8 @@
 use crate::config::RiskConfig;
 use crate::git::diff::FileDiff;
-use crate::github::PrInfo;
+use crate::git::merge::MergeCheck;
+use crate::git::tag::ReleaseRange;
+use crate::github::{CommitInfo, PrInfo, SignatureStatus};
 
 use super::classify::{classify_file_change, count_changes, effective_path, ChangeCategory};
 
@@ -58,6 +60,12 @@ pub struct AnalysisResult {
     pub files: Vec<FileAnalysis>,
     /// 変更サマリ
     pub summary: AnalysisSummary,
+    /// ベースブランチへのマージ可否（チェック未実施なら `None`）
+    pub merge_check: Option<MergeCheck>,
+    /// マージ可否チェックが失敗した場合のエラーメッセージ
+    pub merge_check_error: Option<String>,
+    /// 署名が有効でないコミットのSHA（チェック未実施なら `None`）
+    pub unsigned_commits: Option<Vec<String>>,
 }
 
 /// 変更のサマリ情報
@@ -88,6 +96,15 @@ pub fn analyze_pr_risk_with_config(
     diffs: &[FileDiff],
     config: &RiskConfig,
 ) -> AnalysisResult {
+    analyze_diffs(pr.number, diffs, config)
+}
+
+/// リリース範囲（2つのタグ間）の差分からリスク分析を行う。`pr_number` は 0 になる
+pub fn analyze_release_risk(range: &ReleaseRange, config: &RiskConfig) -> AnalysisResult {
+    analyze_diffs(0, &range.files, config)
+}
+
+fn analyze_diffs(pr_number: u64, diffs: &[FileDiff], config: &RiskConfig) -> AnalysisResult {
     let file_analyses: Vec<FileAnalysis> = diffs
         .iter()
         .map(|diff| {
@@ -105,11 +122,58 @@ pub fn analyze_pr_risk_with_config(
     let risk = calculate_risk_score(diffs, &file_analyses, &summary, config);
 
     AnalysisResult {
-        pr_number: pr.number,
+        pr_number,
         risk,
         files: file_analyses,
         summary,
+        merge_check: None,
+        merge_check_error: None,
+        unsigned_commits: None,
+    }
+}
+
+/// マージ可否チェックの結果を分析結果に反映する
+pub fn apply_merge_check(result: &mut AnalysisResult, check: MergeCheck) {
+    apply_merge_check_with_config(result, check, &RiskConfig::default())
+}
+
+/// RiskConfig を指定してマージ可否チェックの結果を反映する。
+/// コンフリクトする場合は `merge_conflicts` 要素を加算し、リスクレベルを再判定する。
+pub fn apply_merge_check_with_config(
+    result: &mut AnalysisResult,
+    check: MergeCheck,
+    config: &RiskConfig,
+) {
+    if !check.mergeable && config.merge_conflict_penalty > 0 {
+        let paths: Vec<&str> = check.conflicts.iter().map(|c| c.path.as_str()).collect();
+        result.risk.factors.push(RiskFactor {
+            name: "merge_conflicts".to_string(),
+            score: config.merge_conflict_penalty,
+            description: format!("ベースブランチとコンフリクトします（{}）", paths.join(", ")),
+        });
+        result.risk.score = (result.risk.score + config.merge_conflict_penalty).min(100);
+        result.risk.level = risk_level(result.risk.score, config);
     }
+    result.merge_check = Some(check);
+}
+
+/// マージ可否チェックの失敗を分析結果に記録する。
+/// 他のPRの分析は続けられるよう、エラーにはせずメッセージだけを残す。
+pub fn apply_merge_check_error(result: &mut AnalysisResult, error: &anyhow::Error) {
+    result.merge_check = None;
+    result.merge_check_error = Some(format!("{error:#}"));
+}
+
+/// PRのコミットの署名状態を分析結果に反映する。
+/// `Signed` 以外のコミットを `unsigned_commits` に記録する。
+pub fn apply_commit_signatures(result: &mut AnalysisResult, commits: &[CommitInfo]) {
+    result.unsigned_commits = Some(
+        commits
+            .iter()
+            .filter(|c| c.signature != SignatureStatus::Signed)
+            .map(|c| c.sha.clone())
+            .collect(),
+    );
 }
 
 fn build_summary(files: &[FileAnalysis]) -> AnalysisSummary {
@@ -204,18 +268,21 @@ fn calculate_risk_score(
 
     total_score = total_score.min(100);
 
-    let level = if total_score <= config.risk_thresholds.low_max {
+    RiskScore {
+        score: total_score,
+        level: risk_level(total_score, config),
+        factors,
+    }
+}
+
+/// スコアを Low/Medium/High の境界で判定する
+fn risk_level(score: u32, config: &RiskConfig) -> RiskLevel {
+    if score <= config.risk_thresholds.low_max {
         RiskLevel::Low
-    } else if total_score <= config.risk_thresholds.medium_max {
+    } else if score <= config.risk_thresholds.medium_max {
         RiskLevel::Medium
     } else {
         RiskLevel::High
-    };
-
-    RiskScore {
-        score: total_score,
-        level,
-        factors,
     }
 }
 
@@ -253,7 +320,7 @@ fn calculate_sensitive_path_score(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::diff::{DiffChunk, DiffLineInfo, FileStatus, LineOrigin};
+    use crate::git::diff::{DiffChunk, DiffLineInfo, FileKind, FileStatus, LineOrigin};
 
     fn make_pr(number: u64) -> PrInfo {
         PrInfo {
@@ -261,7 +328,9 @@ mod tests {
             title: "Test PR".to_string(),
             author: "tester".to_string(),
             state: "open".to_string(),
+            merge_commit_sha: None,
             head_branch: "feature".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
             additions: 0,
@@ -280,6 +349,7 @@ mod tests {
                 old_lineno: None,
                 new_lineno: Some(1),
                 content: "added\n".to_string(),
+                highlights: Vec::new(),
             });
         }
         for _ in 0..deletions {
@@ -288,6 +358,7 @@ mod tests {
                 old_lineno: Some(1),
                 new_lineno: None,
                 content: "removed\n".to_string(),
+                highlights: Vec::new(),
             });
         }
 
@@ -295,6 +366,11 @@ mod tests {
             old_path: Some(path.to_string()),
             new_path: Some(path.to_string()),
             status: FileStatus::Modified,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: if lines.is_empty() {
                 vec![]
             } else {
@@ -446,4 +522,107 @@ mod tests {
         assert_eq!(result.files[0].deletions, 5);
         assert_eq!(result.files[0].category, ChangeCategory::Logic);
     }
+
+    fn conflicting_check() -> MergeCheck {
+        use crate::git::merge::{ConflictKind, MergeConflict};
+        MergeCheck {
+            base: "base".to_string(),
+            head: "head".to_string(),
+            merge_base: None,
+            mergeable: false,
+            conflicts: vec![MergeConflict {
+                path: "src/lib.rs".to_string(),
+                kind: ConflictKind::Content,
+                hunks: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_apply_merge_check_adds_conflict_factor() {
+        let pr = make_pr(1);
+        let diffs = vec![make_diff("README.md", 5, 2)];
+        let mut result = analyze_pr_risk(&pr, &diffs);
+        assert_eq!(result.risk.level, RiskLevel::Low);
+        let before = result.risk.score;
+
+        apply_merge_check(&mut result, conflicting_check());
+
+        let factor = result
+            .risk
+            .factors
+            .iter()
+            .find(|f| f.name == "merge_conflicts")
+            .unwrap();
+        assert_eq!(factor.score, 30);
+        assert!(factor.description.contains("src/lib.rs"));
+        assert_eq!(result.risk.score, before + 30);
+        assert_eq!(result.risk.level, RiskLevel::Medium);
+        assert!(result.merge_check.as_ref().is_some_and(|m| !m.mergeable));
+    }
+
+    #[test]
+    fn test_apply_merge_check_clean_merge_keeps_score() {
+        let pr = make_pr(1);
+        let diffs = vec![make_diff("README.md", 5, 2)];
+        let mut result = analyze_pr_risk(&pr, &diffs);
+        let before = result.risk.score;
+
+        let check = MergeCheck {
+            mergeable: true,
+            conflicts: vec![],
+            ..conflicting_check()
+        };
+        apply_merge_check(&mut result, check);
+
+        assert_eq!(result.risk.score, before);
+        assert!(result
+            .risk
+            .factors
+            .iter()
+            .all(|f| f.name != "merge_conflicts"));
+        assert!(result.merge_check.is_some());
+    }
+
+    #[test]
+    fn test_apply_merge_check_zero_penalty() {
+        let pr = make_pr(1);
+        let mut result = analyze_pr_risk(&pr, &[make_diff("README.md", 1, 0)]);
+        let config = RiskConfig {
+            merge_conflict_penalty: 0,
+            ..RiskConfig::default()
+        };
+        apply_merge_check_with_config(&mut result, conflicting_check(), &config);
+        assert!(result.risk.factors.is_empty());
+        // チェック結果自体は保持される
+        assert!(result.merge_check.is_some());
+    }
+
+    #[test]
+    fn test_apply_commit_signatures() {
+        let commit = |sha: &str, signature| CommitInfo {
+            sha: sha.to_string(),
+            message: String::new(),
+            author: String::new(),
+            date: String::new(),
+            commit_url: String::new(),
+            signature,
+        };
+        let mut result = analyze_pr_risk(&make_pr(1), &[make_diff("README.md", 1, 0)]);
+        assert!(result.unsigned_commits.is_none());
+
+        apply_commit_signatures(
+            &mut result,
+            &[
+                commit("a", SignatureStatus::Signed),
+                commit("b", SignatureStatus::Unsigned),
+                commit("c", SignatureStatus::Invalid),
+                commit("d", SignatureStatus::Unverified),
+            ],
+        );
+        assert_eq!(
+            result.unsigned_commits,
+            Some(vec!["b".to_string(), "c".to_string(), "d".to_string()])
+        );
+    }
 }
@@ -62,6 +62,9 @@ fn risk_within_threshold(risk: &RiskLevel, max_risk: &AutoApproveMaxRisk) -> boo
 ///
 /// - `enabled == false` の場合は空リストを返す
 /// - 各PRのリスクレベルが `auto_approve_max_risk` 以下の場合にapprove対象とする
+/// - `merge_check` でベースブランチとのコンフリクトが判明しているPRは対象外とする
+/// - マージ可否チェックに失敗したPR（`merge_check_error`）は対象外とする
+/// - `require_signed_commits == true` の場合、全コミットの署名が有効と確認できたPRのみ対象とする
 pub fn evaluate_auto_approve(
     analyses: &[AnalysisResult],
     config: &AutomationConfig,
@@ -73,6 +76,12 @@ pub fn evaluate_auto_approve(
     analyses
         .iter()
         .filter(|a| risk_within_threshold(&a.risk.level, &config.auto_approve_max_risk))
+        .filter(|a| a.merge_check.as_ref().is_none_or(|m| m.mergeable))
+        .filter(|a| a.merge_check_error.is_none())
+        .filter(|a| {
+            !config.require_signed_commits
+                || a.unsigned_commits.as_ref().is_some_and(|c| c.is_empty())
+        })
         .map(|a| {
             let categories: Vec<ChangeCategory> = a
                 .summary
@@ -220,6 +229,9 @@ mod tests {
                 has_test_changes: false,
                 categories,
             },
+            merge_check: None,
+            merge_check_error: None,
+            unsigned_commits: None,
         }
     }
 
@@ -277,6 +289,58 @@ mod tests {
         assert_eq!(candidates[1].pr_number, 2);
     }
 
+    #[test]
+    fn test_merge_conflict_excluded() {
+        use crate::git::merge::MergeCheck;
+
+        let check = |mergeable| MergeCheck {
+            base: "base".to_string(),
+            head: "head".to_string(),
+            merge_base: None,
+            mergeable,
+            conflicts: vec![],
+        };
+        let mut conflicting = make_analysis(1, RiskLevel::Low, 5);
+        conflicting.merge_check = Some(check(false));
+        let mut clean = make_analysis(2, RiskLevel::Low, 5);
+        clean.merge_check = Some(check(true));
+        let unchecked = make_analysis(3, RiskLevel::Low, 5);
+        let mut failed = make_analysis(4, RiskLevel::Low, 5);
+        failed.merge_check_error = Some("fetch failed".to_string());
+        let config = AutomationConfig {
+            enabled: true,
+            auto_approve_max_risk: AutoApproveMaxRisk::Low,
+            ..Default::default()
+        };
+
+        let candidates = evaluate_auto_approve(&[conflicting, clean, unchecked, failed], &config);
+        let numbers: Vec<u64> = candidates.iter().map(|c| c.pr_number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_require_signed_commits() {
+        let mut unsigned = make_analysis(1, RiskLevel::Low, 5);
+        unsigned.unsigned_commits = Some(vec!["abc123".to_string()]);
+        let mut signed = make_analysis(2, RiskLevel::Low, 5);
+        signed.unsigned_commits = Some(vec![]);
+        let unchecked = make_analysis(3, RiskLevel::Low, 5);
+        let analyses = [unsigned, signed, unchecked];
+
+        let mut config = AutomationConfig {
+            enabled: true,
+            auto_approve_max_risk: AutoApproveMaxRisk::Low,
+            ..Default::default()
+        };
+        assert_eq!(evaluate_auto_approve(&analyses, &config).len(), 3);
+
+        config.require_signed_commits = true;
+        let candidates = evaluate_auto_approve(&analyses, &config);
+        let numbers: Vec<u64> = candidates.iter().map(|c| c.pr_number).collect();
+        // 未チェックのPRも署名を確認できないため対象外
+        assert_eq!(numbers, vec![2]);
+    }
+
     #[test]
     fn test_high_risk_excluded() {
         let analyses = vec![
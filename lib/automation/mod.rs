@@ -1,5 +1,6 @@
 pub mod auto_approve;
 pub mod orchestration;
+pub mod revert;
 
 pub use auto_approve::{
     evaluate_auto_approve, execute_auto_approve, ApproveOutcome, AutoApproveCandidate,
@@ -9,3 +10,6 @@ pub use orchestration::{
     execute_auto_approve_with_merge, ApproveWithMergeOutcome, AutoApproveWithMergeResult,
     AutoMergeStatus,
 };
+pub use revert::{
+    create_revert_pr, prepare_revert_branch, RevertBranch, RevertPrResult, RevertTarget,
+};
@@ -0,0 +1,559 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::git::cherry_pick::{revert, PickOptions};
+use crate::git::open_repo;
+use crate::git::remote::{fetch, push, SyncProgress};
+use crate::git::worktree::{
+    add_worktree, remove_worktree, sibling_worktree_path, RemoveWorktreeOptions, WorktreeInfo,
+};
+use crate::github::pull_request::{CommitInfo, GitHubClient, NewPullRequest, PrInfo};
+
+/// revert する対象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RevertTarget {
+    /// マージ済みのPR。マージコミットを revert する
+    Pr { pr: Box<PrInfo> },
+    /// 任意のコミット。`base_branch` の先端から revert ブランチを作る
+    Commit { sha: String, base_branch: String },
+}
+
+/// revert 用に作成したブランチ
+#[derive(Debug, Clone, Serialize)]
+pub struct RevertBranch {
+    /// revert ブランチ名（`revert/pr-<番号>` または `revert/<短縮SHA>`）
+    pub branch: String,
+    /// revert ブランチを展開した worktree
+    pub worktree: WorktreeInfo,
+    /// PR のマージ先
+    pub base_branch: String,
+    /// revert したコミット（PR の場合は `merge_commit_sha`）
+    pub reverted_sha: String,
+    /// 実際に revert したコミット（新しい順）。rebase マージの PR では PR の全コミット
+    pub reverted_commits: Vec<String>,
+    /// 作成した revert コミット
+    pub revert_commit: String,
+    /// PR タイトル
+    pub title: String,
+    /// PR 本文
+    pub body: String,
+}
+
+/// revert PR の作成結果
+#[derive(Debug, Clone, Serialize)]
+pub struct RevertPrResult {
+    pub branch: RevertBranch,
+    /// 作成した revert PR
+    pub pr: PrInfo,
+}
+
+/// revert ブランチを新しい worktree に作成し、origin に push する。
+///
+/// - origin から base ブランチを取得し、その先端から revert ブランチを作る
+/// - マージコミットは第1親を mainline として revert する
+/// - `RevertTarget::Pr` では `pr_commits`（`list_pr_commits` の結果）からマージ方法を判定し、
+///   rebase マージされた PR は PR の全コミットを revert する
+/// - コンフリクトなどで失敗した場合は、作成した worktree とブランチを削除してエラーを返す
+pub fn prepare_revert_branch(
+    repo_path: &str,
+    target: &RevertTarget,
+    pr_commits: &[CommitInfo],
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<RevertBranch> {
+    let (sha, base_branch) = match target {
+        RevertTarget::Pr { pr } => {
+            if pr.state != "merged" {
+                anyhow::bail!("PR #{} はマージされていません", pr.number);
+            }
+            let sha = pr
+                .merge_commit_sha
+                .clone()
+                .with_context(|| format!("PR #{} のマージコミットが不明です", pr.number))?;
+            (sha, pr.base_branch.clone())
+        }
+        RevertTarget::Commit { sha, base_branch } => (sha.clone(), base_branch.clone()),
+    };
+
+    let base_ref = format!("refs/remotes/origin/{base_branch}");
+    let refspec = format!("+refs/heads/{base_branch}:{base_ref}");
+    fetch(repo_path, "origin", &[refspec.as_str()], progress)
+        .with_context(|| format!("ベースブランチ '{base_branch}' の取得に失敗しました"))?;
+
+    let repo = open_repo(repo_path)?;
+    let commit = repo
+        .revparse_single(&sha)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("コミット '{sha}' が見つかりません"))?;
+    let reverted_sha = commit.id().to_string();
+    let summary = commit.summary().unwrap_or_default().to_string();
+    let mainline = (commit.parent_count() > 1).then_some(1);
+    let reverted_commits = match target {
+        RevertTarget::Pr { pr } => pr_commits_to_revert(&commit, pr.number, pr_commits)?,
+        RevertTarget::Commit { .. } => vec![reverted_sha.clone()],
+    };
+
+    let (branch, title, body) = match target {
+        RevertTarget::Pr { pr } => (
+            format!("revert/pr-{}", pr.number),
+            format!("Revert \"{}\"", pr.title),
+            format!("Reverts #{}", pr.number),
+        ),
+        RevertTarget::Commit { .. } => (
+            format!("revert/{}", &reverted_sha[..7]),
+            format!("Revert \"{summary}\""),
+            format!("Reverts commit {reverted_sha}"),
+        ),
+    };
+
+    if repo.find_branch(&branch, git2::BranchType::Local).is_ok() {
+        anyhow::bail!("ブランチ '{branch}' は既に存在します");
+    }
+    let start = repo
+        .find_reference(&base_ref)
+        .and_then(|r| r.peel_to_commit())
+        .with_context(|| format!("'{base_ref}' が見つかりません"))?;
+    let (name, wt_path) = sibling_worktree_path(&repo, &branch)?;
+    let wt_path_str = wt_path
+        .to_str()
+        .with_context(|| "worktree パスの変換に失敗しました")?;
+    repo.branch(&branch, &start, false)
+        .with_context(|| format!("ブランチ '{branch}' の作成に失敗しました"))?;
+
+    let revert_commit = match revert_and_push(
+        repo_path,
+        &branch,
+        wt_path_str,
+        &reverted_commits,
+        mainline,
+        progress,
+    ) {
+        Ok(revert_commit) => revert_commit,
+        Err(e) => {
+            discard_revert_branch(repo_path, &name, &branch);
+            return Err(e);
+        }
+    };
+
+    Ok(RevertBranch {
+        branch: branch.clone(),
+        worktree: WorktreeInfo {
+            name,
+            path: wt_path,
+            branch: Some(branch),
+            is_main: false,
+            is_locked: false,
+            lock_reason: None,
+        },
+        base_branch,
+        reverted_sha,
+        reverted_commits,
+        revert_commit,
+        title,
+        body,
+    })
+}
+
+/// revert ブランチを作成し、revert PR を開く。
+pub async fn create_revert_pr(
+    repo_path: &str,
+    owner: &str,
+    repo: &str,
+    target: &RevertTarget,
+    token: &str,
+    client: &GitHubClient,
+) -> Result<RevertPrResult> {
+    let pr_commits = match target {
+        RevertTarget::Pr { pr } => {
+            client
+                .list_pr_commits(owner, repo, pr.number, token)
+                .await?
+        }
+        RevertTarget::Commit { .. } => Vec::new(),
+    };
+    let branch = prepare_revert_branch(repo_path, target, &pr_commits, &mut |_| {})?;
+    let request = NewPullRequest {
+        title: branch.title.clone(),
+        head: branch.branch.clone(),
+        base: branch.base_branch.clone(),
+        body: branch.body.clone(),
+    };
+    let pr = client
+        .create_pull_request(owner, repo, &request, token)
+        .await?;
+    Ok(RevertPrResult { branch, pr })
+}
+
+/// マージ済み PR で revert するコミットを新しい順に返す。
+///
+/// "Rebase and merge" では PR のコミットが base の第1親の列に積み直され、
+/// `merge_commit_sha` はその最後のコミットしか指さない。`merged` から第1親を
+/// たどったコミットのメッセージが PR のコミットと一致すれば、その全体を返す。
+/// マージコミットや squash マージでは `merged` だけを返す。
+fn pr_commits_to_revert(
+    merged: &git2::Commit,
+    pr_number: u64,
+    pr_commits: &[CommitInfo],
+) -> Result<Vec<String>> {
+    if merged.parent_count() > 1 || pr_commits.len() == 1 {
+        return Ok(vec![merged.id().to_string()]);
+    }
+    if pr_commits.is_empty() {
+        anyhow::bail!("PR #{pr_number} のコミット一覧がないため、マージ方法を判定できません");
+    }
+
+    let mut rebased = Vec::new();
+    let mut current = merged.clone();
+    for pr_commit in pr_commits.iter().rev() {
+        if current.message().map(str::trim_end) != Some(pr_commit.message.trim_end()) {
+            break;
+        }
+        rebased.push(current.id().to_string());
+        if rebased.len() == pr_commits.len() {
+            break;
+        }
+        match current.parent(0) {
+            Ok(parent) => current = parent,
+            Err(_) => break,
+        }
+    }
+
+    match rebased.len() {
+        // squash マージ
+        0 => Ok(vec![merged.id().to_string()]),
+        n if n == pr_commits.len() => Ok(rebased),
+        n => anyhow::bail!(
+            "PR #{pr_number} は rebase マージされたようですが、ベースブランチ上で {n} / {} 件のコミットしか特定できません",
+            pr_commits.len()
+        ),
+    }
+}
+
+/// worktree に revert ブランチを展開し、`commits` を順に revert して push する。
+/// 最後に作成した revert コミットを返す。
+fn revert_and_push(
+    repo_path: &str,
+    branch: &str,
+    wt_path: &str,
+    commits: &[String],
+    mainline: Option<u32>,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<String> {
+    add_worktree(repo_path, wt_path, branch)?;
+
+    let mut revert_commit = String::new();
+    for sha in commits {
+        let result = revert(wt_path, sha, &PickOptions { mainline })?;
+        revert_commit = result.commit.with_context(|| {
+            format!(
+                "{sha} の revert がコンフリクトしました（{}）",
+                result.conflicting_paths.join(", ")
+            )
+        })?;
+    }
+
+    push(repo_path, "origin", branch, true, progress)?;
+    Ok(revert_commit)
+}
+
+/// 失敗した revert のために作った worktree とブランチを削除する。
+fn discard_revert_branch(repo_path: &str, worktree_name: &str, branch: &str) {
+    let options = RemoveWorktreeOptions {
+        force: true,
+        delete_branch: true,
+    };
+    if remove_worktree(repo_path, worktree_name, &options).is_err() {
+        // worktree の作成前に失敗した場合はブランチだけが残っている
+        if let Ok(repo) = open_repo(repo_path) {
+            let _ = repo
+                .find_branch(branch, git2::BranchType::Local)
+                .and_then(|mut b| b.delete());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
+    use git2::{Oid, Repository, Signature};
+    use tempfile::TempDir;
+
+    /// feature ブランチを main にマージした origin と、その clone を作る。
+    /// clone は一時ディレクトリ内の `repo` に置き、worktree も同じ一時ディレクトリに作られる。
+    fn origin_with_merge() -> (TempDir, TempDir, Oid, Oid) {
+        let (origin_dir, origin) = init_repo_with_commit();
+        {
+            let base = origin.head().unwrap().peel_to_commit().unwrap();
+            origin.branch("feature", &base, false).unwrap();
+        }
+        let feature = commit_file(
+            &origin,
+            "feature",
+            "feature.txt",
+            "content\n",
+            "Add feature",
+        );
+        let plain = commit_file(&origin, "main", "main.txt", "content\n", "Main work");
+
+        let main = origin.find_commit(plain).unwrap();
+        let feature = origin.find_commit(feature).unwrap();
+        let mut index = origin.merge_commits(&main, &feature, None).unwrap();
+        let tree = origin
+            .find_tree(index.write_tree_to(&origin).unwrap())
+            .unwrap();
+        let sig = Signature::now("Test", "test@test.com").unwrap();
+        let merge = origin
+            .commit(
+                Some("refs/heads/main"),
+                &sig,
+                &sig,
+                "Merge pull request #5",
+                &tree,
+                &[&main, &feature],
+            )
+            .unwrap();
+
+        // push を受け付けるよう origin はベアリポジトリにする
+        let bare_dir = TempDir::new().unwrap();
+        let url = format!("file://{}", origin_dir.path().display());
+        git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(&url, bare_dir.path())
+            .unwrap();
+
+        let outer = TempDir::new().unwrap();
+        let url = format!("file://{}", bare_dir.path().display());
+        let clone = Repository::clone(&url, outer.path().join("repo")).unwrap();
+        let mut config = clone.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+        (bare_dir, outer, merge, plain)
+    }
+
+    fn merged_pr(merge: Oid) -> PrInfo {
+        PrInfo {
+            number: 5,
+            title: "Add feature".to_string(),
+            author: "agent".to_string(),
+            state: "merged".to_string(),
+            merge_commit_sha: Some(merge.to_string()),
+            head_branch: "feature".to_string(),
+            head_repo: None,
+            base_branch: "main".to_string(),
+            updated_at: String::new(),
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: String::new(),
+            html_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_from_merged_pr() {
+        let (origin_dir, outer, merge, _) = origin_with_merge();
+        let repo_path = outer.path().join("repo");
+        let target = RevertTarget::Pr {
+            pr: Box::new(merged_pr(merge)),
+        };
+
+        let branch =
+            prepare_revert_branch(repo_path.to_str().unwrap(), &target, &[], &mut |_| {}).unwrap();
+
+        assert_eq!(branch.branch, "revert/pr-5");
+        assert_eq!(branch.base_branch, "main");
+        assert_eq!(branch.reverted_sha, merge.to_string());
+        assert_eq!(branch.reverted_commits, vec![merge.to_string()]);
+        assert_eq!(branch.title, "Revert \"Add feature\"");
+        assert_eq!(branch.body, "Reverts #5");
+        assert_eq!(branch.worktree.path, outer.path().join("repo-revert-pr-5"));
+        assert!(!branch.worktree.path.join("feature.txt").exists());
+        assert!(branch.worktree.path.join("main.txt").exists());
+
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        let pushed = origin
+            .find_reference("refs/heads/revert/pr-5")
+            .unwrap()
+            .target()
+            .unwrap();
+        assert_eq!(pushed.to_string(), branch.revert_commit);
+        assert_eq!(
+            origin.find_commit(pushed).unwrap().parent_id(0).unwrap(),
+            merge
+        );
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_from_sha() {
+        let (_origin_dir, outer, _, plain) = origin_with_merge();
+        let repo_path = outer.path().join("repo");
+        let target = RevertTarget::Commit {
+            sha: plain.to_string(),
+            base_branch: "main".to_string(),
+        };
+
+        let branch =
+            prepare_revert_branch(repo_path.to_str().unwrap(), &target, &[], &mut |_| {}).unwrap();
+
+        assert_eq!(branch.branch, format!("revert/{}", &plain.to_string()[..7]));
+        assert_eq!(branch.title, "Revert \"Main work\"");
+        assert!(!branch.worktree.path.join("main.txt").exists());
+        assert!(branch.worktree.path.join("feature.txt").exists());
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_rejects_unmerged_pr() {
+        let (_origin_dir, outer, merge, _) = origin_with_merge();
+        let mut pr = merged_pr(merge);
+        pr.state = "open".to_string();
+
+        let err = prepare_revert_branch(
+            outer.path().join("repo").to_str().unwrap(),
+            &RevertTarget::Pr { pr: Box::new(pr) },
+            &[],
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("マージされていません"));
+    }
+
+    fn pr_commit(message: &str) -> CommitInfo {
+        CommitInfo {
+            sha: String::new(),
+            message: message.to_string(),
+            author: String::new(),
+            date: String::new(),
+            commit_url: String::new(),
+            signature: crate::github::SignatureStatus::Unsigned,
+        }
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_from_rebase_merged_pr() {
+        let (origin_dir, outer, _, _) = origin_with_merge();
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        commit_file(&origin, "main", "a.txt", "content\n", "Add a");
+        let last = commit_file(&origin, "main", "b.txt", "content\n", "Add b");
+        let mut pr = merged_pr(last);
+        pr.merge_commit_sha = Some(last.to_string());
+        let pr_commits = [pr_commit("Add a\n"), pr_commit("Add b\n")];
+
+        let branch = prepare_revert_branch(
+            outer.path().join("repo").to_str().unwrap(),
+            &RevertTarget::Pr { pr: Box::new(pr) },
+            &pr_commits,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(branch.reverted_commits.len(), 2);
+        assert_eq!(branch.reverted_commits[0], last.to_string());
+        assert!(!branch.worktree.path.join("a.txt").exists());
+        assert!(!branch.worktree.path.join("b.txt").exists());
+        assert!(branch.worktree.path.join("feature.txt").exists());
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_from_squash_merged_pr() {
+        let (origin_dir, outer, _, _) = origin_with_merge();
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        commit_file(&origin, "main", "a.txt", "content\n", "Add a");
+        let squashed = commit_file(&origin, "main", "b.txt", "content\n", "Add feature (#5)");
+        let pr_commits = [pr_commit("Add b"), pr_commit("Fix b")];
+
+        let branch = prepare_revert_branch(
+            outer.path().join("repo").to_str().unwrap(),
+            &RevertTarget::Pr {
+                pr: Box::new(merged_pr(squashed)),
+            },
+            &pr_commits,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        assert_eq!(branch.reverted_commits, vec![squashed.to_string()]);
+        assert!(branch.worktree.path.join("a.txt").exists());
+        assert!(!branch.worktree.path.join("b.txt").exists());
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_refuses_partial_rebase_match() {
+        let (origin_dir, outer, _, _) = origin_with_merge();
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        commit_file(&origin, "main", "a.txt", "content\n", "Unrelated");
+        let last = commit_file(&origin, "main", "b.txt", "content\n", "Add b");
+        let pr_commits = [pr_commit("Add a"), pr_commit("Add b")];
+        let repo_path = outer.path().join("repo");
+
+        let err = prepare_revert_branch(
+            repo_path.to_str().unwrap(),
+            &RevertTarget::Pr {
+                pr: Box::new(merged_pr(last)),
+            },
+            &pr_commits,
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("rebase"), "{err}");
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo
+            .find_branch("revert/pr-5", git2::BranchType::Local)
+            .is_err());
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_refuses_empty_revert() {
+        let (origin_dir, outer, _, _) = origin_with_merge();
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        let change = commit_file(&origin, "main", "hello.txt", "changed\n", "Change hello");
+        commit_file(&origin, "main", "hello.txt", "hello\n", "Restore hello");
+        let repo_path = outer.path().join("repo");
+        let target = RevertTarget::Commit {
+            sha: change.to_string(),
+            base_branch: "main".to_string(),
+        };
+
+        let err = prepare_revert_branch(repo_path.to_str().unwrap(), &target, &[], &mut |_| {})
+            .unwrap_err();
+        assert!(err.to_string().contains("already in HEAD"), "{err}");
+
+        let branch = format!("revert/{}", &change.to_string()[..7]);
+        assert!(origin
+            .find_reference(&format!("refs/heads/{branch}"))
+            .is_err());
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo.find_branch(&branch, git2::BranchType::Local).is_err());
+        assert!(repo.worktrees().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prepare_revert_branch_cleans_up_on_conflict() {
+        let (origin_dir, outer, _, _) = origin_with_merge();
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        let first = commit_file(&origin, "main", "c.txt", "one\n", "Add c");
+        commit_file(&origin, "main", "c.txt", "two\n", "Change c");
+        let repo_path = outer.path().join("repo");
+        let target = RevertTarget::Commit {
+            sha: first.to_string(),
+            base_branch: "main".to_string(),
+        };
+
+        let err = prepare_revert_branch(repo_path.to_str().unwrap(), &target, &[], &mut |_| {})
+            .unwrap_err();
+        assert!(err.to_string().contains("コンフリクト"), "{err}");
+
+        let branch = format!("revert/{}", &first.to_string()[..7]);
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo.find_branch(&branch, git2::BranchType::Local).is_err());
+        assert!(repo.worktrees().unwrap().is_empty());
+        assert!(!outer
+            .path()
+            .join(format!("repo-{}", branch.replace('/', "-")))
+            .exists());
+        // 後片付けされているので再実行できる
+        let err = prepare_revert_branch(repo_path.to_str().unwrap(), &target, &[], &mut |_| {})
+            .unwrap_err();
+        assert!(!err.to_string().contains("既に存在"), "{err}");
+    }
+}
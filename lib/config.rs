@@ -67,6 +67,9 @@ pub struct RiskConfig {
     /// ロジック変更があるのにテストがない場合のペナルティスコア
     #[serde(default = "default_missing_test_penalty")]
     pub missing_test_penalty: u32,
+    /// ベースブランチとコンフリクトする場合のペナルティスコア
+    #[serde(default = "default_merge_conflict_penalty")]
+    pub merge_conflict_penalty: u32,
     /// Low/Medium/High の境界スコア
     #[serde(default)]
     pub risk_thresholds: RiskThresholds,
@@ -81,6 +84,8 @@ fn default_category_weights() -> HashMap<ChangeCategory, f64> {
     weights.insert(ChangeCategory::Documentation, 1.0);
     weights.insert(ChangeCategory::Dependency, 1.0);
     weights.insert(ChangeCategory::Refactor, 1.0);
+    weights.insert(ChangeCategory::Submodule, 1.0);
+    weights.insert(ChangeCategory::Asset, 1.0);
     weights.insert(ChangeCategory::Other, 1.0);
     weights
 }
@@ -192,6 +197,10 @@ fn default_missing_test_penalty() -> u32 {
     15
 }
 
+fn default_merge_conflict_penalty() -> u32 {
+    30
+}
+
 impl Default for RiskConfig {
     fn default() -> Self {
         Self {
@@ -200,6 +209,7 @@ impl Default for RiskConfig {
             file_count_thresholds: default_file_count_thresholds(),
             line_count_thresholds: default_line_count_thresholds(),
             missing_test_penalty: default_missing_test_penalty(),
+            merge_conflict_penalty: default_merge_conflict_penalty(),
             risk_thresholds: RiskThresholds::default(),
         }
     }
@@ -231,6 +241,9 @@ pub struct AutomationConfig {
     /// auto-approve時にPRに付与するラベル名
     #[serde(default = "default_auto_approve_label")]
     pub auto_approve_label: String,
+    /// 全コミットに有効な署名があるPRのみ自動approveする
+    #[serde(default)]
+    pub require_signed_commits: bool,
 }
 
 impl Default for AutomationConfig {
@@ -242,6 +255,7 @@ impl Default for AutomationConfig {
             auto_merge_method: MergeMethod::Merge,
             risk_config: RiskConfig::default(),
             auto_approve_label: default_auto_approve_label(),
+            require_signed_commits: false,
         }
     }
 }
@@ -620,6 +634,7 @@ mod tests {
         let config = AutomationConfig::default();
         assert!(!config.enabled);
         assert_eq!(config.auto_approve_max_risk, AutoApproveMaxRisk::Low);
+        assert!(!config.require_signed_commits);
     }
 
     #[test]
@@ -727,6 +742,7 @@ mod tests {
         assert!(config.automation.enabled);
         assert!(!config.automation.enable_auto_merge);
         assert_eq!(config.automation.auto_merge_method, MergeMethod::Merge);
+        assert!(!config.automation.require_signed_commits);
     }
 
     // ── リポジトリ別設定テスト ──────────────────────────────────────────
@@ -771,6 +787,7 @@ mod tests {
                 auto_merge_method: MergeMethod::Squash,
                 risk_config: RiskConfig::default(),
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
         );
 
@@ -797,6 +814,7 @@ mod tests {
             auto_merge_method: MergeMethod::Rebase,
             risk_config: RiskConfig::default(),
             auto_approve_label: default_auto_approve_label(),
+            require_signed_commits: false,
         };
         config.set_repo_automation_config("owner/repo".to_string(), repo_config.clone());
 
@@ -859,6 +877,7 @@ mod tests {
                 auto_merge_method: MergeMethod::Squash,
                 risk_config: RiskConfig::default(),
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
         );
         repo_automation.insert(
@@ -870,6 +889,7 @@ mod tests {
                 auto_merge_method: MergeMethod::Merge,
                 risk_config: RiskConfig::default(),
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
         );
 
@@ -902,6 +922,7 @@ mod tests {
                 auto_merge_method: MergeMethod::Rebase,
                 risk_config: RiskConfig::default(),
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
         );
 
@@ -933,6 +954,7 @@ mod tests {
                 auto_merge_method: MergeMethod::Squash,
                 risk_config: RiskConfig::default(),
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
         );
 
@@ -960,7 +982,7 @@ mod tests {
     #[test]
     fn test_risk_config_default() {
         let config = RiskConfig::default();
-        assert_eq!(config.category_weights.len(), 8);
+        assert_eq!(config.category_weights.len(), 10);
         assert_eq!(
             config
                 .category_weights
@@ -982,6 +1004,7 @@ mod tests {
             .sensitive_patterns
             .iter()
             .any(|p| p.pattern == "migration"));
+        assert_eq!(config.merge_conflict_penalty, 30);
         assert_eq!(config.risk_thresholds, RiskThresholds::default());
     }
 
@@ -1051,12 +1074,14 @@ mod tests {
                     file_count_thresholds: default_file_count_thresholds(),
                     line_count_thresholds: default_line_count_thresholds(),
                     missing_test_penalty: 15,
+                    merge_conflict_penalty: 40,
                     risk_thresholds: RiskThresholds {
                         low_max: 30,
                         medium_max: 60,
                     },
                 },
                 auto_approve_label: default_auto_approve_label(),
+                require_signed_commits: false,
             },
             ..Default::default()
         };
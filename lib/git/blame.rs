@@ -0,0 +1,201 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::diff::{FileDiff, FileKind, FileStatus, LineOrigin};
+use super::log::format_git_time;
+use super::open_repo;
+
+/// Who last touched a line on the base revision.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LineBlame {
+    pub commit: String,
+    pub author: String,
+    pub author_email: String,
+    /// Author time as a Unix timestamp.
+    pub time: i64,
+    /// Author date in RFC 3339 format.
+    pub date: String,
+}
+
+/// Blame for one file, keyed by old line number (`DiffLineInfo::old_lineno`).
+pub type FileBlame = BTreeMap<u32, LineBlame>;
+
+/// Blame every deleted line of `diffs` on `base_rev`.
+///
+/// Modified lines show up as deletions in a diff, so this covers both.
+/// Returns a map from old path to the blame of that file's deleted lines.
+/// Added files, binaries and submodules are skipped.
+pub fn blame_deleted_lines(
+    repo_path: &str,
+    base_rev: &str,
+    diffs: &[FileDiff],
+) -> Result<BTreeMap<String, FileBlame>> {
+    let repo = open_repo(repo_path)?;
+    let base = repo
+        .revparse_single(base_rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Revision '{base_rev}' not found"))?;
+
+    let mut result = BTreeMap::new();
+    for diff in diffs {
+        if diff.status == FileStatus::Added || diff.kind != FileKind::Text {
+            continue;
+        }
+        let Some(path) = diff.old_path.as_deref() else {
+            continue;
+        };
+
+        let old_lines: Vec<u32> = diff
+            .chunks
+            .iter()
+            .flat_map(|chunk| &chunk.lines)
+            .filter(|line| line.origin == LineOrigin::Deletion)
+            .filter_map(|line| line.old_lineno)
+            .collect();
+        let (Some(&min), Some(&max)) = (old_lines.iter().min(), old_lines.iter().max()) else {
+            continue;
+        };
+
+        let mut opts = git2::BlameOptions::new();
+        opts.newest_commit(base.id())
+            .min_line(min as usize)
+            .max_line(max as usize);
+        let blame = repo
+            .blame_file(Path::new(path), Some(&mut opts))
+            .with_context(|| format!("Failed to blame '{path}' at '{base_rev}'"))?;
+
+        let mut file_blame = FileBlame::new();
+        for lineno in old_lines {
+            let Some(hunk) = blame.get_line(lineno as usize) else {
+                continue;
+            };
+            let signature = hunk.final_signature();
+            file_blame.insert(
+                lineno,
+                LineBlame {
+                    commit: hunk.final_commit_id().to_string(),
+                    author: signature.name().unwrap_or("").to_string(),
+                    author_email: signature.email().unwrap_or("").to_string(),
+                    time: signature.when().seconds(),
+                    date: format_git_time(signature.when()),
+                },
+            );
+        }
+        result.insert(path.to_string(), file_blame);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{diff_branches, diff_workdir, DiffRequest};
+    use crate::git::test_utils::{commit_file_as, init_test_repo, signature_at};
+    use std::fs;
+
+    #[test]
+    fn test_blame_deleted_lines() {
+        let (dir, repo) = init_test_repo();
+        let alice = commit_file_as(
+            &repo,
+            "HEAD",
+            "code.rs",
+            "one\ntwo\nthree\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        let bob = commit_file_as(
+            &repo,
+            "HEAD",
+            "code.rs",
+            "one\nTWO\nthree\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+
+        fs::write(dir.path().join("code.rs"), "uno\nTWO\ntres\n").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let diffs = diff_workdir(path, &DiffRequest::default()).unwrap();
+
+        let blame = blame_deleted_lines(path, "HEAD", &diffs).unwrap();
+        let file = &blame["code.rs"];
+        assert_eq!(file.keys().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(file[&1].author, "Alice");
+        assert_eq!(file[&1].commit, alice.to_string());
+        assert_eq!(file[&1].time, 1_000);
+        assert_eq!(file[&3].author, "Alice");
+
+        // Line 2 last touched by Bob
+        fs::write(dir.path().join("code.rs"), "one\nzwei\nthree\n").unwrap();
+        let diffs = diff_workdir(path, &DiffRequest::default()).unwrap();
+        let blame = blame_deleted_lines(path, "HEAD", &diffs).unwrap();
+        let line = &blame["code.rs"][&2];
+        assert_eq!(line.commit, bob.to_string());
+        assert_eq!(line.author_email, "bob@example.com");
+        assert_eq!(line.date, "1970-01-01T00:33:20Z");
+    }
+
+    #[test]
+    fn test_blame_uses_base_revision() {
+        let (dir, repo) = init_test_repo();
+        let base = commit_file_as(
+            &repo,
+            "HEAD",
+            "code.rs",
+            "one\ntwo\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        let base_branch = repo
+            .branch("base", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        let base_name = base_branch.name().unwrap().unwrap().to_string();
+        // A later commit on HEAD rewrites line 1; the base still has Alice's
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "code.rs",
+            "ONE\ntwo\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+        let path = dir.path().to_str().unwrap();
+
+        let diffs = diff_branches(path, &base_name, "HEAD", &DiffRequest::default()).unwrap();
+        let blame = blame_deleted_lines(path, &base_name, &diffs).unwrap();
+        assert_eq!(blame["code.rs"][&1].author, "Alice");
+    }
+
+    #[test]
+    fn test_blame_skips_added_files_and_pure_additions() {
+        let (dir, repo) = init_test_repo();
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "code.rs",
+            "one\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        fs::write(dir.path().join("code.rs"), "one\ntwo\n").unwrap();
+        fs::write(dir.path().join("new.rs"), "new\n").unwrap();
+        let path = dir.path().to_str().unwrap();
+        let request = DiffRequest {
+            include_untracked: true,
+            ..Default::default()
+        };
+        let diffs = diff_workdir(path, &request).unwrap();
+
+        let blame = blame_deleted_lines(path, "HEAD", &diffs).unwrap();
+        assert!(blame.is_empty());
+    }
+
+    #[test]
+    fn test_blame_unknown_revision() {
+        let (dir, _repo) = init_test_repo();
+        let result = blame_deleted_lines(dir.path().to_str().unwrap(), "nope", &[]);
+        assert!(result.is_err());
+    }
+}
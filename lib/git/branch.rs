@@ -1,9 +1,11 @@
 use anyhow::{Context, Result};
-use git2::BranchType;
+use git2::{BranchType, Oid};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use super::open_repo;
+use super::journal::{self, Operation};
 use super::worktree::list_worktrees;
+use super::{open_repo, parse_github_remote, stash};
 use crate::github::PrInfo;
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -22,12 +24,119 @@ pub struct EnrichedBranchInfo {
     pub upstream: Option<String>,
     pub is_local: bool,
     pub is_remote: bool,
+    /// このブランチが存在するリモート名（同名ブランチ、または設定された upstream のリモート）
+    pub remotes: Vec<String>,
+    /// 設定された upstream のリモート名（`branch.<name>.remote`）
+    pub upstream_remote: Option<String>,
     pub has_worktree: bool,
     pub worktree_path: Option<PathBuf>,
     pub pr_number: Option<u64>,
     pub pr_title: Option<String>,
+    /// upstream に対する ahead/behind（upstream 未設定なら `None`）
+    pub upstream_ahead_behind: Option<AheadBehind>,
+    /// 比較対象のベースブランチ名。open な PR があればその base、なければデフォルトブランチ
+    pub base_branch: Option<String>,
+    /// ベースブランチに対する ahead/behind
+    pub base_ahead_behind: Option<AheadBehind>,
+    /// 最新コミットの SHA
+    pub last_commit_sha: Option<String>,
+    /// 最新コミットの author 名
+    pub last_commit_author: Option<String>,
+    /// 最新コミットの author 時刻（Unix タイムスタンプ）
+    pub last_commit_time: Option<i64>,
+    /// マージ済みか。作成後のコミットがデフォルトブランチから到達可能、
+    /// または PR がマージ済みの場合に `true`
+    pub is_merged: bool,
 }
 
+/// Commit counts between a ref and the ref it is compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct AheadBehind {
+    /// Commits on the ref but not on the other side.
+    pub ahead: usize,
+    /// Commits on the other side but not on the ref.
+    pub behind: usize,
+}
+
+impl AheadBehind {
+    pub(crate) fn between(repo: &git2::Repository, local: Oid, other: Oid) -> Result<Self> {
+        let (ahead, behind) = repo.graph_ahead_behind(local, other)?;
+        Ok(Self { ahead, behind })
+    }
+}
+
+/// Find the repository's default branch.
+///
+/// Looks at the upstream remote first (the fork parent in a fork setup, see
+/// `get_repo_remotes`) and then at `origin`. Uses `<remote>/HEAD` when the
+/// remote has advertised one, then `<remote>/main` and `<remote>/master`,
+/// and finally the local `main` and `master`. Returns the branch name
+/// (e.g. `origin/main`) and its tip.
+pub(crate) fn default_branch(repo: &git2::Repository) -> Option<(String, Oid)> {
+    let remotes = preferred_remotes(repo);
+    for remote in &remotes {
+        let Ok(remote_head) = repo.find_reference(&format!("refs/remotes/{remote}/HEAD")) else {
+            continue;
+        };
+        if let Some(target) = remote_head.symbolic_target() {
+            let name = target.trim_start_matches("refs/remotes/").to_string();
+            if let Ok(oid) = repo.refname_to_id(target) {
+                return Some((name, oid));
+            }
+        }
+    }
+
+    let remote_candidates = remotes.iter().flat_map(|remote| {
+        ["main", "master"].map(|name| (format!("{remote}/{name}"), BranchType::Remote))
+    });
+    let local_candidates = ["main", "master"].map(|name| (name.to_string(), BranchType::Local));
+    remote_candidates
+        .chain(local_candidates)
+        .find_map(|(name, kind)| {
+            let oid = repo.find_branch(&name, kind).ok()?.get().target()?;
+            Some((name, oid))
+        })
+}
+
+/// Options for `switch_branch_with`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SwitchOptions {
+    /// Stash local changes before switching and restore them afterwards.
+    pub auto_stash: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SwitchResult {
+    /// Local changes were stashed before the checkout.
+    pub stashed: bool,
+    /// The stash applied cleanly on the new branch and was dropped.
+    pub restored: bool,
+    /// Files left with conflict markers while restoring the stash.
+    pub conflicting_paths: Vec<String>,
+}
+
+/// Returned (inside `anyhow::Error`) when a checkout would overwrite local
+/// changes. Downcast to get the paths.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SwitchConflict {
+    pub branch: String,
+    pub conflicting_paths: Vec<String>,
+}
+
+impl std::fmt::Display for SwitchConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cannot switch to '{}': local changes would be overwritten in {}",
+            self.branch,
+            self.conflicting_paths.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for SwitchConflict {}
+
 /// List all local branches for the repository at `repo_path`.
 pub fn list_branches(repo_path: &str) -> Result<Vec<BranchInfo>> {
     let repo = open_repo(repo_path)?;
@@ -68,28 +177,118 @@ pub fn create_branch(repo_path: &str, name: &str) -> Result<()> {
 }
 
 /// Switch (checkout) to an existing local branch.
+///
+/// Fails with [`SwitchConflict`] when local changes would be overwritten.
 pub fn switch_branch(repo_path: &str, name: &str) -> Result<()> {
-    let repo = open_repo(repo_path)?;
+    switch_branch_with(repo_path, name, &SwitchOptions::default()).map(|_| ())
+}
 
-    let branch = repo
-        .find_branch(name, BranchType::Local)
-        .with_context(|| format!("Branch '{name}' not found"))?;
+/// Switch to an existing local branch, optionally carrying local changes over.
+///
+/// With `auto_stash`, local changes (including untracked files) are stashed
+/// before the checkout and popped on the new branch. If they conflict with
+/// the new branch, conflict markers are written, the stash is kept as
+/// `stash@{0}` and `SwitchResult::conflicting_paths` lists the files. If the
+/// checkout itself fails, the stash is restored on the original branch.
+///
+/// The previous HEAD is recorded in the operation journal, so the switch can
+/// be reverted with `journal::undo_last_operation`.
+pub fn switch_branch_with(
+    repo_path: &str,
+    name: &str,
+    options: &SwitchOptions,
+) -> Result<SwitchResult> {
+    let mut repo = open_repo(repo_path)?;
 
-    let refname = branch
-        .get()
-        .name()
-        .context("Invalid branch reference name")?;
+    let (refname, target) = {
+        let branch = repo
+            .find_branch(name, BranchType::Local)
+            .with_context(|| format!("Branch '{name}' not found"))?;
+        let refname = branch
+            .get()
+            .name()
+            .context("Invalid branch reference name")?
+            .to_string();
+        let target = branch
+            .get()
+            .peel_to_commit()
+            .with_context(|| format!("Failed to resolve branch '{name}'"))?
+            .id();
+        (refname, target)
+    };
 
-    repo.set_head(refname)
-        .with_context(|| format!("Failed to set HEAD to '{name}'"))?;
+    let stashed = options.auto_stash
+        && stash::save(
+            &mut repo,
+            Some(&format!("reown: auto-stash before switching to {name}")),
+            true,
+        )?
+        .is_some();
 
-    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().safe()))
-        .with_context(|| format!("Failed to checkout '{name}'"))?;
+    let previous = repo.head().ok().and_then(|head| {
+        let from = head
+            .is_branch()
+            .then(|| head.shorthand().map(str::to_string));
+        head.target().map(|oid| (from.flatten(), oid))
+    });
+    let operation = previous.map(|(from, oid)| {
+        let operation = Operation::SwitchBranch {
+            from,
+            to: name.to_string(),
+        };
+        (operation, oid)
+    });
+    if let Some((operation, oid)) = &operation {
+        journal::record(&repo, operation, *oid)?;
+    }
 
-    Ok(())
+    if let Err(e) = checkout_branch(&repo, name, Some(&refname), target) {
+        let e = if stashed {
+            restore_auto_stash(&mut repo, e)
+        } else {
+            e
+        };
+        if let Some((operation, _)) = &operation {
+            // Best effort, so a journal error cannot hide the auto-stash state
+            let _ = journal::discard_last(&repo, operation);
+        }
+        return Err(e);
+    }
+
+    if !stashed {
+        return Ok(SwitchResult::default());
+    }
+    let restored = stash::apply(&mut repo, 0, true)?;
+    Ok(SwitchResult {
+        stashed: true,
+        restored: restored.applied && restored.conflicting_paths.is_empty(),
+        conflicting_paths: restored.conflicting_paths,
+    })
+}
+
+/// Pop the auto-stash back after a failed switch. When it cannot be restored
+/// cleanly, `err` is extended with where the changes were left.
+fn restore_auto_stash(repo: &mut git2::Repository, err: anyhow::Error) -> anyhow::Error {
+    match stash::apply(repo, 0, true) {
+        Ok(restored) if restored.applied && restored.conflicting_paths.is_empty() => err,
+        Ok(restored) if restored.applied => err.context(format!(
+            "Restoring the auto-stash left conflicts in {}; changes still in stash@{{0}}",
+            restored.conflicting_paths.join(", ")
+        )),
+        Ok(restored) => err.context(format!(
+            "Auto-stash could not be restored over {}; changes still in stash@{{0}}",
+            restored.conflicting_paths.join(", ")
+        )),
+        Err(apply_err) => err.context(format!(
+            "Auto-stash could not be restored ({apply_err:#}); changes still in stash@{{0}}"
+        )),
+    }
 }
 
 /// Delete a local branch. Refuses to delete the currently checked-out branch.
+///
+/// The branch's target is recorded in the operation journal first, so the
+/// deletion can be reverted with `journal::undo_last_operation`.
 pub fn delete_branch(repo_path: &str, name: &str) -> Result<()> {
     let repo = open_repo(repo_path)?;
 
@@ -101,9 +300,24 @@ pub fn delete_branch(repo_path: &str, name: &str) -> Result<()> {
         anyhow::bail!("Cannot delete the currently checked-out branch '{name}'");
     }
 
-    branch
-        .delete()
-        .with_context(|| format!("Failed to delete branch '{name}'"))?;
+    let target = branch
+        .get()
+        .target()
+        .with_context(|| format!("Branch '{name}' has no target"))?;
+    let upstream = branch
+        .upstream()
+        .ok()
+        .and_then(|u| u.name().ok().flatten().map(str::to_string));
+    let operation = Operation::DeleteBranch {
+        branch: name.to_string(),
+        upstream,
+    };
+    journal::record(&repo, &operation, target)?;
+
+    if let Err(e) = branch.delete() {
+        journal::discard_last(&repo, &operation)?;
+        return Err(e).with_context(|| format!("Failed to delete branch '{name}'"));
+    }
 
     Ok(())
 }
@@ -119,53 +333,306 @@ pub fn list_enriched_branches(
 
     let repo = open_repo(repo_path)?;
 
-    // リモートブランチ名を収集
-    let mut remote_branch_names = std::collections::HashSet::new();
-    if let Ok(remote_branches) = repo.branches(Some(BranchType::Remote)) {
-        for (branch, _) in remote_branches.flatten() {
-            if let Ok(Some(name)) = branch.name() {
-                // "origin/feature/foo" → "feature/foo"
-                if let Some((_, short)) = name.split_once('/') {
-                    remote_branch_names.insert(short.to_string());
-                }
+    // リモートごとのブランチ名を収集: ブランチ名 → そのブランチがあるリモート名
+    let remotes = remote_names(&repo)?;
+    let mut branch_remotes: HashMap<String, Vec<String>> = HashMap::new();
+    for reference in repo.references_glob("refs/remotes/*")?.flatten() {
+        if let Some((remote, short)) = reference
+            .name()
+            .and_then(|name| split_remote_ref(name, &remotes))
+        {
+            if short != "HEAD" {
+                branch_remotes
+                    .entry(short.to_string())
+                    .or_default()
+                    .push(remote.to_string());
             }
         }
     }
 
-    let enriched = branches
-        .into_iter()
-        .map(|b| {
-            // worktree のマッチング: worktree の branch 名と一致するか
-            let wt = worktrees
-                .iter()
-                .find(|w| w.branch.as_deref() == Some(&b.name));
-
-            // PR のマッチング: head_branch がブランチ名と一致する open な PR
-            let pr = pull_requests
-                .iter()
-                .find(|p| p.head_branch == b.name && p.state == "open");
-
-            EnrichedBranchInfo {
-                is_local: true,
-                is_remote: remote_branch_names.contains(&b.name),
-                has_worktree: wt.is_some(),
-                worktree_path: wt.map(|w| w.path.clone()),
-                pr_number: pr.map(|p| p.number),
-                pr_title: pr.map(|p| p.title.clone()),
-                name: b.name,
-                is_head: b.is_head,
-                upstream: b.upstream,
+    let default = default_branch(&repo);
+
+    let mut enriched = Vec::with_capacity(branches.len());
+    for b in branches {
+        // worktree のマッチング: worktree の branch 名と一致するか
+        let wt = worktrees
+            .iter()
+            .find(|w| w.branch.as_deref() == Some(&b.name));
+
+        // 設定された upstream（branch.<name>.remote / merge）を解決する
+        let refname = format!("refs/heads/{}", b.name);
+        let upstream_ref = repo
+            .branch_upstream_name(&refname)
+            .ok()
+            .and_then(|buf| buf.as_str().map(str::to_string));
+        let upstream_remote = repo
+            .branch_upstream_remote(&refname)
+            .ok()
+            .and_then(|buf| buf.as_str().map(str::to_string))
+            .filter(|remote| remote != ".");
+        let upstream_oid = upstream_ref
+            .as_deref()
+            .and_then(|r| repo.refname_to_id(r).ok());
+        // upstream 側のブランチ名（ローカル名と異なる場合がある）
+        let upstream_branch = upstream_ref
+            .as_deref()
+            .and_then(|r| split_remote_ref(r, &remotes))
+            .map(|(_, short)| short.to_string());
+
+        let mut on_remotes = branch_remotes.get(&b.name).cloned().unwrap_or_default();
+        if let (Some(remote), Some(_)) = (&upstream_remote, upstream_oid) {
+            if !on_remotes.contains(remote) {
+                on_remotes.push(remote.clone());
             }
-        })
-        .collect();
+        }
+        on_remotes.sort();
+
+        // PR のマッチング: head_branch がブランチ名（または upstream 側の名前）と一致する
+        // open な PR。head リポジトリが分かる場合は、ブランチの push 先リポジトリと
+        // 一致するものだけを対象にする（fork の同名ブランチと取り違えないため）
+        let home_repo = remote_github_repo(&repo, upstream_remote.as_deref().unwrap_or("origin"));
+        let is_branch_pr = |p: &&PrInfo| {
+            (p.head_branch == b.name || upstream_branch.as_deref() == Some(&p.head_branch))
+                && match (&p.head_repo, &home_repo) {
+                    (Some(head), Some(home)) => head.eq_ignore_ascii_case(home),
+                    _ => true,
+                }
+        };
+        let pr = pull_requests
+            .iter()
+            .filter(is_branch_pr)
+            .find(|p| p.state == "open");
+        let pr_merged = pull_requests
+            .iter()
+            .filter(is_branch_pr)
+            .any(|p| p.state == "merged");
+
+        let tip = repo
+            .find_branch(&b.name, BranchType::Local)?
+            .get()
+            .peel_to_commit()
+            .with_context(|| format!("Failed to resolve branch '{}'", b.name))?;
+
+        let upstream_ahead_behind = match upstream_oid {
+            Some(oid) => Some(AheadBehind::between(&repo, tip.id(), oid)?),
+            None => None,
+        };
+
+        // ベースブランチ: PR の base を優先し、なければデフォルトブランチ
+        let base = pr
+            .and_then(|p| resolve_branch(&repo, &p.base_branch))
+            .or_else(|| default.clone());
+        let base_ahead_behind = match &base {
+            Some((_, oid)) => Some(AheadBehind::between(&repo, tip.id(), *oid)?),
+            None => None,
+        };
+
+        // 作成後にコミットのないブランチは、デフォルトブランチから到達可能でも
+        // マージ済みとはみなさない（作成直後のブランチを消さないため）。
+        // reflog が無い（期限切れや clone・fetch で作られた）ブランチは作成時点が
+        // 分からないので、先端がデフォルトブランチの先端と異なり、
+        // そこから到達可能ならマージ済みとする
+        let is_merged = pr_merged
+            || match &default {
+                Some((_, oid)) => match branch_start(&repo, &refname) {
+                    Some(start) if start == tip.id() => false,
+                    Some(_) => *oid == tip.id() || repo.graph_descendant_of(*oid, tip.id())?,
+                    None => *oid != tip.id() && repo.graph_descendant_of(*oid, tip.id())?,
+                },
+                None => false,
+            };
+
+        enriched.push(EnrichedBranchInfo {
+            is_local: true,
+            is_remote: !on_remotes.is_empty(),
+            remotes: on_remotes,
+            upstream_remote,
+            has_worktree: wt.is_some(),
+            worktree_path: wt.map(|w| w.path.clone()),
+            pr_number: pr.map(|p| p.number),
+            pr_title: pr.map(|p| p.title.clone()),
+            upstream_ahead_behind,
+            base_branch: base.map(|(name, _)| name),
+            base_ahead_behind,
+            last_commit_sha: Some(tip.id().to_string()),
+            last_commit_author: tip.author().name().map(str::to_string),
+            last_commit_time: Some(tip.author().when().seconds()),
+            is_merged,
+            name: b.name,
+            is_head: b.is_head,
+            upstream: b.upstream,
+        });
+    }
 
     Ok(enriched)
 }
 
+/// デフォルトブランチにマージ済みのブランチを一括削除する。
+///
+/// worktree でチェックアウト中のブランチ、open な PR があるブランチ、
+/// デフォルトブランチ自身は削除しない。削除したブランチ名を返す。
+pub fn cleanup_merged_branches(repo_path: &str, pull_requests: &[PrInfo]) -> Result<Vec<String>> {
+    let repo = open_repo(repo_path)?;
+    let remotes = remote_names(&repo)?;
+    let default_name = default_branch(&repo).map(|(name, _)| {
+        split_remote_ref(&format!("refs/remotes/{name}"), &remotes)
+            .map(|(_, branch)| branch.to_string())
+            .unwrap_or(name)
+    });
+
+    let mut deleted = Vec::new();
+    for b in list_enriched_branches(repo_path, pull_requests)? {
+        if !b.is_merged
+            || b.is_head
+            || b.has_worktree
+            || b.pr_number.is_some()
+            || default_name.as_deref() == Some(b.name.as_str())
+        {
+            continue;
+        }
+        delete_branch(repo_path, &b.name)?;
+        deleted.push(b.name);
+    }
+
+    Ok(deleted)
+}
+
+/// Safely check out `target` and point HEAD at `refname`, or detach HEAD at
+/// `target` when `refname` is `None`.
+pub(crate) fn checkout_branch(
+    repo: &git2::Repository,
+    name: &str,
+    refname: Option<&str>,
+    target: Oid,
+) -> Result<()> {
+    let commit = repo.find_commit(target)?;
+    let mut conflicting_paths = Vec::new();
+    let result = {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout
+            .safe()
+            .notify_on(git2::CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path.and_then(|p| p.to_str()) {
+                    conflicting_paths.push(path.to_string());
+                }
+                true
+            });
+        repo.checkout_tree(commit.as_object(), Some(&mut checkout))
+    };
+    if let Err(e) = result {
+        if !conflicting_paths.is_empty() {
+            return Err(SwitchConflict {
+                branch: name.to_string(),
+                conflicting_paths,
+            }
+            .into());
+        }
+        return Err(e).with_context(|| format!("Failed to checkout '{name}'"));
+    }
+
+    match refname {
+        Some(refname) => repo.set_head(refname),
+        None => repo.set_head_detached(target),
+    }
+    .with_context(|| format!("Failed to set HEAD to '{name}'"))?;
+    Ok(())
+}
+
+/// ベースブランチを探すリモートの優先順（fork 構成の upstream、origin の順）。
+fn preferred_remotes(repo: &git2::Repository) -> Vec<String> {
+    let mut remotes: Vec<String> = super::upstream_remote(repo).into_iter().collect();
+    if !remotes.iter().any(|r| r == "origin") {
+        remotes.push("origin".to_string());
+    }
+    remotes
+}
+
+/// ブランチ作成時に指していたコミット（reflog の最古のエントリ）。
+/// reflog がなければ `None`。
+fn branch_start(repo: &git2::Repository, refname: &str) -> Option<Oid> {
+    let reflog = repo.reflog(refname).ok()?;
+    reflog.iter().next_back().map(|entry| entry.id_new())
+}
+
+/// リポジトリに設定されたリモート名。長いものから順に並べる。
+fn remote_names(repo: &git2::Repository) -> Result<Vec<String>> {
+    let mut names: Vec<String> = repo
+        .remotes()
+        .context("Failed to list remotes")?
+        .iter()
+        .flatten()
+        .map(str::to_string)
+        .collect();
+    // "origin" と "origin/mirror" のように前方一致するリモートがあっても長い方を優先する
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    Ok(names)
+}
+
+/// `refs/remotes/<remote>/<branch>` をリモート名とブランチ名に分ける。
+/// `remotes` は `remote_names` の結果（長い順）を渡す。
+fn split_remote_ref<'a>(refname: &'a str, remotes: &[String]) -> Option<(&'a str, &'a str)> {
+    let rest = refname.strip_prefix("refs/remotes/")?;
+    remotes.iter().find_map(|remote| {
+        let branch = rest.strip_prefix(remote.as_str())?.strip_prefix('/')?;
+        Some((&rest[..remote.len()], branch))
+    })
+}
+
+/// リモートの URL から GitHub の `owner/repo` を得る。
+fn remote_github_repo(repo: &git2::Repository, remote: &str) -> Option<String> {
+    let remote = repo.find_remote(remote).ok()?;
+    let (owner, name) = parse_github_remote(remote.url()?)?;
+    Some(format!("{owner}/{name}"))
+}
+
+/// ブランチ名を `<upstream>/<name>`、`origin/<name>`、ローカル `<name>` の順で解決する。
+fn resolve_branch(repo: &git2::Repository, name: &str) -> Option<(String, Oid)> {
+    for remote in preferred_remotes(repo) {
+        let remote_branch = format!("{remote}/{name}");
+        if let Some(oid) = repo
+            .find_branch(&remote_branch, BranchType::Remote)
+            .ok()
+            .and_then(|b| b.get().target())
+        {
+            return Some((remote_branch, oid));
+        }
+    }
+    let oid = repo
+        .find_branch(name, BranchType::Local)
+        .ok()?
+        .get()
+        .target()?;
+    Some((name.to_string(), oid))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::test_utils::init_test_repo;
+    use crate::git::test_utils::{
+        commit_file_as, init_repo_with_commit, init_test_repo, signature_at,
+    };
+    use git2::Repository;
+
+    /// `branch` の先端に `file` を書き換えるコミットを積む（ワークツリーは触らない）。
+    fn open_pr(number: u64, head: &str, base: &str) -> PrInfo {
+        PrInfo {
+            number,
+            title: format!("PR {number}"),
+            author: "alice".to_string(),
+            state: "open".to_string(),
+            merge_commit_sha: None,
+            head_branch: head.to_string(),
+            head_repo: None,
+            base_branch: base.to_string(),
+            updated_at: "2025-01-15T10:30:00Z".to_string(),
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: String::new(),
+            html_url: String::new(),
+        }
+    }
 
     #[test]
     fn test_list_branches_single_branch() {
@@ -259,7 +726,9 @@ mod tests {
             title: "Add feature X".to_string(),
             author: "alice".to_string(),
             state: "open".to_string(),
+            merge_commit_sha: None,
             head_branch: "feature-x".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-15T10:30:00Z".to_string(),
             additions: 10,
@@ -286,7 +755,9 @@ mod tests {
             title: "Old PR".to_string(),
             author: "bob".to_string(),
             state: "closed".to_string(),
+            merge_commit_sha: None,
             head_branch: "old-branch".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
             additions: 0,
@@ -300,4 +771,513 @@ mod tests {
         let branch = enriched.iter().find(|b| b.name == "old-branch").unwrap();
         assert!(branch.pr_number.is_none());
     }
+
+    #[test]
+    fn test_list_enriched_branches_freshness() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        commit_file_as(
+            &repo,
+            "feature",
+            "a.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        let tip = commit_file_as(
+            &repo,
+            "feature",
+            "a.txt",
+            "Bob 2000\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+        commit_file_as(
+            &repo,
+            "main",
+            "b.txt",
+            "Carol 3000\n",
+            "edit",
+            &signature_at("Carol", 3_000),
+        );
+
+        let enriched = list_enriched_branches(path, &[]).unwrap();
+        let feature = enriched.iter().find(|b| b.name == "feature").unwrap();
+        assert_eq!(feature.last_commit_sha, Some(tip.to_string()));
+        assert_eq!(feature.last_commit_author.as_deref(), Some("Bob"));
+        assert_eq!(feature.last_commit_time, Some(2_000));
+        assert_eq!(feature.base_branch.as_deref(), Some("main"));
+        assert_eq!(
+            feature.base_ahead_behind,
+            Some(AheadBehind {
+                ahead: 2,
+                behind: 1
+            })
+        );
+        assert!(feature.upstream_ahead_behind.is_none());
+        assert!(!feature.is_merged);
+    }
+
+    #[test]
+    fn test_list_enriched_branches_upstream_and_pr_base() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        repo.remote("origin", "https://github.com/owner/repo.git")
+            .unwrap();
+        create_branch(path, "release").unwrap();
+        create_branch(path, "feature").unwrap();
+        let pushed = commit_file_as(
+            &repo,
+            "feature",
+            "a.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        repo.reference("refs/remotes/origin/feature", pushed, true, "test")
+            .unwrap();
+        repo.find_branch("feature", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/feature"))
+            .unwrap();
+        commit_file_as(
+            &repo,
+            "feature",
+            "a.txt",
+            "Alice 2000\n",
+            "edit",
+            &signature_at("Alice", 2_000),
+        );
+        commit_file_as(
+            &repo,
+            "release",
+            "r.txt",
+            "Bob 3000\n",
+            "edit",
+            &signature_at("Bob", 3_000),
+        );
+
+        let prs = vec![open_pr(7, "feature", "release")];
+        let enriched = list_enriched_branches(path, &prs).unwrap();
+        let feature = enriched.iter().find(|b| b.name == "feature").unwrap();
+        assert_eq!(
+            feature.upstream_ahead_behind,
+            Some(AheadBehind {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        // PR の base（release）と比較する
+        assert_eq!(feature.base_branch.as_deref(), Some("release"));
+        assert_eq!(
+            feature.base_ahead_behind,
+            Some(AheadBehind {
+                ahead: 2,
+                behind: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_list_enriched_branches_merged() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        for name in ["merged", "fresh", "unmerged", "squashed"] {
+            create_branch(path, name).unwrap();
+        }
+        let tip = commit_file_as(
+            &repo,
+            "merged",
+            "a.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        repo.reference("refs/heads/main", tip, true, "fast-forward")
+            .unwrap();
+        commit_file_as(
+            &repo,
+            "main",
+            "b.txt",
+            "Bob 2000\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+        commit_file_as(
+            &repo,
+            "unmerged",
+            "c.txt",
+            "Carol 3000\n",
+            "edit",
+            &signature_at("Carol", 3_000),
+        );
+        commit_file_as(
+            &repo,
+            "squashed",
+            "d.txt",
+            "Dave 4000\n",
+            "edit",
+            &signature_at("Dave", 4_000),
+        );
+
+        let mut squashed_pr = open_pr(3, "squashed", "main");
+        squashed_pr.state = "merged".to_string();
+        let enriched = list_enriched_branches(path, &[squashed_pr]).unwrap();
+        let is_merged = |name: &str| enriched.iter().find(|b| b.name == name).unwrap().is_merged;
+        assert!(is_merged("merged"));
+        // 作成後にコミットのないブランチはデフォルトブランチから到達可能でもマージ済みにしない
+        assert!(!is_merged("fresh"));
+        assert!(!is_merged("unmerged"));
+        // squash マージなどで先端が到達不能でも PR がマージ済みならマージ済み
+        assert!(is_merged("squashed"));
+    }
+
+    #[test]
+    fn test_list_enriched_branches_merged_without_reflog() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        for name in ["merged", "at-main", "unmerged"] {
+            create_branch(path, name).unwrap();
+        }
+        let tip = commit_file_as(
+            &repo,
+            "merged",
+            "a.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        repo.reference("refs/heads/main", tip, true, "fast-forward")
+            .unwrap();
+        let main = commit_file_as(
+            &repo,
+            "main",
+            "b.txt",
+            "Bob 2000\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+        repo.reference("refs/heads/at-main", main, true, "fast-forward")
+            .unwrap();
+        commit_file_as(
+            &repo,
+            "unmerged",
+            "c.txt",
+            "Carol 3000\n",
+            "edit",
+            &signature_at("Carol", 3_000),
+        );
+        for name in ["merged", "at-main", "unmerged"] {
+            repo.reflog_delete(&format!("refs/heads/{name}")).unwrap();
+        }
+
+        let enriched = list_enriched_branches(path, &[]).unwrap();
+        let is_merged = |name: &str| enriched.iter().find(|b| b.name == name).unwrap().is_merged;
+        // reflog が無くても、デフォルトブランチから到達可能ならマージ済み
+        assert!(is_merged("merged"));
+        // デフォルトブランチの先端と同じブランチはマージ済みにしない
+        assert!(!is_merged("at-main"));
+        assert!(!is_merged("unmerged"));
+    }
+
+    #[test]
+    fn test_cleanup_merged_branches() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "done").unwrap();
+        let done = commit_file_as(
+            &repo,
+            "done",
+            "d.txt",
+            "Dave 500\n",
+            "edit",
+            &signature_at("Dave", 500),
+        );
+        repo.reference("refs/heads/main", done, true, "fast-forward")
+            .unwrap();
+        for name in ["with-pr", "unmerged", "fresh"] {
+            create_branch(path, name).unwrap();
+        }
+        let with_pr = commit_file_as(
+            &repo,
+            "with-pr",
+            "p.txt",
+            "Pat 600\n",
+            "edit",
+            &signature_at("Pat", 600),
+        );
+        repo.reference("refs/heads/main", with_pr, true, "fast-forward")
+            .unwrap();
+        commit_file_as(
+            &repo,
+            "unmerged",
+            "a.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        let wt_path = dir.path().join("wt-checked-out");
+        crate::git::worktree::add_worktree(path, wt_path.to_str().unwrap(), "checked-out").unwrap();
+        commit_file_as(
+            &repo,
+            "main",
+            "b.txt",
+            "Bob 2000\n",
+            "edit",
+            &signature_at("Bob", 2_000),
+        );
+
+        let prs = vec![open_pr(1, "with-pr", "main")];
+        let deleted = cleanup_merged_branches(path, &prs).unwrap();
+        assert_eq!(deleted, vec!["done".to_string()]);
+
+        let remaining: Vec<String> = list_branches(path)
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        for name in ["main", "with-pr", "unmerged", "checked-out", "fresh"] {
+            assert!(remaining.iter().any(|b| b == name), "{name} was deleted");
+        }
+    }
+
+    /// origin（自分の fork）と upstream（本家）の 2 リモート構成を作る。
+    fn init_fork_repo() -> (tempfile::TempDir, Repository) {
+        let (dir, repo) = init_repo_with_commit();
+        repo.remote("origin", "git@github.com:me/repo.git").unwrap();
+        repo.remote("upstream", "https://github.com/owner/repo.git")
+            .unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        for r in [
+            "refs/remotes/origin/feature/foo",
+            "refs/remotes/upstream/feature/foo",
+            "refs/remotes/upstream/main",
+            "refs/remotes/origin/feature-v2",
+        ] {
+            repo.reference(r, head, true, "test").unwrap();
+        }
+        (dir, repo)
+    }
+
+    fn track(repo: &Repository, local: &str, upstream: &str) {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch(local, &head, false)
+            .unwrap()
+            .set_upstream(Some(upstream))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_list_enriched_branches_multi_remote() {
+        let (dir, repo) = init_fork_repo();
+        track(&repo, "feature/foo", "origin/feature/foo");
+        let path = dir.path().to_str().unwrap();
+
+        let enriched = list_enriched_branches(path, &[]).unwrap();
+        let foo = enriched.iter().find(|b| b.name == "feature/foo").unwrap();
+        assert!(foo.is_remote);
+        assert_eq!(foo.remotes, vec!["origin", "upstream"]);
+        assert_eq!(foo.upstream_remote.as_deref(), Some("origin"));
+
+        let main = enriched.iter().find(|b| b.name == "main").unwrap();
+        assert_eq!(main.remotes, vec!["upstream"]);
+        assert!(main.upstream_remote.is_none());
+    }
+
+    #[test]
+    fn test_list_enriched_branches_upstream_with_other_name() {
+        let (dir, repo) = init_fork_repo();
+        track(&repo, "work", "origin/feature-v2");
+        let path = dir.path().to_str().unwrap();
+
+        let mut pr = open_pr(5, "feature-v2", "main");
+        pr.head_repo = Some("me/repo".to_string());
+        let enriched = list_enriched_branches(path, &[pr]).unwrap();
+        let work = enriched.iter().find(|b| b.name == "work").unwrap();
+        assert!(work.is_remote);
+        assert_eq!(work.remotes, vec!["origin"]);
+        assert_eq!(
+            work.upstream_ahead_behind,
+            Some(AheadBehind {
+                ahead: 0,
+                behind: 0
+            })
+        );
+        assert_eq!(work.pr_number, Some(5));
+    }
+
+    #[test]
+    fn test_list_enriched_branches_fork_pr_does_not_collide() {
+        let (dir, repo) = init_fork_repo();
+        track(&repo, "feature/foo", "origin/feature/foo");
+        let path = dir.path().to_str().unwrap();
+
+        let mut other_fork = open_pr(1, "feature/foo", "main");
+        other_fork.head_repo = Some("someone/repo".to_string());
+        let enriched = list_enriched_branches(path, std::slice::from_ref(&other_fork)).unwrap();
+        let foo = enriched.iter().find(|b| b.name == "feature/foo").unwrap();
+        assert!(foo.pr_number.is_none());
+
+        let mut ours = open_pr(2, "feature/foo", "main");
+        ours.head_repo = Some("Me/Repo".to_string());
+        let enriched = list_enriched_branches(path, &[other_fork, ours]).unwrap();
+        let foo = enriched.iter().find(|b| b.name == "feature/foo").unwrap();
+        assert_eq!(foo.pr_number, Some(2));
+    }
+
+    #[test]
+    fn test_default_branch_prefers_fork_upstream() {
+        let (_dir, repo) = init_fork_repo();
+        let head = repo.head().unwrap().target().unwrap();
+        let (name, oid) = default_branch(&repo).unwrap();
+        assert_eq!(name, "upstream/main");
+        assert_eq!(oid, head);
+
+        let (name, _) = resolve_branch(&repo, "feature/foo").unwrap();
+        assert_eq!(name, "upstream/feature/foo");
+        let (name, _) = resolve_branch(&repo, "feature-v2").unwrap();
+        assert_eq!(name, "origin/feature-v2");
+    }
+
+    #[test]
+    fn test_split_remote_ref_prefers_longest_remote() {
+        let remotes = vec!["origin/mirror".to_string(), "origin".to_string()];
+        assert_eq!(
+            split_remote_ref("refs/remotes/origin/mirror/main", &remotes),
+            Some(("origin/mirror", "main"))
+        );
+        assert_eq!(
+            split_remote_ref("refs/remotes/origin/feature/foo", &remotes),
+            Some(("origin", "feature/foo"))
+        );
+        assert_eq!(split_remote_ref("refs/heads/main", &remotes), None);
+    }
+
+    #[test]
+    fn test_switch_branch_conflict_lists_paths() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        commit_file_as(
+            &repo,
+            "feature",
+            "hello.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        std::fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+
+        let err = switch_branch(path, "feature").unwrap_err();
+        let conflict = err.downcast_ref::<SwitchConflict>().unwrap();
+        assert_eq!(conflict.branch, "feature");
+        assert_eq!(conflict.conflicting_paths, vec!["hello.txt"]);
+        // HEAD and the local change stay where they were
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "local\n"
+        );
+    }
+
+    #[test]
+    fn test_switch_branch_auto_stash_restores_changes() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        commit_file_as(
+            &repo,
+            "feature",
+            "other.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        std::fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+        std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+        let result =
+            switch_branch_with(path, "feature", &SwitchOptions { auto_stash: true }).unwrap();
+        assert!(result.stashed);
+        assert!(result.restored);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+        assert!(dir.path().join("other.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "local\n"
+        );
+        assert!(dir.path().join("new.txt").exists());
+        assert!(stash::list_stashes(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_switch_branch_auto_stash_clean_worktree() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+
+        let result =
+            switch_branch_with(path, "feature", &SwitchOptions { auto_stash: true }).unwrap();
+        assert_eq!(result, SwitchResult::default());
+    }
+
+    #[test]
+    fn test_switch_branch_auto_stash_conflict_keeps_stash() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        commit_file_as(
+            &repo,
+            "feature",
+            "hello.txt",
+            "Alice 1000\n",
+            "edit",
+            &signature_at("Alice", 1_000),
+        );
+        std::fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+
+        let result =
+            switch_branch_with(path, "feature", &SwitchOptions { auto_stash: true }).unwrap();
+        assert!(result.stashed);
+        assert!(!result.restored);
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+        assert!(std::fs::read_to_string(dir.path().join("hello.txt"))
+            .unwrap()
+            .contains("<<<<<<<"));
+        assert_eq!(stash::list_stashes(path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_auto_stash_reports_kept_stash() {
+        let (dir, mut repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "stashed\n").unwrap();
+        stash::save(&mut repo, None, true).unwrap().unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+
+        let err = restore_auto_stash(&mut repo, anyhow::anyhow!("checkout failed"));
+        let message = format!("{err:#}");
+        assert!(message.contains("stash@{0}"));
+        assert!(message.contains("hello.txt"));
+        assert!(message.contains("checkout failed"));
+        assert_eq!(stash::list_stashes(path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_auto_stash_clean_keeps_error() {
+        let (dir, mut repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("hello.txt"), "stashed\n").unwrap();
+        stash::save(&mut repo, None, true).unwrap().unwrap();
+
+        let err = restore_auto_stash(&mut repo, anyhow::anyhow!("checkout failed"));
+        assert_eq!(format!("{err:#}"), "checkout failed");
+        assert!(stash::list_stashes(path).unwrap().is_empty());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "stashed\n"
+        );
+    }
 }
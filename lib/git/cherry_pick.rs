@@ -0,0 +1,353 @@
+use anyhow::{Context, Result};
+use git2::{CherrypickOptions, Repository, RepositoryState, RevertOptions};
+
+use super::open_repo;
+use super::rebase::tracked_changes;
+use super::stash::conflicted_paths;
+
+/// Options for `cherry_pick` and `revert`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PickOptions {
+    /// Parent (1-based) to diff a merge commit against, like `git -m`.
+    /// Required for merge commits and rejected for any other commit.
+    pub mainline: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PickResult {
+    /// The new commit, or `None` when the operation stopped on conflicts.
+    pub commit: Option<String>,
+    /// Conflicting paths. Resolve and stage them, then call `continue_pick`,
+    /// or give up with `abort_pick`.
+    pub conflicting_paths: Vec<String>,
+}
+
+/// Apply the changes of commit `rev` on top of HEAD in `worktree_path`.
+///
+/// The new commit keeps the original author and message.
+pub fn cherry_pick(worktree_path: &str, rev: &str, options: &PickOptions) -> Result<PickResult> {
+    let repo = open_repo(worktree_path)?;
+    let commit = prepare(&repo, rev, options)?;
+    let mut opts = CherrypickOptions::new();
+    if let Some(mainline) = options.mainline {
+        opts.mainline(mainline);
+    }
+    repo.cherrypick(&commit, Some(&mut opts))
+        .with_context(|| format!("Failed to cherry-pick '{rev}'"))?;
+    finish(&repo)
+}
+
+/// Create a commit on HEAD in `worktree_path` that undoes commit `rev`.
+///
+/// For a merge commit, `mainline` selects the parent whose side is kept;
+/// `1` undoes everything the merge brought into the first parent.
+pub fn revert(worktree_path: &str, rev: &str, options: &PickOptions) -> Result<PickResult> {
+    let repo = open_repo(worktree_path)?;
+    let commit = prepare(&repo, rev, options)?;
+    let mut opts = RevertOptions::new();
+    if let Some(mainline) = options.mainline {
+        opts.mainline(mainline);
+    }
+    repo.revert(&commit, Some(&mut opts))
+        .with_context(|| format!("Failed to revert '{rev}'"))?;
+    finish(&repo)
+}
+
+/// Commit a cherry-pick or revert whose conflicts have been resolved and
+/// staged.
+pub fn continue_pick(worktree_path: &str) -> Result<PickResult> {
+    let repo = open_repo(worktree_path)?;
+    in_progress(&repo)?;
+    let unresolved = conflicted_paths(&repo)?;
+    if !unresolved.is_empty() {
+        anyhow::bail!("Unresolved conflicts remain: {}", unresolved.join(", "));
+    }
+    finish(&repo)
+}
+
+/// Abandon a cherry-pick or revert that stopped on conflicts, resetting the
+/// index and worktree to HEAD.
+pub fn abort_pick(worktree_path: &str) -> Result<()> {
+    let repo = open_repo(worktree_path)?;
+    in_progress(&repo)?;
+    let head = repo.head()?.peel_to_commit()?;
+    repo.reset(head.as_object(), git2::ResetType::Hard, None)
+        .context("Failed to reset to HEAD")?;
+    repo.cleanup_state().context("Failed to clean up state")
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn prepare<'r>(repo: &'r Repository, rev: &str, options: &PickOptions) -> Result<git2::Commit<'r>> {
+    if repo.state() != RepositoryState::Clean {
+        anyhow::bail!("Another operation is in progress ({:?})", repo.state());
+    }
+    let dirty = tracked_changes(repo)?;
+    if !dirty.is_empty() {
+        anyhow::bail!("Worktree has uncommitted changes: {}", dirty.join(", "));
+    }
+
+    let commit = repo
+        .revparse_single(rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Revision '{rev}' not found"))?;
+    let parents = commit.parent_count();
+    match options.mainline {
+        None if parents > 1 => {
+            anyhow::bail!("Commit '{rev}' is a merge; a mainline parent must be specified")
+        }
+        Some(_) if parents < 2 => {
+            anyhow::bail!("Mainline was specified but commit '{rev}' is not a merge")
+        }
+        Some(n) if n == 0 || n as usize > parents => {
+            anyhow::bail!("Commit '{rev}' has no parent {n}")
+        }
+        _ => {}
+    }
+    Ok(commit)
+}
+
+fn in_progress(repo: &Repository) -> Result<()> {
+    match repo.state() {
+        RepositoryState::CherryPick | RepositoryState::Revert => Ok(()),
+        _ => anyhow::bail!("No cherry-pick or revert in progress"),
+    }
+}
+
+/// Commit the prepared index with the message libgit2 left in MERGE_MSG,
+/// unless conflicts remain. Like git, refuses to create an empty commit when
+/// the changes are already in HEAD; the operation is ended in that case.
+fn finish(repo: &Repository) -> Result<PickResult> {
+    let conflicting_paths = conflicted_paths(repo)?;
+    if !conflicting_paths.is_empty() {
+        return Ok(PickResult {
+            commit: None,
+            conflicting_paths,
+        });
+    }
+
+    let signature = repo
+        .signature()
+        .context("user.name and user.email must be set in git config")?;
+    let author = if repo.state() == RepositoryState::CherryPick {
+        let picked = repo
+            .find_reference("CHERRY_PICK_HEAD")
+            .and_then(|r| r.peel_to_commit())
+            .context("CHERRY_PICK_HEAD not found")?;
+        let author = picked.author().to_owned();
+        author
+    } else {
+        signature.clone()
+    };
+    let message = repo.message().context("Failed to read prepared message")?;
+
+    let mut index = repo.index().context("Failed to read index")?;
+    let tree = repo.find_tree(index.write_tree().context("Failed to write tree")?)?;
+    let head = repo.head()?.peel_to_commit()?;
+    if tree.id() == head.tree_id() {
+        repo.cleanup_state().context("Failed to clean up state")?;
+        anyhow::bail!("Nothing to commit: the changes are already in HEAD");
+    }
+    let oid = repo
+        .commit(Some("HEAD"), &author, &signature, &message, &tree, &[&head])
+        .context("Failed to create commit")?;
+    repo.cleanup_state().context("Failed to clean up state")?;
+
+    Ok(PickResult {
+        commit: Some(oid.to_string()),
+        conflicting_paths: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{
+        commit_file, commit_file_as, init_repo_with_commit, signature_at,
+    };
+    use git2::{Oid, Signature};
+    use std::fs;
+    use std::path::Path;
+
+    fn checkout(repo: &Repository, branch: &str) {
+        repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+    }
+
+    /// Merge `feature` (which adds feature.txt) into `main` with a merge
+    /// commit, returning its id.
+    fn merged_feature(repo: &Repository) -> Oid {
+        {
+            let base = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &base, false).unwrap();
+        }
+        checkout(repo, "feature");
+        let feature = commit_file(repo, "HEAD", "feature.txt", "feature\n", "Add feature");
+        checkout(repo, "main");
+        commit_file(repo, "HEAD", "main.txt", "main\n", "Main work");
+
+        let main = repo.head().unwrap().peel_to_commit().unwrap();
+        let feature = repo.find_commit(feature).unwrap();
+        let mut index = repo.merge_commits(&main, &feature, None).unwrap();
+        let tree = repo.find_tree(index.write_tree_to(repo).unwrap()).unwrap();
+        let sig = Signature::now("Merger", "merger@test.com").unwrap();
+        let merge = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "Merge feature",
+                &tree,
+                &[&main, &feature],
+            )
+            .unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        merge
+    }
+
+    #[test]
+    fn test_cherry_pick_keeps_author_and_message() {
+        let (dir, repo) = init_repo_with_commit();
+        {
+            let base = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &base, false).unwrap();
+        }
+        checkout(&repo, "feature");
+        let author = signature_at("Author", 1_000);
+        let picked = commit_file_as(
+            &repo,
+            "HEAD",
+            "feature.txt",
+            "feature\n",
+            "Add feature",
+            &author,
+        );
+        checkout(&repo, "main");
+
+        let result = cherry_pick(
+            dir.path().to_str().unwrap(),
+            &picked.to_string(),
+            &PickOptions::default(),
+        )
+        .unwrap();
+
+        assert!(result.conflicting_paths.is_empty());
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit, Some(head.id().to_string()));
+        assert_ne!(head.id(), picked);
+        assert_eq!(head.author().name(), Some("Author"));
+        assert_eq!(head.committer().name(), Some("Test"));
+        assert_eq!(head.message(), Some("Add feature"));
+        assert!(dir.path().join("feature.txt").exists());
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_revert_merge_commit_with_mainline() {
+        let (dir, repo) = init_repo_with_commit();
+        let merge = merged_feature(&repo);
+        let path = dir.path().to_str().unwrap();
+
+        let err = revert(path, "HEAD", &PickOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("mainline"));
+
+        let result = revert(path, "HEAD", &PickOptions { mainline: Some(1) }).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit, Some(head.id().to_string()));
+        assert_eq!(head.parent_id(0).unwrap(), merge);
+        assert!(head
+            .message()
+            .unwrap()
+            .starts_with("Revert \"Merge feature\""));
+        assert!(!dir.path().join("feature.txt").exists());
+        assert!(dir.path().join("main.txt").exists());
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_mainline_rejected_for_non_merge() {
+        let (dir, _repo) = init_repo_with_commit();
+        let err = revert(
+            dir.path().to_str().unwrap(),
+            "HEAD",
+            &PickOptions { mainline: Some(1) },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not a merge"));
+    }
+
+    #[test]
+    fn test_revert_conflict_then_continue() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        let first = commit_file(&repo, "HEAD", "hello.txt", "first\n", "First");
+        commit_file(&repo, "HEAD", "hello.txt", "second\n", "Second");
+
+        let result = revert(path, &first.to_string(), &PickOptions::default()).unwrap();
+        assert_eq!(result.commit, None);
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        assert_eq!(repo.state(), RepositoryState::Revert);
+        assert!(continue_pick(path).is_err());
+
+        fs::write(dir.path().join("hello.txt"), "resolved\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        index.add_path(Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = continue_pick(path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit, Some(head.id().to_string()));
+        assert!(head.message().unwrap().starts_with("Revert \"First\""));
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_already_applied_is_refused() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        {
+            let base = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &base, false).unwrap();
+        }
+        checkout(&repo, "feature");
+        let picked = commit_file(&repo, "HEAD", "feature.txt", "feature\n", "Add feature");
+        checkout(&repo, "main");
+        let before = commit_file(&repo, "HEAD", "feature.txt", "feature\n", "Same change");
+
+        let err = cherry_pick(path, &picked.to_string(), &PickOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("already in HEAD"), "{err}");
+        assert_eq!(repo.head().unwrap().target().unwrap(), before);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_cherry_pick_conflict_then_abort() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        {
+            let base = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &base, false).unwrap();
+        }
+        let before = commit_file(&repo, "HEAD", "hello.txt", "main\n", "Main edit");
+        checkout(&repo, "feature");
+        let picked = commit_file(&repo, "HEAD", "hello.txt", "feature\n", "Feature edit");
+        checkout(&repo, "main");
+
+        let result = cherry_pick(path, &picked.to_string(), &PickOptions::default()).unwrap();
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        assert_eq!(repo.state(), RepositoryState::CherryPick);
+
+        abort_pick(path).unwrap();
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target().unwrap(), before);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "main\n"
+        );
+        assert!(abort_pick(path).is_err());
+    }
+}
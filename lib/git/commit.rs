@@ -0,0 +1,452 @@
+use anyhow::{Context, Result};
+use git2::{Repository, RepositoryState, Signature};
+
+use super::diff::{collect_diff, FileDiff, FileStatus};
+use super::open_repo;
+
+/// Options for `create_commit`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CommitRequest {
+    /// Commit message, typed by the user or taken from
+    /// `draft_commit_message`.
+    pub message: String,
+    /// Replace the HEAD commit instead of creating a new one.
+    pub amend: bool,
+    /// Append a `Signed-off-by:` trailer for the committer.
+    pub sign_off: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CommitResult {
+    pub sha: String,
+    pub summary: String,
+}
+
+/// Commit the current index of the worktree at `worktree_path`.
+///
+/// Works in the main worktree and in any linked worktree. Author and
+/// committer are taken from `user.name` / `user.email` in git config.
+/// When amending, the original author is kept and only the committer,
+/// message and tree are replaced.
+///
+/// During a merge, the commit gets every `MERGE_HEAD` as an extra parent.
+/// Once a merge, cherry-pick or revert is committed its state files are
+/// removed, as `git commit` does.
+pub fn create_commit(worktree_path: &str, request: &CommitRequest) -> Result<CommitResult> {
+    let mut repo = open_repo(worktree_path)?;
+    let signature = repo
+        .signature()
+        .context("user.name and user.email must be set in git config")?;
+
+    let mut message = git2::message_prettify(&request.message, None)
+        .context("Failed to clean up commit message")?;
+    if message.trim().is_empty() {
+        anyhow::bail!("Commit message is empty");
+    }
+    if request.sign_off {
+        message = append_sign_off(&message, &signature);
+    }
+
+    let mut index = repo.index().context("Failed to read index")?;
+    if index.has_conflicts() {
+        anyhow::bail!("Cannot commit: resolve the conflicts in the index first");
+    }
+    let merge_heads = merge_head_ids(&mut repo)?
+        .into_iter()
+        .map(|oid| {
+            repo.find_commit(oid)
+                .context("Failed to resolve MERGE_HEAD")
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let tree = repo.find_tree(index.write_tree().context("Failed to write tree")?)?;
+    let head = head_commit(&repo)?;
+
+    let oid = if request.amend {
+        let head = head.context("Cannot amend: HEAD has no commit yet")?;
+        if !merge_heads.is_empty() {
+            anyhow::bail!("Cannot amend while a merge is in progress");
+        }
+        head.amend(
+            Some("HEAD"),
+            None,
+            Some(&signature),
+            None,
+            Some(&message),
+            Some(&tree),
+        )
+        .context("Failed to amend commit")?
+    } else {
+        if merge_heads.is_empty() && head.as_ref().is_some_and(|h| h.tree_id() == tree.id()) {
+            anyhow::bail!("Nothing to commit: the index matches HEAD");
+        }
+        let parents: Vec<&git2::Commit<'_>> = head.iter().chain(&merge_heads).collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            &message,
+            &tree,
+            &parents,
+        )
+        .context("Failed to create commit")?
+    };
+
+    if matches!(
+        repo.state(),
+        RepositoryState::Merge | RepositoryState::CherryPick | RepositoryState::Revert
+    ) {
+        repo.cleanup_state()
+            .context("Failed to clean up the merge state")?;
+    }
+
+    Ok(CommitResult {
+        sha: oid.to_string(),
+        summary: message.lines().next().unwrap_or("").to_string(),
+    })
+}
+
+/// Build a commit message draft from the staged changes.
+///
+/// A single file gives "Add/Update/Delete/Rename <path>"; several files
+/// give "Update N files" with the file list in the body.
+pub fn draft_commit_message(worktree_path: &str) -> Result<String> {
+    let repo = open_repo(worktree_path)?;
+    let head_tree = head_commit(&repo)?.map(|c| c.tree()).transpose()?;
+    let mut diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), None, None)
+        .context("Failed to compute staged diff")?;
+    diff.find_similar(None)?;
+    let files = collect_diff(&repo, &diff)?;
+
+    match files.as_slice() {
+        [] => anyhow::bail!("Nothing staged to describe"),
+        [file] => Ok(format!("{}\n", describe_file(file))),
+        _ => {
+            let mut message = format!("Update {} files\n\n", files.len());
+            for file in &files {
+                message.push_str(&format!("- {}\n", describe_file(file)));
+            }
+            Ok(message)
+        }
+    }
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn head_commit(repo: &Repository) -> Result<Option<git2::Commit<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_commit()?)),
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => Ok(None),
+        Err(e) => Err(e).context("Failed to resolve HEAD"),
+    }
+}
+
+/// Commits listed in `MERGE_HEAD`, empty when no merge is in progress.
+fn merge_head_ids(repo: &mut Repository) -> Result<Vec<git2::Oid>> {
+    if repo.state() != RepositoryState::Merge {
+        return Ok(Vec::new());
+    }
+    let mut oids = Vec::new();
+    repo.mergehead_foreach(|oid| {
+        oids.push(*oid);
+        true
+    })
+    .context("Failed to read MERGE_HEAD")?;
+    Ok(oids)
+}
+
+fn describe_file(file: &FileDiff) -> String {
+    let path = file
+        .new_path
+        .as_deref()
+        .or(file.old_path.as_deref())
+        .unwrap_or("");
+    match file.status {
+        FileStatus::Added => format!("Add {path}"),
+        FileStatus::Deleted => format!("Delete {path}"),
+        FileStatus::Renamed => format!(
+            "Rename {} to {path}",
+            file.old_path.as_deref().unwrap_or("")
+        ),
+        _ => format!("Update {path}"),
+    }
+}
+
+/// Append `Signed-off-by:` unless the message already ends with the same
+/// trailer. Joins an existing trailer block instead of starting a new one.
+fn append_sign_off(message: &str, signature: &Signature<'_>) -> String {
+    let trailer = format!(
+        "Signed-off-by: {} <{}>",
+        signature.name().unwrap_or(""),
+        signature.email().unwrap_or("")
+    );
+    let body = message.trim_end();
+    if body.lines().last() == Some(trailer.as_str()) {
+        return format!("{body}\n");
+    }
+
+    let last_paragraph = body.rsplit("\n\n").next().unwrap_or("");
+    let in_trailer_block = body.contains("\n\n")
+        && last_paragraph.lines().all(|line| {
+            line.split_once(": ")
+                .is_some_and(|(key, _)| !key.contains(' '))
+        });
+    let separator = if in_trailer_block { "\n" } else { "\n\n" };
+    format!("{body}{separator}{trailer}\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
+    use crate::git::worktree::add_worktree;
+    use std::fs;
+    use std::path::Path;
+
+    fn stage(repo: &Repository, path: &str) {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+    }
+
+    fn request(message: &str) -> CommitRequest {
+        CommitRequest {
+            message: message.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_create_commit() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+
+        let result =
+            create_commit(dir.path().to_str().unwrap(), &request("Change greeting")).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.sha, head.id().to_string());
+        assert_eq!(result.summary, "Change greeting");
+        assert_eq!(head.message(), Some("Change greeting\n"));
+        assert_eq!(head.author().name(), Some("Test"));
+        assert_eq!(head.committer().email(), Some("test@test.com"));
+        assert_eq!(head.parent_count(), 1);
+    }
+
+    #[test]
+    fn test_create_commit_keeps_hash_lines() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+
+        let result = create_commit(
+            dir.path().to_str().unwrap(),
+            &request("#123 fix login\n\n# Notes\nSession expired early.  \n"),
+        )
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.summary, "#123 fix login");
+        assert_eq!(
+            head.message(),
+            Some("#123 fix login\n\n# Notes\nSession expired early.\n")
+        );
+    }
+
+    #[test]
+    fn test_create_commit_nothing_staged() {
+        let (dir, _repo) = init_repo_with_commit();
+        let result = create_commit(dir.path().to_str().unwrap(), &request("Empty"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Nothing to commit"));
+    }
+
+    #[test]
+    fn test_create_commit_empty_message() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+        let result = create_commit(dir.path().to_str().unwrap(), &request("  \n\t\n"));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_commit_amend() {
+        let (dir, repo) = init_repo_with_commit();
+        let original = repo.head().unwrap().peel_to_commit().unwrap();
+        fs::write(dir.path().join("hello.txt"), "amended\n").unwrap();
+        stage(&repo, "hello.txt");
+
+        let result = create_commit(
+            dir.path().to_str().unwrap(),
+            &CommitRequest {
+                message: "Amended init".to_string(),
+                amend: true,
+                sign_off: false,
+            },
+        )
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.sha, head.id().to_string());
+        assert_ne!(head.id(), original.id());
+        assert_eq!(head.parent_count(), original.parent_count());
+        assert_eq!(head.message(), Some("Amended init\n"));
+        let blob = head
+            .tree()
+            .unwrap()
+            .get_path(Path::new("hello.txt"))
+            .unwrap()
+            .to_object(&repo)
+            .unwrap()
+            .peel_to_blob()
+            .unwrap();
+        assert_eq!(blob.content(), b"amended\n");
+    }
+
+    #[test]
+    fn test_create_commit_sign_off() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+
+        create_commit(
+            dir.path().to_str().unwrap(),
+            &CommitRequest {
+                message: "Change greeting".to_string(),
+                amend: false,
+                sign_off: true,
+            },
+        )
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(
+            head.message(),
+            Some("Change greeting\n\nSigned-off-by: Test <test@test.com>\n")
+        );
+    }
+
+    #[test]
+    fn test_create_commit_concludes_merge() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &base, false).unwrap();
+        let feature = commit_file(&repo, "feature", "other.txt", "feature\n", "Add other.txt");
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+        create_commit(path, &request("Change greeting")).unwrap();
+        let main = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        let annotated = repo.find_annotated_commit(feature).unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+        assert_eq!(repo.state(), RepositoryState::Merge);
+        let amend = CommitRequest {
+            message: "Merge feature".to_string(),
+            amend: true,
+            sign_off: false,
+        };
+        assert!(create_commit(path, &amend).is_err());
+
+        create_commit(path, &request("Merge feature")).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![main, feature]);
+        assert!(head.tree().unwrap().get_name("other.txt").is_some());
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert!(!repo.path().join("MERGE_HEAD").exists());
+    }
+
+    #[test]
+    fn test_create_commit_concludes_cherry_pick() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &base, false).unwrap();
+        let feature = commit_file(&repo, "feature", "other.txt", "feature\n", "Add other.txt");
+        let main = repo.head().unwrap().peel_to_commit().unwrap().id();
+
+        repo.cherrypick(&repo.find_commit(feature).unwrap(), None)
+            .unwrap();
+        assert_eq!(repo.state(), RepositoryState::CherryPick);
+
+        create_commit(path, &request("Add other.txt")).unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![main]);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert!(!repo.path().join("CHERRY_PICK_HEAD").exists());
+    }
+
+    #[test]
+    fn test_append_sign_off_joins_trailer_block() {
+        let sig = Signature::now("Test", "test@test.com").unwrap();
+        assert_eq!(
+            append_sign_off("Fix bug\n\nRefs: #12\n", &sig),
+            "Fix bug\n\nRefs: #12\nSigned-off-by: Test <test@test.com>\n"
+        );
+        // Already signed off
+        assert_eq!(
+            append_sign_off("Fix bug\n\nSigned-off-by: Test <test@test.com>\n", &sig),
+            "Fix bug\n\nSigned-off-by: Test <test@test.com>\n"
+        );
+        // A plain body paragraph is not a trailer block
+        assert_eq!(
+            append_sign_off("Fix bug\n\nThe cache was stale.\n", &sig),
+            "Fix bug\n\nThe cache was stale.\n\nSigned-off-by: Test <test@test.com>\n"
+        );
+    }
+
+    #[test]
+    fn test_create_commit_in_linked_worktree() {
+        let (dir, repo) = init_repo_with_commit();
+        let wt_dir = tempfile::TempDir::new().unwrap();
+        let wt_path = wt_dir.path().join("feature");
+        add_worktree(
+            dir.path().to_str().unwrap(),
+            wt_path.to_str().unwrap(),
+            "feature",
+        )
+        .unwrap();
+
+        fs::write(wt_path.join("feature.txt"), "feature\n").unwrap();
+        let wt_repo = Repository::open(&wt_path).unwrap();
+        stage(&wt_repo, "feature.txt");
+
+        let result = create_commit(wt_path.to_str().unwrap(), &request("Add feature")).unwrap();
+
+        let branch_tip = repo
+            .find_branch("feature", git2::BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        assert_eq!(branch_tip.id().to_string(), result.sha);
+        // The main worktree's branch is untouched
+        let main_head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(main_head.message(), Some("init"));
+    }
+
+    #[test]
+    fn test_draft_commit_message() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        assert!(draft_commit_message(path).is_err());
+
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stage(&repo, "hello.txt");
+        assert_eq!(draft_commit_message(path).unwrap(), "Update hello.txt\n");
+
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+        stage(&repo, "new.txt");
+        assert_eq!(
+            draft_commit_message(path).unwrap(),
+            "Update 2 files\n\n- Update hello.txt\n- Add new.txt\n"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result};
 use git2::Delta;
+use std::collections::HashMap;
 
 use super::open_repo;
+use super::word_diff::highlight_chunk;
 
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub enum LineOrigin {
@@ -28,6 +30,17 @@ pub struct DiffLineInfo {
     pub old_lineno: Option<u32>,
     pub new_lineno: Option<u32>,
     pub content: String,
+    /// Parts of `content` that changed relative to the paired line on the
+    /// other side. Empty for context lines and unpaired lines.
+    pub highlights: Vec<HighlightRange>,
+}
+
+/// Half-open range of changed characters within a line (char offsets,
+/// not bytes).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -41,32 +54,190 @@ pub struct FileDiff {
     pub old_path: Option<String>,
     pub new_path: Option<String>,
     pub status: FileStatus,
+    /// Similarity percentage (0–100) for renamed or copied files.
+    pub similarity: Option<u16>,
+    pub kind: FileKind,
+    /// Blob on the old side. `None` for added files and submodules.
+    pub old_blob: Option<BlobInfo>,
+    /// Blob on the new side. `None` for deleted files and submodules.
+    pub new_blob: Option<BlobInfo>,
+    /// Commit pointers when `kind` is `Submodule`.
+    pub submodule: Option<SubmoduleChange>,
     pub chunks: Vec<DiffChunk>,
 }
 
+/// What kind of content a file entry holds. Only `Text` files carry chunks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub enum FileKind {
+    #[default]
+    Text,
+    Binary,
+    /// Git LFS pointer file; the real content lives in LFS storage.
+    LfsPointer,
+    /// Gitlink entry (mode 160000) pointing at a submodule commit.
+    Submodule,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct BlobInfo {
+    pub oid: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SubmoduleChange {
+    /// `None` when the submodule was added.
+    pub old_commit: Option<String>,
+    /// `None` when the submodule was removed.
+    pub new_commit: Option<String>,
+}
+
+/// First line of every Git LFS pointer file.
+const LFS_POINTER_PREFIX: &str = "version https://git-lfs.github.com/spec/";
+
+/// Git LFS pointer files are always smaller than this many bytes.
+const LFS_POINTER_MAX_SIZE: u64 = 1024;
+
+/// Whether the first line of a file is an LFS pointer header.
+fn is_lfs_pointer(first_line: &str) -> bool {
+    first_line.starts_with(LFS_POINTER_PREFIX)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub enum FileStatus {
     Added,
     Deleted,
     Modified,
     Renamed,
+    Copied,
     Other,
 }
 
 impl From<Delta> for FileStatus {
     fn from(d: Delta) -> Self {
         match d {
-            Delta::Added => FileStatus::Added,
+            // An untracked file is new content just like an added one;
+            // `diff_workdir_grouped` keeps them apart
+            Delta::Added | Delta::Untracked => FileStatus::Added,
             Delta::Deleted => FileStatus::Deleted,
             Delta::Modified => FileStatus::Modified,
             Delta::Renamed => FileStatus::Renamed,
+            Delta::Copied => FileStatus::Copied,
             _ => FileStatus::Other,
         }
     }
 }
 
+/// Rename / copy detection settings passed to git2's similarity search.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RenameDetection {
+    /// Also detect copies (the source file is kept unchanged).
+    #[serde(default)]
+    pub copies: bool,
+    /// Minimum similarity percentage (0–100) to pair two files.
+    #[serde(default = "default_similarity_threshold")]
+    pub threshold: u16,
+}
+
+fn default_similarity_threshold() -> u16 {
+    // Same default as `git diff -M`
+    50
+}
+
+impl Default for RenameDetection {
+    fn default() -> Self {
+        Self {
+            copies: false,
+            threshold: default_similarity_threshold(),
+        }
+    }
+}
+
+/// How whitespace differences are treated when computing a diff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum WhitespaceMode {
+    /// Whitespace changes are reported like any other change.
+    #[default]
+    Show,
+    /// Ignore all whitespace (`git diff -w`).
+    IgnoreAll,
+    /// Ignore changes in the amount of whitespace (`git diff -b`).
+    IgnoreChange,
+    /// Ignore whitespace at end of line (`git diff --ignore-space-at-eol`).
+    IgnoreEol,
+}
+
+/// Options shared by every diff entry point.
+///
+/// `DiffRequest::default()` reproduces plain `git diff` output: 3 context
+/// lines, whitespace shown, no path filter, no untracked files and no
+/// rename detection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DiffRequest {
+    /// Number of unchanged lines around each hunk (git default: 3).
+    pub context_lines: Option<u32>,
+    pub whitespace: WhitespaceMode,
+    /// Limit the diff to these paths / glob patterns (e.g. `"frontend/"`).
+    pub pathspecs: Vec<String>,
+    /// Include untracked files. Only affects `diff_workdir`.
+    pub include_untracked: bool,
+    /// Files larger than this many bytes are treated as binary (no hunks).
+    pub max_file_size: Option<u64>,
+    pub renames: Option<RenameDetection>,
+}
+
+impl DiffRequest {
+    fn to_git2_options(&self) -> git2::DiffOptions {
+        let mut opts = git2::DiffOptions::new();
+
+        if let Some(lines) = self.context_lines {
+            opts.context_lines(lines);
+        }
+
+        match self.whitespace {
+            WhitespaceMode::Show => {}
+            WhitespaceMode::IgnoreAll => {
+                opts.ignore_whitespace(true);
+            }
+            WhitespaceMode::IgnoreChange => {
+                opts.ignore_whitespace_change(true);
+            }
+            WhitespaceMode::IgnoreEol => {
+                opts.ignore_whitespace_eol(true);
+            }
+        }
+
+        for spec in &self.pathspecs {
+            opts.pathspec(spec.as_str());
+        }
+
+        if self.include_untracked {
+            opts.include_untracked(true)
+                .recurse_untracked_dirs(true)
+                .show_untracked_content(true);
+        }
+
+        if let Some(size) = self.max_file_size {
+            opts.max_size(i64::try_from(size).unwrap_or(i64::MAX));
+        }
+
+        // Copy detection needs the unmodified files as candidate sources.
+        // They are dropped again in `find_similar`.
+        if self.renames.as_ref().is_some_and(|r| r.copies) {
+            opts.include_unmodified(true);
+        }
+
+        opts
+    }
+}
+
 /// Return the diff of the working directory against HEAD.
-pub fn diff_workdir(repo_path: &str) -> Result<Vec<FileDiff>> {
+///
+/// When `request.renames` is set, moved and (optionally) copied files are
+/// paired up instead of being reported as a delete plus an add.
+pub fn diff_workdir(repo_path: &str, request: &DiffRequest) -> Result<Vec<FileDiff>> {
     let repo = open_repo(repo_path)?;
 
     let head_tree = match repo.head() {
@@ -74,16 +245,83 @@ pub fn diff_workdir(repo_path: &str) -> Result<Vec<FileDiff>> {
         Err(_) => None, // unborn repo
     };
 
-    let diff = repo
-        .diff_tree_to_workdir_with_index(head_tree.as_ref(), None)
+    let mut diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut request.to_git2_options()))
         .context("Failed to compute workdir diff")?;
+    find_similar(&mut diff, request.renames.as_ref())?;
+
+    collect_diff(&repo, &diff)
+}
+
+/// Working tree changes split the way `git status` shows them.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorkdirChanges {
+    /// Index vs HEAD.
+    pub staged: Vec<FileDiff>,
+    /// Working directory vs index, tracked files only.
+    pub unstaged: Vec<FileDiff>,
+    /// Files not in the index and not ignored.
+    pub untracked: Vec<FileDiff>,
+}
+
+/// Return staged, unstaged and untracked changes as separate diffs.
+///
+/// Unlike `diff_workdir`, untracked files are always collected (into their
+/// own group); `request.include_untracked` is ignored.
+pub fn diff_workdir_grouped(repo_path: &str, request: &DiffRequest) -> Result<WorkdirChanges> {
+    let repo = open_repo(repo_path)?;
+
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(_) => None, // unborn repo
+    };
+    let index = repo.index().context("Failed to read index")?;
+
+    let tracked_request = DiffRequest {
+        include_untracked: false,
+        ..request.clone()
+    };
+    let mut staged = repo
+        .diff_tree_to_index(
+            head_tree.as_ref(),
+            Some(&index),
+            Some(&mut tracked_request.to_git2_options()),
+        )
+        .context("Failed to compute staged diff")?;
+    find_similar(&mut staged, request.renames.as_ref())?;
+
+    let workdir_request = DiffRequest {
+        include_untracked: true,
+        ..request.clone()
+    };
+    let mut workdir = repo
+        .diff_index_to_workdir(Some(&index), Some(&mut workdir_request.to_git2_options()))
+        .context("Failed to compute unstaged diff")?;
+    find_similar(&mut workdir, request.renames.as_ref())?;
 
-    collect_diff(&diff)
+    let untracked_flags: Vec<bool> = workdir
+        .deltas()
+        .map(|d| d.status() == Delta::Untracked)
+        .collect();
+    let (untracked, unstaged): (Vec<_>, Vec<_>) = collect_diff(&repo, &workdir)?
+        .into_iter()
+        .zip(untracked_flags)
+        .partition(|(_, is_untracked)| *is_untracked);
+
+    Ok(WorkdirChanges {
+        staged: collect_diff(&repo, &staged)?,
+        unstaged: unstaged.into_iter().map(|(file, _)| file).collect(),
+        untracked: untracked.into_iter().map(|(file, _)| file).collect(),
+    })
 }
 
 /// Return the diff introduced by `commit_sha` relative to its first parent.
 #[allow(dead_code)] // used in Phase 2 for PR diff display
-pub fn diff_commit(repo_path: &str, commit_sha: &str) -> Result<Vec<FileDiff>> {
+pub fn diff_commit(
+    repo_path: &str,
+    commit_sha: &str,
+    request: &DiffRequest,
+) -> Result<Vec<FileDiff>> {
     let repo = open_repo(repo_path)?;
 
     let oid = repo
@@ -96,17 +334,27 @@ pub fn diff_commit(repo_path: &str, commit_sha: &str) -> Result<Vec<FileDiff>> {
 
     let parent_tree = commit.parent(0).ok().map(|p| p.tree()).transpose()?;
 
-    let diff = repo
-        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), None)
+    let mut diff = repo
+        .diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit_tree),
+            Some(&mut request.to_git2_options()),
+        )
         .context("Failed to compute commit diff")?;
+    find_similar(&mut diff, request.renames.as_ref())?;
 
-    collect_diff(&diff)
+    collect_diff(&repo, &diff)
 }
 
 /// Return the diff between two refs (branches, tags, or commit SHAs).
 ///
 /// Compares `base_ref` → `head_ref`, e.g. "main" → "feature/auth".
-pub fn diff_branches(repo_path: &str, base_ref: &str, head_ref: &str) -> Result<Vec<FileDiff>> {
+pub fn diff_branches(
+    repo_path: &str,
+    base_ref: &str,
+    head_ref: &str,
+    request: &DiffRequest,
+) -> Result<Vec<FileDiff>> {
     let repo = open_repo(repo_path)?;
 
     let base_tree = repo
@@ -121,17 +369,172 @@ pub fn diff_branches(repo_path: &str, base_ref: &str, head_ref: &str) -> Result<
         .peel_to_tree()
         .with_context(|| format!("Failed to peel '{head_ref}' to tree"))?;
 
-    let diff = repo
-        .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), None)
+    let mut diff = repo
+        .diff_tree_to_tree(
+            Some(&base_tree),
+            Some(&head_tree),
+            Some(&mut request.to_git2_options()),
+        )
+        .context("Failed to compute branch diff")?;
+    find_similar(&mut diff, request.renames.as_ref())?;
+
+    collect_diff(&repo, &diff)
+}
+
+/// Which commit the head is compared against in `compare_branches`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum CompareMode {
+    /// Two-dot: diff the base tip directly against the head tip.
+    #[default]
+    Direct,
+    /// Three-dot: diff `merge_base(base, head)` against the head tip,
+    /// the same way GitHub shows a PR diff.
+    MergeBase,
+}
+
+/// Result of `compare_branches`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BranchComparison {
+    /// SHA of the merge base of base and head (None for unrelated histories).
+    pub merge_base: Option<String>,
+    /// Number of commits on head that are not on base.
+    pub ahead: usize,
+    /// Number of commits on base that are not on head.
+    pub behind: usize,
+    pub files: Vec<FileDiff>,
+}
+
+/// Compare `base_ref` → `head_ref` and report the merge base and the
+/// ahead/behind commit counts along with the diff.
+///
+/// With `CompareMode::MergeBase`, changes that landed on the base after the
+/// head was branched off are not shown as reversed changes.
+pub fn compare_branches(
+    repo_path: &str,
+    base_ref: &str,
+    head_ref: &str,
+    mode: CompareMode,
+    request: &DiffRequest,
+) -> Result<BranchComparison> {
+    let repo = open_repo(repo_path)?;
+
+    let base_commit = repo
+        .revparse_single(base_ref)
+        .with_context(|| format!("Ref '{base_ref}' not found"))?
+        .peel_to_commit()
+        .with_context(|| format!("Failed to peel '{base_ref}' to commit"))?;
+
+    let head_commit = repo
+        .revparse_single(head_ref)
+        .with_context(|| format!("Ref '{head_ref}' not found"))?
+        .peel_to_commit()
+        .with_context(|| format!("Failed to peel '{head_ref}' to commit"))?;
+
+    let merge_base = repo.merge_base(base_commit.id(), head_commit.id()).ok();
+
+    let (ahead, behind) = repo
+        .graph_ahead_behind(head_commit.id(), base_commit.id())
+        .with_context(|| {
+            format!("Failed to count commits between '{base_ref}' and '{head_ref}'")
+        })?;
+
+    let base_tree = match mode {
+        CompareMode::Direct => base_commit.tree()?,
+        CompareMode::MergeBase => {
+            let oid = merge_base.with_context(|| {
+                format!("No common ancestor between '{base_ref}' and '{head_ref}'")
+            })?;
+            repo.find_commit(oid)?.tree()?
+        }
+    };
+    let head_tree = head_commit.tree()?;
+
+    let mut diff = repo
+        .diff_tree_to_tree(
+            Some(&base_tree),
+            Some(&head_tree),
+            Some(&mut request.to_git2_options()),
+        )
         .context("Failed to compute branch diff")?;
+    find_similar(&mut diff, request.renames.as_ref())?;
 
-    collect_diff(&diff)
+    Ok(BranchComparison {
+        merge_base: merge_base.map(|oid| oid.to_string()),
+        ahead,
+        behind,
+        files: collect_diff(&repo, &diff)?,
+    })
 }
 
 // ── internals ────────────────────────────────────────────────────────────────
 
-fn collect_diff(diff: &git2::Diff<'_>) -> Result<Vec<FileDiff>> {
+fn find_similar(diff: &mut git2::Diff<'_>, renames: Option<&RenameDetection>) -> Result<()> {
+    let Some(renames) = renames else {
+        return Ok(());
+    };
+
+    let mut opts = git2::DiffFindOptions::new();
+    opts.renames(true)
+        .rename_threshold(renames.threshold)
+        .remove_unmodified(true);
+    if renames.copies {
+        opts.copies(true)
+            .copies_from_unmodified(true)
+            .copy_threshold(renames.threshold);
+    }
+
+    diff.find_similar(Some(&mut opts))
+        .context("Failed to detect renamed files")
+}
+
+/// Similarity scores of the renamed and copied files in `diff`, keyed by
+/// `(old_path, new_path)`.
+///
+/// git2 does not expose `git_diff_delta.similarity`, so the scores are read
+/// back from the patch headers. Only the headers are formatted, not the
+/// file contents.
+fn similarity_scores(diff: &git2::Diff<'_>) -> Result<HashMap<(String, String), u16>> {
+    let mut scores = HashMap::new();
+    if !diff
+        .deltas()
+        .any(|d| matches!(d.status(), Delta::Renamed | Delta::Copied))
+    {
+        return Ok(scores);
+    }
+    diff.print(git2::DiffFormat::PatchHeader, |delta, _, line| {
+        let score = std::str::from_utf8(line.content())
+            .ok()
+            .and_then(parse_similarity);
+        if let (Some(score), Some(old), Some(new)) =
+            (score, delta.old_file().path(), delta.new_file().path())
+        {
+            let key = (
+                old.to_string_lossy().into_owned(),
+                new.to_string_lossy().into_owned(),
+            );
+            scores.insert(key, score);
+        }
+        true
+    })
+    .context("Failed to read rename similarity")?;
+    Ok(scores)
+}
+
+/// Extract the `similarity index NN%` value from a patch header.
+fn parse_similarity(patch_text: &str) -> Option<u16> {
+    patch_text
+        .lines()
+        .take_while(|l| !l.starts_with("@@"))
+        .find_map(|l| l.strip_prefix("similarity index "))
+        .and_then(|rest| rest.trim_end_matches('%').parse().ok())
+}
+
+pub(crate) fn collect_diff(
+    repo: &git2::Repository,
+    diff: &git2::Diff<'_>,
+) -> Result<Vec<FileDiff>> {
     let mut files = Vec::new();
+    let mut similarities = similarity_scores(diff)?;
 
     for (idx, delta) in diff.deltas().enumerate() {
         let mut file_diff = FileDiff {
@@ -144,19 +547,53 @@ fn collect_diff(diff: &git2::Diff<'_>) -> Result<Vec<FileDiff>> {
                 .path()
                 .map(|p| p.to_string_lossy().into_owned()),
             status: delta.status().into(),
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: Vec::new(),
         };
 
+        if is_submodule(&delta) {
+            file_diff.kind = FileKind::Submodule;
+            file_diff.submodule = Some(SubmoduleChange {
+                old_commit: non_zero_oid(delta.old_file().id()),
+                new_commit: non_zero_oid(delta.new_file().id()),
+            });
+            files.push(file_diff);
+            continue;
+        }
+
         let patch =
             git2::Patch::from_diff(diff, idx).context("Failed to create patch from diff")?;
         let patch = match patch {
             Some(p) => p,
             None => {
+                file_diff.old_blob = blob_info(&delta.old_file());
+                file_diff.new_blob = blob_info(&delta.new_file());
                 files.push(file_diff);
                 continue;
             }
         };
 
+        // Sizes and the binary flag are only reliable once the patch has
+        // loaded the content, so read them from the patch's own delta.
+        let patch_delta = patch.delta();
+        file_diff.old_blob = blob_info(&patch_delta.old_file());
+        file_diff.new_blob = blob_info(&patch_delta.new_file());
+        if patch_delta.flags().is_binary() {
+            file_diff.kind = FileKind::Binary;
+        } else if is_lfs_pointer_file(repo, &patch_delta.old_file())
+            || is_lfs_pointer_file(repo, &patch_delta.new_file())
+        {
+            file_diff.kind = FileKind::LfsPointer;
+        }
+
+        if let (Some(old), Some(new)) = (&file_diff.old_path, &file_diff.new_path) {
+            file_diff.similarity = similarities.remove(&(old.clone(), new.clone()));
+        }
+
         for hunk_idx in 0..patch.num_hunks() {
             let (hunk, line_count) = patch.hunk(hunk_idx)?;
             let header = std::str::from_utf8(hunk.header())
@@ -179,9 +616,11 @@ fn collect_diff(diff: &git2::Diff<'_>) -> Result<Vec<FileDiff>> {
                     old_lineno: line.old_lineno(),
                     new_lineno: line.new_lineno(),
                     content,
+                    highlights: Vec::new(),
                 });
             }
 
+            highlight_chunk(&mut chunk);
             file_diff.chunks.push(chunk);
         }
 
@@ -191,17 +630,66 @@ fn collect_diff(diff: &git2::Diff<'_>) -> Result<Vec<FileDiff>> {
     Ok(files)
 }
 
+fn is_submodule(delta: &git2::DiffDelta<'_>) -> bool {
+    delta.old_file().mode() == git2::FileMode::Commit
+        || delta.new_file().mode() == git2::FileMode::Commit
+}
+
+fn non_zero_oid(oid: git2::Oid) -> Option<String> {
+    (!oid.is_zero()).then(|| oid.to_string())
+}
+
+fn blob_info(file: &git2::DiffFile<'_>) -> Option<BlobInfo> {
+    if !file.exists() {
+        return None;
+    }
+    Some(BlobInfo {
+        oid: file.id().to_string(),
+        size: file.size(),
+    })
+}
+
+/// Whether `file` is an LFS pointer, judged from its full content rather than
+/// from the hunks, which may not include the header line.
+///
+/// Pointer files are tiny, so larger blobs are not read. Working tree files
+/// that are not in the object database yet are read from disk.
+fn is_lfs_pointer_file(repo: &git2::Repository, file: &git2::DiffFile<'_>) -> bool {
+    if !file.exists() || file.size() > LFS_POINTER_MAX_SIZE {
+        return false;
+    }
+    if let Ok(blob) = repo.find_blob(file.id()) {
+        return blob.content().starts_with(LFS_POINTER_PREFIX.as_bytes());
+    }
+    let (Some(workdir), Some(path)) = (repo.workdir(), file.path()) else {
+        return false;
+    };
+    std::fs::read(workdir.join(path))
+        .is_ok_and(|content| content.starts_with(LFS_POINTER_PREFIX.as_bytes()))
+}
+
+/// An LFS pointer replaces the whole file, so its header is always the first
+/// line of whichever side exists. Used where only the patch text is known,
+/// e.g. the files of a GitHub PR.
+pub(crate) fn chunks_contain_lfs_pointer(chunks: &[DiffChunk]) -> bool {
+    chunks
+        .iter()
+        .flat_map(|chunk| &chunk.lines)
+        .filter(|line| line.old_lineno == Some(1) || line.new_lineno == Some(1))
+        .any(|line| is_lfs_pointer(&line.content))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::test_utils::init_repo_with_commit;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
     use git2::Signature;
     use std::fs;
 
     #[test]
     fn test_diff_workdir_clean() {
         let (dir, _) = init_repo_with_commit();
-        let diffs = diff_workdir(dir.path().to_str().unwrap()).unwrap();
+        let diffs = diff_workdir(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap();
         assert!(diffs.is_empty(), "clean workdir should produce no diff");
     }
 
@@ -209,7 +697,7 @@ mod tests {
     fn test_diff_workdir_modified_file() {
         let (dir, _) = init_repo_with_commit();
         fs::write(dir.path().join("hello.txt"), "hello\nworld\n").unwrap();
-        let diffs = diff_workdir(dir.path().to_str().unwrap()).unwrap();
+        let diffs = diff_workdir(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap();
         assert_eq!(diffs.len(), 1);
         assert_eq!(diffs[0].new_path.as_deref(), Some("hello.txt"));
         assert_eq!(diffs[0].status, FileStatus::Modified);
@@ -233,7 +721,12 @@ mod tests {
                 .unwrap()
         };
 
-        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string()).unwrap();
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
         assert_eq!(diffs.len(), 1);
         assert_eq!(diffs[0].status, FileStatus::Modified);
         // Should have at least one chunk with an added line.
@@ -280,7 +773,13 @@ mod tests {
         )
         .unwrap();
 
-        let diffs = diff_branches(dir.path().to_str().unwrap(), "main", "feature").unwrap();
+        let diffs = diff_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "feature",
+            &DiffRequest::default(),
+        )
+        .unwrap();
         assert_eq!(diffs.len(), 1);
         assert_eq!(diffs[0].new_path.as_deref(), Some("feature.txt"));
         assert_eq!(diffs[0].status, FileStatus::Added);
@@ -290,7 +789,615 @@ mod tests {
     fn test_diff_branches_no_changes() {
         let (dir, _) = init_repo_with_commit();
         // Comparing a branch to itself should produce no diff
-        let diffs = diff_branches(dir.path().to_str().unwrap(), "main", "main").unwrap();
+        let diffs = diff_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "main",
+            &DiffRequest::default(),
+        )
+        .unwrap();
         assert!(diffs.is_empty());
     }
+
+    /// Stage `path` as it is in the working tree, including its removal, so
+    /// the next `commit_file` on HEAD picks it up.
+    fn stage_path(repo: &git2::Repository, path: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all([path].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all([path].iter(), None).unwrap();
+        index.write().unwrap();
+    }
+
+    const LONG_CONTENT: &str = "line 1\nline 2\nline 3\nline 4\nline 5\nline 6\nline 7\nline 8\n";
+
+    #[test]
+    fn test_diff_commit_without_rename_detection_reports_delete_and_add() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        fs::remove_file(dir.path().join("a.txt")).unwrap();
+        stage_path(&repo, "a.txt");
+        let oid = commit_file(&repo, "HEAD", "b.txt", LONG_CONTENT, "move a");
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs.iter().all(|d| d.similarity.is_none()));
+    }
+
+    #[test]
+    fn test_diff_commit_detects_exact_rename() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        fs::remove_file(dir.path().join("a.txt")).unwrap();
+        stage_path(&repo, "a.txt");
+        let oid = commit_file(&repo, "HEAD", "b.txt", LONG_CONTENT, "move a");
+
+        let request = DiffRequest {
+            renames: Some(RenameDetection::default()),
+            ..Default::default()
+        };
+        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, FileStatus::Renamed);
+        assert_eq!(diffs[0].old_path.as_deref(), Some("a.txt"));
+        assert_eq!(diffs[0].new_path.as_deref(), Some("b.txt"));
+        assert_eq!(diffs[0].similarity, Some(100));
+        assert!(diffs[0].chunks.is_empty());
+    }
+
+    #[test]
+    fn test_diff_commit_detects_rename_with_edits() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        fs::remove_file(dir.path().join("a.txt")).unwrap();
+        stage_path(&repo, "a.txt");
+        let oid = commit_file(
+            &repo,
+            "HEAD",
+            "b.txt",
+            LONG_CONTENT.replace("line 8", "line eight"),
+            "move and edit a",
+        );
+
+        let request = DiffRequest {
+            renames: Some(RenameDetection::default()),
+            ..Default::default()
+        };
+        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, FileStatus::Renamed);
+        let similarity = diffs[0].similarity.unwrap();
+        assert!((50..100).contains(&similarity), "got {similarity}");
+        assert!(!diffs[0].chunks.is_empty());
+    }
+
+    #[test]
+    fn test_diff_commit_rename_threshold_not_met() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        fs::remove_file(dir.path().join("a.txt")).unwrap();
+        stage_path(&repo, "a.txt");
+        let oid = commit_file(
+            &repo,
+            "HEAD",
+            "b.txt",
+            LONG_CONTENT.replace("line 8", "line eight"),
+            "move and edit a",
+        );
+
+        let request = DiffRequest {
+            renames: Some(RenameDetection {
+                copies: false,
+                threshold: 100,
+            }),
+            ..Default::default()
+        };
+        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string(), &request).unwrap();
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs.iter().all(|d| d.status != FileStatus::Renamed));
+    }
+
+    #[test]
+    fn test_diff_commit_detects_copy() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        let oid = commit_file(&repo, "HEAD", "copy.txt", LONG_CONTENT, "copy a");
+
+        let request = DiffRequest {
+            renames: Some(RenameDetection {
+                copies: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, FileStatus::Copied);
+        assert_eq!(diffs[0].old_path.as_deref(), Some("a.txt"));
+        assert_eq!(diffs[0].new_path.as_deref(), Some("copy.txt"));
+        assert_eq!(diffs[0].similarity, Some(100));
+    }
+
+    #[test]
+    fn test_diff_workdir_detects_staged_rename() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::rename(dir.path().join("hello.txt"), dir.path().join("moved.txt")).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .remove_path(std::path::Path::new("hello.txt"))
+            .unwrap();
+        index.add_path(std::path::Path::new("moved.txt")).unwrap();
+        index.write().unwrap();
+
+        let request = DiffRequest {
+            renames: Some(RenameDetection::default()),
+            ..Default::default()
+        };
+        let diffs = diff_workdir(dir.path().to_str().unwrap(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].status, FileStatus::Renamed);
+        assert_eq!(diffs[0].similarity, Some(100));
+    }
+
+    #[test]
+    fn test_parse_similarity() {
+        let header = "diff --git a/a.txt b/b.txt\nsimilarity index 87%\nrename from a.txt\nrename to b.txt\n@@ -1 +1 @@\n";
+        assert_eq!(parse_similarity(header), Some(87));
+        assert_eq!(parse_similarity("diff --git a/a b/a\n@@ -1 +1 @@\n"), None);
+    }
+
+    /// main: init → "main change", feature: init → "feature change"
+    fn init_diverged_branches() -> (tempfile::TempDir, git2::Repository) {
+        let (dir, repo) = init_repo_with_commit();
+        {
+            let head = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &head, false).unwrap();
+        }
+
+        commit_file(&repo, "HEAD", "main.txt", "main change\n", "main change");
+
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(
+            &repo,
+            "HEAD",
+            "feature.txt",
+            "feature change\n",
+            "feature change",
+        );
+
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_compare_branches_direct_includes_upstream_changes() {
+        let (dir, _repo) = init_diverged_branches();
+        let result = compare_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "feature",
+            CompareMode::Direct,
+            &DiffRequest::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.files.len(), 2);
+        let main_file = result
+            .files
+            .iter()
+            .find(|f| f.old_path.as_deref() == Some("main.txt"))
+            .unwrap();
+        assert_eq!(main_file.status, FileStatus::Deleted);
+    }
+
+    #[test]
+    fn test_compare_branches_merge_base_excludes_upstream_changes() {
+        let (dir, repo) = init_diverged_branches();
+        let result = compare_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "feature",
+            CompareMode::MergeBase,
+            &DiffRequest::default(),
+        )
+        .unwrap();
+
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].new_path.as_deref(), Some("feature.txt"));
+        assert_eq!(result.files[0].status, FileStatus::Added);
+
+        let main = repo.revparse_single("main").unwrap().id();
+        let feature = repo.revparse_single("feature").unwrap().id();
+        let expected = repo.merge_base(main, feature).unwrap().to_string();
+        assert_eq!(result.merge_base.as_deref(), Some(expected.as_str()));
+        assert_eq!(result.ahead, 1);
+        assert_eq!(result.behind, 1);
+    }
+
+    #[test]
+    fn test_compare_branches_same_ref() {
+        let (dir, _) = init_repo_with_commit();
+        let result = compare_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "main",
+            CompareMode::MergeBase,
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert!(result.files.is_empty());
+        assert_eq!(result.ahead, 0);
+        assert_eq!(result.behind, 0);
+    }
+
+    #[test]
+    fn test_compare_branches_unknown_ref() {
+        let (dir, _) = init_repo_with_commit();
+        let result = compare_branches(
+            dir.path().to_str().unwrap(),
+            "main",
+            "no-such-branch",
+            CompareMode::MergeBase,
+            &DiffRequest::default(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_diff_commit_highlights_changed_word() {
+        let (dir, repo) = init_repo_with_commit();
+        let oid = commit_file(&repo, "HEAD", "hello.txt", "hello there\n", "edit hello");
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        let lines = &diffs[0].chunks[0].lines;
+        let added = lines
+            .iter()
+            .find(|l| l.origin == LineOrigin::Addition)
+            .unwrap();
+        assert_eq!(added.highlights, vec![HighlightRange { start: 5, end: 11 }]);
+    }
+
+    #[test]
+    fn test_diff_request_context_lines() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "a.txt", LONG_CONTENT, "add a");
+        fs::write(
+            dir.path().join("a.txt"),
+            LONG_CONTENT.replace("line 5", "line five"),
+        )
+        .unwrap();
+        let path = dir.path().to_str().unwrap();
+
+        let context_count = |diffs: &[FileDiff]| {
+            diffs[0].chunks[0]
+                .lines
+                .iter()
+                .filter(|l| l.origin == LineOrigin::Context)
+                .count()
+        };
+
+        let default = diff_workdir(path, &DiffRequest::default()).unwrap();
+        assert_eq!(context_count(&default), 6);
+
+        let request = DiffRequest {
+            context_lines: Some(0),
+            ..Default::default()
+        };
+        let narrow = diff_workdir(path, &request).unwrap();
+        assert_eq!(context_count(&narrow), 0);
+    }
+
+    #[test]
+    fn test_diff_request_ignore_whitespace() {
+        let (dir, _) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "hello   \n").unwrap();
+        let path = dir.path().to_str().unwrap();
+
+        let shown = diff_workdir(path, &DiffRequest::default()).unwrap();
+        assert!(shown[0].chunks.iter().any(|c| !c.lines.is_empty()));
+
+        for whitespace in [WhitespaceMode::IgnoreAll, WhitespaceMode::IgnoreEol] {
+            let request = DiffRequest {
+                whitespace,
+                ..Default::default()
+            };
+            let ignored = diff_workdir(path, &request).unwrap();
+            assert!(
+                ignored.iter().all(|d| d.chunks.is_empty()),
+                "{whitespace:?} should hide whitespace-only changes"
+            );
+        }
+    }
+
+    #[test]
+    fn test_diff_request_pathspecs() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::create_dir(dir.path().join("frontend")).unwrap();
+        fs::write(dir.path().join("frontend").join("app.ts"), "a\n").unwrap();
+        stage_path(&repo, "frontend/app.ts");
+        let oid = commit_file(&repo, "HEAD", "backend.rs", "b\n", "add files");
+
+        let request = DiffRequest {
+            pathspecs: vec!["frontend/".to_string()],
+            ..Default::default()
+        };
+        let diffs = diff_commit(dir.path().to_str().unwrap(), &oid.to_string(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].new_path.as_deref(), Some("frontend/app.ts"));
+    }
+
+    #[test]
+    fn test_diff_request_include_untracked() {
+        let (dir, _) = init_repo_with_commit();
+        fs::create_dir(dir.path().join("new_dir")).unwrap();
+        fs::write(dir.path().join("new_dir").join("new.txt"), "new\n").unwrap();
+        let path = dir.path().to_str().unwrap();
+
+        let default = diff_workdir(path, &DiffRequest::default()).unwrap();
+        assert!(default.is_empty());
+
+        let request = DiffRequest {
+            include_untracked: true,
+            ..Default::default()
+        };
+        let diffs = diff_workdir(path, &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].new_path.as_deref(), Some("new_dir/new.txt"));
+        assert_eq!(diffs[0].status, FileStatus::Added);
+        assert!(!diffs[0].chunks.is_empty());
+    }
+
+    #[test]
+    fn test_diff_request_max_file_size() {
+        let (dir, _) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), LONG_CONTENT).unwrap();
+        let request = DiffRequest {
+            max_file_size: Some(4),
+            ..Default::default()
+        };
+        let diffs = diff_workdir(dir.path().to_str().unwrap(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, FileKind::Binary);
+        assert!(diffs[0].chunks.is_empty());
+
+        // A file just under the limit is still diffed as text
+        let request = DiffRequest {
+            max_file_size: Some(LONG_CONTENT.len() as u64 + 1),
+            ..Default::default()
+        };
+        let diffs = diff_workdir(dir.path().to_str().unwrap(), &request).unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, FileKind::Text);
+        assert!(!diffs[0].chunks.is_empty());
+    }
+
+    #[test]
+    fn test_diff_request_deserializes_with_defaults() {
+        let request: DiffRequest =
+            serde_json::from_str(r#"{"whitespace": "IgnoreChange", "pathspecs": ["lib/"]}"#)
+                .unwrap();
+        assert_eq!(request.whitespace, WhitespaceMode::IgnoreChange);
+        assert_eq!(request.pathspecs, vec!["lib/".to_string()]);
+        assert!(request.context_lines.is_none());
+        assert!(!request.include_untracked);
+        assert!(request.renames.is_none());
+    }
+
+    #[test]
+    fn test_diff_commit_text_file_has_blob_info() {
+        let (dir, repo) = init_repo_with_commit();
+        let oid = commit_file(&repo, "HEAD", "hello.txt", "hello world\n", "edit");
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert_eq!(diffs[0].kind, FileKind::Text);
+        let new_blob = diffs[0].new_blob.as_ref().unwrap();
+        assert_eq!(new_blob.size, 12);
+        assert_eq!(
+            new_blob.oid,
+            repo.blob(b"hello world\n").unwrap().to_string()
+        );
+        assert!(diffs[0].old_blob.is_some());
+    }
+
+    #[test]
+    fn test_diff_commit_binary_file() {
+        let (dir, repo) = init_repo_with_commit();
+        let oid = commit_file(
+            &repo,
+            "HEAD",
+            "logo.png",
+            [0x89, b'P', b'N', b'G', 0, 0, 1, 2],
+            "add image",
+        );
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, FileKind::Binary);
+        assert!(diffs[0].chunks.is_empty());
+        assert!(diffs[0].old_blob.is_none());
+        assert_eq!(diffs[0].new_blob.as_ref().unwrap().size, 8);
+    }
+
+    #[test]
+    fn test_diff_commit_lfs_pointer() {
+        let (dir, repo) = init_repo_with_commit();
+        let oid = commit_file(
+            &repo,
+            "HEAD",
+            "video.mp4",
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 12345\n",
+            "add lfs pointer",
+        );
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert_eq!(diffs[0].kind, FileKind::LfsPointer);
+    }
+
+    #[test]
+    fn test_diff_lfs_pointer_outside_hunks() {
+        let (dir, repo) = init_repo_with_commit();
+        let pointer = |size: u32| {
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize {size}\n")
+        };
+        commit_file(&repo, "HEAD", "video.mp4", pointer(1), "add lfs pointer");
+        let oid = commit_file(&repo, "HEAD", "video.mp4", pointer(2), "update lfs pointer");
+        let path = dir.path().to_str().unwrap();
+
+        // Without context the hunk only holds the `size` line
+        let request = DiffRequest {
+            context_lines: Some(0),
+            ..Default::default()
+        };
+        let diffs = diff_commit(path, &oid.to_string(), &request).unwrap();
+        assert!(diffs[0].chunks[0]
+            .lines
+            .iter()
+            .all(|l| !l.content.starts_with("version")));
+        assert_eq!(diffs[0].kind, FileKind::LfsPointer);
+
+        // The new side only exists in the working tree
+        fs::write(dir.path().join("video.mp4"), pointer(3)).unwrap();
+        let diffs = diff_workdir(path, &request).unwrap();
+        assert_eq!(diffs[0].kind, FileKind::LfsPointer);
+    }
+
+    #[test]
+    fn test_diff_commit_submodule_bump() {
+        let (dir, repo) = init_repo_with_commit();
+        let sig = Signature::now("Test", "test@test.com").unwrap();
+        let old_sub = git2::Oid::from_str("1111111111111111111111111111111111111111").unwrap();
+        let new_sub = git2::Oid::from_str("2222222222222222222222222222222222222222").unwrap();
+
+        let commit_gitlink = |id: git2::Oid, message: &str| {
+            let mut index = repo.index().unwrap();
+            index
+                .add(&git2::IndexEntry {
+                    ctime: git2::IndexTime::new(0, 0),
+                    mtime: git2::IndexTime::new(0, 0),
+                    dev: 0,
+                    ino: 0,
+                    mode: 0o160000,
+                    uid: 0,
+                    gid: 0,
+                    file_size: 0,
+                    id,
+                    flags: 0,
+                    flags_extended: 0,
+                    path: b"vendor/lib".to_vec(),
+                })
+                .unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap()
+        };
+        commit_gitlink(old_sub, "add submodule");
+        let oid = commit_gitlink(new_sub, "bump submodule");
+
+        let diffs = diff_commit(
+            dir.path().to_str().unwrap(),
+            &oid.to_string(),
+            &DiffRequest::default(),
+        )
+        .unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].kind, FileKind::Submodule);
+        assert_eq!(
+            diffs[0].submodule,
+            Some(SubmoduleChange {
+                old_commit: Some(old_sub.to_string()),
+                new_commit: Some(new_sub.to_string()),
+            })
+        );
+        assert!(diffs[0].chunks.is_empty());
+        assert!(diffs[0].new_blob.is_none());
+    }
+
+    #[test]
+    fn test_diff_workdir_grouped_separates_staged_unstaged_untracked() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("staged.txt"), "staged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.path().join("hello.txt"), "hello world\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+        let changes =
+            diff_workdir_grouped(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap();
+
+        assert_eq!(changes.staged.len(), 1);
+        assert_eq!(changes.staged[0].new_path.as_deref(), Some("staged.txt"));
+        assert_eq!(changes.staged[0].status, FileStatus::Added);
+        assert_eq!(changes.unstaged.len(), 1);
+        assert_eq!(changes.unstaged[0].new_path.as_deref(), Some("hello.txt"));
+        assert_eq!(changes.unstaged[0].status, FileStatus::Modified);
+        assert_eq!(changes.untracked.len(), 1);
+        assert_eq!(changes.untracked[0].new_path.as_deref(), Some("new.txt"));
+        assert_eq!(changes.untracked[0].status, FileStatus::Added);
+        assert!(!changes.untracked[0].chunks.is_empty());
+    }
+
+    #[test]
+    fn test_diff_workdir_grouped_same_file_staged_and_modified() {
+        let (dir, repo) = init_repo_with_commit();
+        fs::write(dir.path().join("hello.txt"), "staged edit\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.path().join("hello.txt"), "loose edit\n").unwrap();
+
+        let changes =
+            diff_workdir_grouped(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap();
+
+        let added = |diff: &FileDiff| -> Vec<String> {
+            diff.chunks
+                .iter()
+                .flat_map(|c| &c.lines)
+                .filter(|l| l.origin == LineOrigin::Addition)
+                .map(|l| l.content.clone())
+                .collect()
+        };
+        assert_eq!(added(&changes.staged[0]), vec!["staged edit\n"]);
+        assert_eq!(added(&changes.unstaged[0]), vec!["loose edit\n"]);
+        assert!(changes.untracked.is_empty());
+    }
+
+    #[test]
+    fn test_diff_workdir_grouped_clean() {
+        let (dir, _) = init_repo_with_commit();
+        let changes =
+            diff_workdir_grouped(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap();
+        assert!(changes.staged.is_empty());
+        assert!(changes.unstaged.is_empty());
+        assert!(changes.untracked.is_empty());
+    }
 }
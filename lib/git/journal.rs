@@ -0,0 +1,405 @@
+//! Operation journal for undoing destructive branch operations.
+//!
+//! Each entry is a reflog entry on [`JOURNAL_REF`]: the entry's new id is the
+//! commit the operation moved away from and its message describes the
+//! operation. Keeping the journal in the reflog means it survives restarts,
+//! and `git gc` keeps the recorded commits alive for as long as the reflog
+//! entries exist.
+//!
+//! The reflog is shared by all linked worktrees, so every entry records the
+//! worktree it was made in and each worktree only sees (and undoes) its own
+//! operations.
+
+use anyhow::{Context, Result};
+use git2::{BranchType, Oid, Repository};
+
+use super::branch::checkout_branch;
+use super::open_repo;
+
+/// Ref whose reflog holds the journal.
+pub const JOURNAL_REF: &str = "refs/reown/journal";
+
+const MESSAGE_PREFIX: &str = "reown-journal: ";
+const UNDO_MESSAGE: &str = "reown-journal: undo";
+
+/// A recorded operation that can be undone.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Operation {
+    /// A local branch was deleted; `target` is where it pointed.
+    DeleteBranch {
+        branch: String,
+        /// Upstream of the branch, e.g. `origin/feature`.
+        upstream: Option<String>,
+    },
+    /// HEAD was switched from `from` to `to`; `target` is the previous HEAD
+    /// commit. `from` is `None` when HEAD was detached.
+    SwitchBranch { from: Option<String>, to: String },
+}
+
+/// What is stored in a journal entry's message.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Record {
+    #[serde(flatten)]
+    operation: Operation,
+    /// Name of the linked worktree, `None` for the main worktree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    worktree: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct JournalEntry {
+    #[serde(flatten)]
+    pub operation: Operation,
+    /// Commit the operation moved away from.
+    pub target: String,
+    /// Seconds since the Unix epoch.
+    pub time: i64,
+}
+
+/// Operations that can still be undone, newest first.
+pub fn list_operations(repo_path: &str) -> Result<Vec<JournalEntry>> {
+    let repo = open_repo(repo_path)?;
+    pending(&repo)
+}
+
+/// Undo the most recent operation that has not been undone yet.
+///
+/// A deleted branch is recreated at its old target (with its upstream if
+/// that still exists); a switch checks the previous branch, or the previous
+/// detached commit, out again. Returns `None` when there is nothing to undo.
+pub fn undo_last_operation(repo_path: &str) -> Result<Option<JournalEntry>> {
+    let repo = open_repo(repo_path)?;
+    let Some(entry) = pending(&repo)?.into_iter().next() else {
+        return Ok(None);
+    };
+    let target = Oid::from_str(&entry.target)?;
+
+    match &entry.operation {
+        Operation::DeleteBranch { branch, upstream } => {
+            if repo.find_branch(branch, BranchType::Local).is_ok() {
+                anyhow::bail!("Branch '{branch}' already exists");
+            }
+            let commit = repo
+                .find_commit(target)
+                .with_context(|| format!("Commit {target} of branch '{branch}' is gone"))?;
+            let mut restored = repo
+                .branch(branch, &commit, false)
+                .with_context(|| format!("Failed to restore branch '{branch}'"))?;
+            if let Some(upstream) = upstream {
+                // The remote branch may have been deleted as well
+                let _ = restored.set_upstream(Some(upstream));
+            }
+        }
+        Operation::SwitchBranch {
+            from: Some(from), ..
+        } => {
+            let branch = repo
+                .find_branch(from, BranchType::Local)
+                .with_context(|| format!("Branch '{from}' not found"))?;
+            let refname = branch
+                .get()
+                .name()
+                .context("Invalid branch reference name")?
+                .to_string();
+            let tip = branch.get().peel_to_commit()?.id();
+            checkout_branch(&repo, from, Some(&refname), tip)?;
+        }
+        Operation::SwitchBranch { from: None, .. } => {
+            checkout_branch(&repo, &entry.target, None, target)?;
+        }
+    }
+
+    append(&repo, target, &undo_message(&repo))?;
+    Ok(Some(entry))
+}
+
+/// Record `operation` before it is carried out.
+pub(crate) fn record(repo: &Repository, operation: &Operation, target: Oid) -> Result<()> {
+    let record = Record {
+        operation: operation.clone(),
+        worktree: worktree_name(repo),
+    };
+    let message = format!("{MESSAGE_PREFIX}{}", serde_json::to_string(&record)?);
+    append(repo, target, &message)
+}
+
+/// Drop the newest entry of `operation` made in this worktree, for an
+/// operation that failed after `record`. Entries of other worktrees are
+/// left alone even when they were written in between.
+pub(crate) fn discard_last(repo: &Repository, operation: &Operation) -> Result<()> {
+    let worktree = worktree_name(repo);
+    let mut reflog = repo.reflog(JOURNAL_REF)?;
+    let index = reflog.iter().position(|entry| {
+        entry
+            .message()
+            .and_then(parse_record)
+            .is_some_and(|record| record.worktree == worktree && record.operation == *operation)
+    });
+    if let Some(index) = index {
+        reflog.remove(index, false)?;
+        reflog.write()?;
+    }
+    Ok(())
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+/// Name of the linked worktree `repo` was opened from, `None` for the main
+/// worktree.
+fn worktree_name(repo: &Repository) -> Option<String> {
+    if !repo.is_worktree() {
+        return None;
+    }
+    // The git dir of a linked worktree is `<common dir>/worktrees/<name>`
+    repo.path()
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn parse_record(message: &str) -> Option<Record> {
+    let json = message.strip_prefix(MESSAGE_PREFIX)?;
+    serde_json::from_str(json).ok()
+}
+
+fn undo_message(repo: &Repository) -> String {
+    match worktree_name(repo) {
+        Some(name) => format!("{UNDO_MESSAGE} {name}"),
+        None => UNDO_MESSAGE.to_string(),
+    }
+}
+
+/// Append one entry to the journal, pointing its ref at `target`.
+fn append(repo: &Repository, target: Oid, message: &str) -> Result<()> {
+    // Refs outside refs/heads are only logged when the reflog already exists
+    repo.reference_ensure_log(JOURNAL_REF)?;
+    if repo.refname_to_id(JOURNAL_REF).ok() != Some(target) {
+        repo.reference(JOURNAL_REF, target, true, message)
+            .context("Failed to write operation journal")?;
+        return Ok(());
+    }
+
+    // libgit2 does not log updates that leave the ref unchanged
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("reown", "reown@localhost"))?;
+    let mut reflog = repo.reflog(JOURNAL_REF)?;
+    reflog.append(target, &signature, Some(message))?;
+    reflog
+        .write()
+        .context("Failed to write operation journal")?;
+    Ok(())
+}
+
+/// Walk the journal of the current worktree newest first, skipping
+/// operations cancelled by undos.
+fn pending(repo: &Repository) -> Result<Vec<JournalEntry>> {
+    let reflog = repo.reflog(JOURNAL_REF)?;
+    let worktree = worktree_name(repo);
+    let undo = undo_message(repo);
+    let mut undos = 0usize;
+    let mut entries = Vec::new();
+    for entry in reflog.iter() {
+        let Some(message) = entry.message() else {
+            continue;
+        };
+        if message == undo {
+            undos += 1;
+            continue;
+        }
+        let Some(Record {
+            operation,
+            worktree: entry_worktree,
+        }) = parse_record(message)
+        else {
+            continue;
+        };
+        if entry_worktree != worktree {
+            continue;
+        }
+        if undos > 0 {
+            undos -= 1;
+            continue;
+        }
+        entries.push(JournalEntry {
+            operation,
+            target: entry.id_new().to_string(),
+            time: entry.committer().when().seconds(),
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::branch::{create_branch, delete_branch, switch_branch};
+    use crate::git::test_utils::init_repo_with_commit;
+
+    #[test]
+    fn test_undo_delete_branch() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        let tip = repo.revparse_single("feature").unwrap().id();
+
+        delete_branch(path, "feature").unwrap();
+        assert!(repo.find_branch("feature", BranchType::Local).is_err());
+
+        let ops = list_operations(path).unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(
+            ops[0].operation,
+            Operation::DeleteBranch {
+                branch: "feature".to_string(),
+                upstream: None,
+            }
+        );
+
+        let undone = undo_last_operation(path).unwrap().unwrap();
+        assert_eq!(undone.target, tip.to_string());
+        let restored = repo.find_branch("feature", BranchType::Local).unwrap();
+        assert_eq!(restored.get().target(), Some(tip));
+        assert!(list_operations(path).unwrap().is_empty());
+        assert!(undo_last_operation(path).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_undo_switch_branch() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+
+        switch_branch(path, "feature").unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+
+        undo_last_operation(path).unwrap().unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+    }
+
+    #[test]
+    fn test_undo_in_reverse_order_survives_reopen() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "a").unwrap();
+        create_branch(path, "b").unwrap();
+        switch_branch(path, "a").unwrap();
+        delete_branch(path, "b").unwrap();
+
+        // Everything below opens the repository afresh, as after a restart
+        let ops = list_operations(path).unwrap();
+        assert_eq!(ops.len(), 2);
+        assert!(matches!(ops[0].operation, Operation::DeleteBranch { .. }));
+
+        undo_last_operation(path).unwrap();
+        let repo = Repository::open(path).unwrap();
+        assert!(repo.find_branch("b", BranchType::Local).is_ok());
+        assert_eq!(repo.head().unwrap().shorthand(), Some("a"));
+
+        undo_last_operation(path).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        assert!(undo_last_operation(path).unwrap().is_none());
+
+        // The journal is an ordinary reflog
+        assert_eq!(repo.reflog(JOURNAL_REF).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_failed_operation_is_not_journaled() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        assert!(delete_branch(path, "main").is_err());
+        assert!(switch_branch(path, "missing").is_err());
+        assert!(list_operations(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_journal_is_per_worktree() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        create_branch(path, "linked").unwrap();
+        create_branch(path, "other").unwrap();
+        let wt_dir = tempfile::tempdir().unwrap();
+        let wt_path = wt_dir.path().join("wt");
+        let wt_path = wt_path.to_str().unwrap();
+        crate::git::worktree::add_worktree(path, wt_path, "linked").unwrap();
+
+        switch_branch(path, "feature").unwrap();
+        switch_branch(wt_path, "other").unwrap();
+
+        let ops = list_operations(wt_path).unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(
+            ops[0].operation,
+            Operation::SwitchBranch {
+                from: Some("linked".to_string()),
+                to: "other".to_string(),
+            }
+        );
+
+        // Undo in the main worktree only reverts its own switch
+        undo_last_operation(path).unwrap().unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        let wt_repo = Repository::open(wt_path).unwrap();
+        assert_eq!(wt_repo.head().unwrap().shorthand(), Some("other"));
+        assert!(list_operations(path).unwrap().is_empty());
+        assert_eq!(list_operations(wt_path).unwrap().len(), 1);
+
+        undo_last_operation(wt_path).unwrap().unwrap();
+        assert_eq!(wt_repo.head().unwrap().shorthand(), Some("linked"));
+        assert!(list_operations(wt_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_discard_last_keeps_other_worktree_entries() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "linked").unwrap();
+        let wt_dir = tempfile::tempdir().unwrap();
+        let wt_path = wt_dir.path().join("wt");
+        let wt_path = wt_path.to_str().unwrap();
+        crate::git::worktree::add_worktree(path, wt_path, "linked").unwrap();
+        let wt_repo = Repository::open(wt_path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+
+        let failed = Operation::SwitchBranch {
+            from: Some("main".to_string()),
+            to: "missing".to_string(),
+        };
+        record(&repo, &failed, head).unwrap();
+        // Another worktree journals an operation before the discard
+        let other = Operation::DeleteBranch {
+            branch: "gone".to_string(),
+            upstream: None,
+        };
+        record(&wt_repo, &other, head).unwrap();
+
+        discard_last(&repo, &failed).unwrap();
+        assert!(list_operations(path).unwrap().is_empty());
+        let ops = list_operations(wt_path).unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].operation, other);
+    }
+
+    #[test]
+    fn test_undo_switch_from_detached_head() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_branch(path, "feature").unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        repo.set_head_detached(head).unwrap();
+
+        switch_branch(path, "feature").unwrap();
+        let undone = undo_last_operation(path).unwrap().unwrap();
+        assert_eq!(
+            undone.operation,
+            Operation::SwitchBranch {
+                from: None,
+                to: "feature".to_string(),
+            }
+        );
+        assert!(repo.head_detached().unwrap());
+        assert_eq!(repo.head().unwrap().target(), Some(head));
+    }
+}
@@ -0,0 +1,550 @@
+use anyhow::{Context, Result};
+use git2::{Commit, Oid, Repository, Sort};
+use std::path::Path;
+
+use super::open_repo;
+use crate::github::{CommitInfo, SignatureStatus};
+
+/// Filters for `log`. Every field is optional; the default lists the whole
+/// history of HEAD.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LogFilter {
+    /// Revision (`main`, a SHA) or range (`A..B`). Defaults to HEAD.
+    pub range: Option<String>,
+    /// Only commits that change this file or directory.
+    pub path: Option<String>,
+    /// Case-insensitive substring of the author name or email.
+    pub author: Option<String>,
+    /// Only commits with a commit time at or after this Unix timestamp.
+    pub since: Option<i64>,
+    /// Only commits with a commit time at or before this Unix timestamp.
+    pub until: Option<i64>,
+    /// Follow only the first parent of merge commits.
+    pub first_parent: bool,
+    /// Maximum number of commits to return.
+    pub limit: Option<usize>,
+}
+
+/// A local commit. `sha`, `message`, `author` and `date` match the fields
+/// of `github::CommitInfo` so local and remote commits render the same way.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct LogEntry {
+    pub sha: String,
+    pub message: String,
+    pub author: String,
+    /// Author date in RFC 3339 format.
+    pub date: String,
+    /// First line of the message.
+    pub summary: String,
+    pub author_email: String,
+    /// Author time as a Unix timestamp.
+    pub author_time: i64,
+    pub committer: String,
+    /// Commit time as a Unix timestamp.
+    pub commit_time: i64,
+    pub parents: Vec<String>,
+    /// Whether the commit carries a well-formed signature. See
+    /// [`signature_status`].
+    pub signature: SignatureStatus,
+}
+
+impl From<LogEntry> for CommitInfo {
+    fn from(entry: LogEntry) -> Self {
+        Self {
+            sha: entry.sha,
+            message: entry.message,
+            author: entry.author,
+            date: entry.date,
+            // Local commits have no web URL
+            commit_url: String::new(),
+            signature: entry.signature,
+        }
+    }
+}
+
+/// Walk history newest first, applying `filter`.
+pub fn log(repo_path: &str, filter: &LogFilter) -> Result<Vec<LogEntry>> {
+    let repo = open_repo(repo_path)?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    if filter.first_parent {
+        revwalk.simplify_first_parent()?;
+    }
+    match filter.range.as_deref() {
+        Some(range) if range.contains("..") => revwalk
+            .push_range(range)
+            .with_context(|| format!("Invalid range '{range}'"))?,
+        Some(rev) => {
+            let commit = repo
+                .revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .with_context(|| format!("Revision '{rev}' not found"))?;
+            revwalk.push(commit.id())?;
+        }
+        None => revwalk.push_head().context("Failed to resolve HEAD")?,
+    }
+
+    let author = filter.author.as_deref().map(str::to_lowercase);
+    let mut entries = Vec::new();
+
+    for oid in revwalk {
+        if filter.limit.is_some_and(|limit| entries.len() >= limit) {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+
+        let time = commit.time().seconds();
+        if filter.since.is_some_and(|since| time < since)
+            || filter.until.is_some_and(|until| time > until)
+        {
+            continue;
+        }
+        if let Some(author) = &author {
+            let sig = commit.author();
+            let name = sig.name().unwrap_or("").to_lowercase();
+            let email = sig.email().unwrap_or("").to_lowercase();
+            if !name.contains(author.as_str()) && !email.contains(author.as_str()) {
+                continue;
+            }
+        }
+        if let Some(path) = &filter.path {
+            if !touches_path(&repo, &commit, Path::new(path), filter.first_parent)? {
+                continue;
+            }
+        }
+
+        entries.push(log_entry(&repo, &commit));
+    }
+
+    Ok(entries)
+}
+
+/// Signature state of the commit `oid`.
+///
+/// Only the form of the signature is checked: a PGP, SSH or X.509 (S/MIME)
+/// block is reported as `Unverified`, never as `Signed`. Verifying it
+/// against trusted keys needs the signing tools and keyrings, which this
+/// crate does not depend on.
+pub fn signature_status(repo: &Repository, oid: Oid) -> Result<SignatureStatus> {
+    let signature = match repo.extract_signature(&oid, None) {
+        Ok((signature, _signed_data)) => signature,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            return Ok(SignatureStatus::Unsigned);
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read signature of {oid}"));
+        }
+    };
+    let well_formed = std::str::from_utf8(&signature).is_ok_and(is_armored_signature);
+    Ok(if well_formed {
+        SignatureStatus::Unverified
+    } else {
+        SignatureStatus::Invalid
+    })
+}
+
+/// Format a git timestamp as RFC 3339 in the signature's own UTC offset,
+/// e.g. `2025-01-15T19:30:00+09:00`.
+pub(crate) fn format_git_time(time: git2::Time) -> String {
+    let offset = i64::from(time.offset_minutes());
+    let local = time.seconds() + offset * 60;
+    let (days, secs) = (local.div_euclid(86_400), local.rem_euclid(86_400));
+    let (year, month, day) = civil_from_days(days);
+    let zone = if offset == 0 {
+        "Z".to_string()
+    } else {
+        let sign = if offset < 0 { '-' } else { '+' };
+        format!("{sign}{:02}:{:02}", offset.abs() / 60, offset.abs() % 60)
+    };
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}{zone}",
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60
+    )
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn log_entry(repo: &Repository, commit: &Commit<'_>) -> LogEntry {
+    let author = commit.author();
+    let committer = commit.committer();
+    LogEntry {
+        sha: commit.id().to_string(),
+        message: commit.message().unwrap_or("").to_string(),
+        author: author.name().unwrap_or("").to_string(),
+        date: format_git_time(author.when()),
+        summary: commit.summary().unwrap_or("").to_string(),
+        author_email: author.email().unwrap_or("").to_string(),
+        author_time: author.when().seconds(),
+        committer: committer.name().unwrap_or("").to_string(),
+        commit_time: commit.time().seconds(),
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        // An unreadable signature header is as untrustworthy as a bad one
+        signature: signature_status(repo, commit.id()).unwrap_or(SignatureStatus::Invalid),
+    }
+}
+
+/// Whether `signature` is a complete ASCII-armored PGP, SSH or S/MIME block
+/// with a non-empty body.
+fn is_armored_signature(signature: &str) -> bool {
+    let Some(rest) = signature.trim().strip_prefix("-----BEGIN ") else {
+        return false;
+    };
+    let Some((kind, rest)) = rest.split_once("-----") else {
+        return false;
+    };
+    if !matches!(kind, "PGP SIGNATURE" | "SSH SIGNATURE" | "SIGNED MESSAGE") {
+        return false;
+    }
+    rest.strip_suffix(format!("-----END {kind}-----").as_str())
+        .is_some_and(|body| !body.trim().is_empty())
+}
+
+/// Whether `commit` changes `path` relative to its parents.
+///
+/// Like `git log -- <path>`, a merge is only listed when the path differs
+/// from every parent (or from the first parent with `first_parent`).
+fn touches_path(
+    repo: &Repository,
+    commit: &Commit<'_>,
+    path: &Path,
+    first_parent: bool,
+) -> Result<bool> {
+    let entry_id =
+        |tree: &git2::Tree<'_>| -> Option<Oid> { tree.get_path(path).ok().map(|e| e.id()) };
+    let current = entry_id(&commit.tree()?);
+
+    let parent_count = if first_parent {
+        commit.parent_count().min(1)
+    } else {
+        commit.parent_count()
+    };
+    if parent_count == 0 {
+        return Ok(current.is_some());
+    }
+
+    for idx in 0..parent_count {
+        let parent_tree = repo.find_commit(commit.parent_id(idx)?)?.tree()?;
+        if entry_id(&parent_tree) == current {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Convert days since 1970-01-01 to a (year, month, day) civil date.
+/// Howard Hinnant's `civil_from_days` algorithm.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file_as, init_repo_with_commit, signature_at};
+    use git2::Time;
+
+    fn summaries(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.summary.as_str()).collect()
+    }
+
+    /// init → "one" (Alice, src/a.rs) → "two" (Bob, docs/b.md) → "three" (Alice, src/a.rs)
+    fn init_history() -> (tempfile::TempDir, Repository) {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "src/a.rs",
+            "1\n",
+            "one",
+            &signature_at("Alice", 1_000_000),
+        );
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "docs/b.md",
+            "2\n",
+            "two",
+            &signature_at("Bob", 2_000_000),
+        );
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "src/a.rs",
+            "3\n",
+            "three\n\nbody",
+            &signature_at("Alice", 3_000_000),
+        );
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_log_all() {
+        let (dir, _repo) = init_history();
+        let entries = log(dir.path().to_str().unwrap(), &LogFilter::default()).unwrap();
+        assert_eq!(summaries(&entries), vec!["three", "two", "one", "init"]);
+
+        let latest = &entries[0];
+        assert_eq!(latest.message, "three\n\nbody");
+        assert_eq!(latest.author, "Alice");
+        assert_eq!(latest.author_email, "alice@example.com");
+        assert_eq!(latest.author_time, 3_000_000);
+        assert_eq!(latest.date, "1970-02-04T17:20:00Z");
+        assert_eq!(latest.parents, vec![entries[1].sha.clone()]);
+        assert!(entries[3].parents.is_empty());
+    }
+
+    #[test]
+    fn test_log_range() {
+        let (dir, repo) = init_history();
+        let one = repo.revparse_single("HEAD~2").unwrap().id();
+        let filter = LogFilter {
+            range: Some(format!("{one}..HEAD")),
+            ..Default::default()
+        };
+        let entries = log(dir.path().to_str().unwrap(), &filter).unwrap();
+        assert_eq!(summaries(&entries), vec!["three", "two"]);
+    }
+
+    #[test]
+    fn test_log_single_revision() {
+        let (dir, _repo) = init_history();
+        let filter = LogFilter {
+            range: Some("HEAD~1".to_string()),
+            ..Default::default()
+        };
+        let entries = log(dir.path().to_str().unwrap(), &filter).unwrap();
+        assert_eq!(summaries(&entries), vec!["two", "one", "init"]);
+    }
+
+    #[test]
+    fn test_log_path_filter() {
+        let (dir, _repo) = init_history();
+        let path = dir.path().to_str().unwrap();
+        let by_file = LogFilter {
+            path: Some("src/a.rs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            summaries(&log(path, &by_file).unwrap()),
+            vec!["three", "one"]
+        );
+
+        let by_dir = LogFilter {
+            path: Some("docs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(summaries(&log(path, &by_dir).unwrap()), vec!["two"]);
+    }
+
+    #[test]
+    fn test_log_author_and_date_filters() {
+        let (dir, _repo) = init_history();
+        let path = dir.path().to_str().unwrap();
+        let by_author = LogFilter {
+            author: Some("ALICE@".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            summaries(&log(path, &by_author).unwrap()),
+            vec!["three", "one"]
+        );
+
+        let window = LogFilter {
+            since: Some(1_500_000),
+            until: Some(2_500_000),
+            ..Default::default()
+        };
+        assert_eq!(summaries(&log(path, &window).unwrap()), vec!["two"]);
+    }
+
+    #[test]
+    fn test_log_limit() {
+        let (dir, _repo) = init_history();
+        let filter = LogFilter {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let entries = log(dir.path().to_str().unwrap(), &filter).unwrap();
+        assert_eq!(summaries(&entries), vec!["three", "two"]);
+    }
+
+    #[test]
+    fn test_log_first_parent() {
+        let (dir, repo) = init_repo_with_commit();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("topic", &base, false).unwrap();
+        let main_tip = commit_file_as(
+            &repo,
+            "HEAD",
+            "main.txt",
+            "m\n",
+            "on main",
+            &signature_at("Alice", 1_000_000),
+        );
+
+        // Commit on topic without moving HEAD
+        repo.set_head("refs/heads/topic").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let topic_tip = commit_file_as(
+            &repo,
+            "HEAD",
+            "topic.txt",
+            "t\n",
+            "on topic",
+            &signature_at("Bob", 2_000_000),
+        );
+        repo.set_head("refs/heads/main").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+
+        let sig = signature_at("Alice", 3_000_000);
+        let main_commit = repo.find_commit(main_tip).unwrap();
+        let topic_commit = repo.find_commit(topic_tip).unwrap();
+        let mut index = repo
+            .merge_commits(&main_commit, &topic_commit, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "merge topic",
+            &tree,
+            &[&main_commit, &topic_commit],
+        )
+        .unwrap();
+
+        let path = dir.path().to_str().unwrap();
+        let all = log(path, &LogFilter::default()).unwrap();
+        assert!(summaries(&all).contains(&"on topic"));
+
+        let first_parent = LogFilter {
+            first_parent: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            summaries(&log(path, &first_parent).unwrap()),
+            vec!["merge topic", "on main", "init"]
+        );
+
+        // The merge brings topic.txt in relative to its first parent
+        let topic_path = LogFilter {
+            path: Some("topic.txt".to_string()),
+            first_parent: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            summaries(&log(path, &topic_path).unwrap()),
+            vec!["merge topic"]
+        );
+    }
+
+    #[test]
+    fn test_log_invalid_revision() {
+        let (dir, _repo) = init_history();
+        let filter = LogFilter {
+            range: Some("no-such-branch".to_string()),
+            ..Default::default()
+        };
+        assert!(log(dir.path().to_str().unwrap(), &filter).is_err());
+    }
+
+    #[test]
+    fn test_format_git_time() {
+        assert_eq!(format_git_time(Time::new(0, 0)), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_git_time(Time::new(1_736_937_000, 540)),
+            "2025-01-15T19:30:00+09:00"
+        );
+        assert_eq!(
+            format_git_time(Time::new(1_709_164_800, -300)),
+            "2024-02-28T19:00:00-05:00"
+        );
+    }
+
+    #[test]
+    fn test_log_entry_into_commit_info() {
+        let (dir, _repo) = init_history();
+        let entry = log(dir.path().to_str().unwrap(), &LogFilter::default())
+            .unwrap()
+            .remove(0);
+        let sha = entry.sha.clone();
+        let info: CommitInfo = entry.into();
+        assert_eq!(info.sha, sha);
+        assert_eq!(info.author, "Alice");
+        assert!(info.commit_url.is_empty());
+    }
+
+    /// Create a commit on HEAD carrying `signature` in its `gpgsig` header.
+    fn commit_with_signature(repo: &Repository, signature: &str) -> Oid {
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let sig = signature_at("Alice", 1_000_000);
+        let buf = repo
+            .commit_create_buffer(&sig, &sig, "signed", &parent.tree().unwrap(), &[&parent])
+            .unwrap();
+        let oid = repo
+            .commit_signed(buf.as_str().unwrap(), signature, None)
+            .unwrap();
+        repo.head()
+            .unwrap()
+            .set_target(oid, "commit (signed)")
+            .unwrap();
+        oid
+    }
+
+    #[test]
+    fn test_signature_status() {
+        let (dir, repo) = init_repo_with_commit();
+        let unsigned = repo.head().unwrap().target().unwrap();
+        assert_eq!(
+            signature_status(&repo, unsigned).unwrap(),
+            SignatureStatus::Unsigned
+        );
+
+        // A well-formed block whose content is not a real signature must not
+        // be reported as signed
+        let signed = commit_with_signature(
+            &repo,
+            "-----BEGIN SSH SIGNATURE-----\nU1NIU0lHAAAAAQ==\n-----END SSH SIGNATURE-----",
+        );
+        assert_eq!(
+            signature_status(&repo, signed).unwrap(),
+            SignatureStatus::Unverified
+        );
+
+        let entries = log(dir.path().to_str().unwrap(), &LogFilter::default()).unwrap();
+        assert_eq!(entries[0].signature, SignatureStatus::Unverified);
+        assert_eq!(entries[1].signature, SignatureStatus::Unsigned);
+        let info: CommitInfo = entries[0].clone().into();
+        assert_eq!(info.signature, SignatureStatus::Unverified);
+    }
+
+    #[test]
+    fn test_signature_status_malformed() {
+        let (_dir, repo) = init_repo_with_commit();
+        let truncated = commit_with_signature(&repo, "-----BEGIN PGP SIGNATURE-----\niQEz");
+        assert_eq!(
+            signature_status(&repo, truncated).unwrap(),
+            SignatureStatus::Invalid
+        );
+        let garbage = commit_with_signature(&repo, "not a signature");
+        assert_eq!(
+            signature_status(&repo, garbage).unwrap(),
+            SignatureStatus::Invalid
+        );
+    }
+}
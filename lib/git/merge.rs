@@ -0,0 +1,395 @@
+use anyhow::{Context, Result};
+use git2::{IndexEntry, Repository};
+
+use super::open_repo;
+use super::remote::{fetch, SyncProgress};
+use super::worktree::fetch_pr_head;
+use crate::github::PrInfo;
+
+/// How the two sides of a conflicting path disagree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum ConflictKind {
+    /// Both sides changed the same lines of a file.
+    Content,
+    /// Both sides added a file at the same path with different content.
+    AddAdd,
+    /// One side modified a file the other side deleted.
+    ModifyDelete,
+    Other,
+}
+
+/// One conflicting region of a file.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ConflictHunk {
+    /// First line of the region in the base side's file (1-based).
+    pub ours_start: u32,
+    /// Lines of the region on the base side.
+    pub ours: Vec<String>,
+    /// First line of the region in the head side's file (1-based).
+    pub theirs_start: u32,
+    /// Lines of the region on the head side.
+    pub theirs: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MergeConflict {
+    pub path: String,
+    pub kind: ConflictKind,
+    /// Conflicting regions. Empty for binary files and for
+    /// `ModifyDelete` / `Other` conflicts.
+    pub hunks: Vec<ConflictHunk>,
+}
+
+/// Result of a trial merge of `head` into `base`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct MergeCheck {
+    /// Resolved commit of the base side.
+    pub base: String,
+    /// Resolved commit of the head side.
+    pub head: String,
+    /// `None` when the two sides share no history.
+    pub merge_base: Option<String>,
+    pub mergeable: bool,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Predict whether `head_rev` merges cleanly into `base_rev`.
+///
+/// The merge is done in memory with `merge_commits`; neither the worktree,
+/// the index nor any ref is touched.
+pub fn check_mergeability(repo_path: &str, base_rev: &str, head_rev: &str) -> Result<MergeCheck> {
+    let repo = open_repo(repo_path)?;
+    let base = resolve_commit(&repo, base_rev)?;
+    let head = resolve_commit(&repo, head_rev)?;
+
+    let merge_base = repo.merge_base(base.id(), head.id()).ok();
+    let index = repo
+        .merge_commits(&base, &head, None)
+        .with_context(|| format!("Failed to merge '{head_rev}' into '{base_rev}'"))?;
+
+    let mut conflicts = Vec::new();
+    if index.has_conflicts() {
+        for conflict in index.conflicts()? {
+            let conflict = conflict?;
+            conflicts.push(describe_conflict(
+                &repo,
+                conflict.ancestor,
+                conflict.our,
+                conflict.their,
+            )?);
+        }
+    }
+
+    Ok(MergeCheck {
+        base: base.id().to_string(),
+        head: head.id().to_string(),
+        merge_base: merge_base.map(|oid| oid.to_string()),
+        mergeable: conflicts.is_empty(),
+        conflicts,
+    })
+}
+
+/// Fetch the head and base of `pr` from `origin` and check them.
+///
+/// The fetched PR head ref is only needed for the check and is deleted
+/// again afterwards.
+pub fn check_pr_mergeability(
+    repo_path: &str,
+    pr: &PrInfo,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<MergeCheck> {
+    let head_ref = fetch_pr_head(repo_path, pr.number, progress)?;
+    let base_ref = format!("refs/remotes/origin/{}", pr.base_branch);
+    let refspec = format!("+refs/heads/{}:{base_ref}", pr.base_branch);
+    let check = fetch(repo_path, "origin", &[refspec.as_str()], progress)
+        .with_context(|| format!("Failed to fetch base branch '{}'", pr.base_branch))
+        .and_then(|_| check_mergeability(repo_path, &base_ref, &head_ref));
+    let removed = delete_ref(repo_path, &head_ref);
+    let check = check?;
+    removed?;
+    Ok(check)
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn delete_ref(repo_path: &str, refname: &str) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let mut reference = repo
+        .find_reference(refname)
+        .with_context(|| format!("Reference '{refname}' not found"))?;
+    reference
+        .delete()
+        .with_context(|| format!("Failed to delete '{refname}'"))
+}
+
+fn resolve_commit<'r>(repo: &'r Repository, rev: &str) -> Result<git2::Commit<'r>> {
+    repo.revparse_single(rev)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Revision '{rev}' not found"))
+}
+
+fn describe_conflict(
+    repo: &Repository,
+    ancestor: Option<IndexEntry>,
+    ours: Option<IndexEntry>,
+    theirs: Option<IndexEntry>,
+) -> Result<MergeConflict> {
+    let path = [&ours, &theirs, &ancestor]
+        .into_iter()
+        .flatten()
+        .map(|entry| String::from_utf8_lossy(&entry.path).into_owned())
+        .next()
+        .unwrap_or_default();
+
+    let (kind, hunks) = match (ancestor, ours, theirs) {
+        (Some(ancestor), Some(ours), Some(theirs)) => (
+            ConflictKind::Content,
+            conflict_hunks(repo, &ancestor, &ours, &theirs)?,
+        ),
+        (None, Some(ours), Some(theirs)) => {
+            // Both added: merge against an empty file
+            let empty = IndexEntry {
+                id: repo.blob(b"")?,
+                file_size: 0,
+                path: ours.path.clone(),
+                ..ours
+            };
+            (
+                ConflictKind::AddAdd,
+                conflict_hunks(repo, &empty, &ours, &theirs)?,
+            )
+        }
+        (Some(_), Some(_), None) | (Some(_), None, Some(_)) => {
+            (ConflictKind::ModifyDelete, Vec::new())
+        }
+        _ => (ConflictKind::Other, Vec::new()),
+    };
+
+    Ok(MergeConflict { path, kind, hunks })
+}
+
+fn conflict_hunks(
+    repo: &Repository,
+    ancestor: &IndexEntry,
+    ours: &IndexEntry,
+    theirs: &IndexEntry,
+) -> Result<Vec<ConflictHunk>> {
+    let result = repo
+        .merge_file_from_index(ancestor, ours, theirs, None)
+        .context("Failed to merge file contents")?;
+    match std::str::from_utf8(result.content()) {
+        Ok(merged) => Ok(parse_conflict_markers(merged)),
+        Err(_) => Ok(Vec::new()), // binary
+    }
+}
+
+/// Split merged file content with `<<<<<<<` / `=======` / `>>>>>>>` markers
+/// into conflict hunks, tracking line numbers on each side.
+fn parse_conflict_markers(merged: &str) -> Vec<ConflictHunk> {
+    enum Section {
+        Outside,
+        Ours,
+        Theirs,
+    }
+
+    let mut hunks = Vec::new();
+    let mut section = Section::Outside;
+    let (mut ours_line, mut theirs_line) = (1u32, 1u32);
+    let mut current: Option<ConflictHunk> = None;
+
+    for line in merged.lines() {
+        match section {
+            Section::Outside if line.starts_with("<<<<<<<") => {
+                section = Section::Ours;
+                current = Some(ConflictHunk {
+                    ours_start: ours_line,
+                    ours: Vec::new(),
+                    theirs_start: theirs_line,
+                    theirs: Vec::new(),
+                });
+            }
+            Section::Outside => {
+                ours_line += 1;
+                theirs_line += 1;
+            }
+            Section::Ours if line.starts_with("=======") => section = Section::Theirs,
+            Section::Ours => {
+                if let Some(hunk) = current.as_mut() {
+                    hunk.ours.push(line.to_string());
+                }
+                ours_line += 1;
+            }
+            Section::Theirs if line.starts_with(">>>>>>>") => {
+                section = Section::Outside;
+                hunks.extend(current.take());
+            }
+            Section::Theirs => {
+                if let Some(hunk) = current.as_mut() {
+                    hunk.theirs.push(line.to_string());
+                }
+                theirs_line += 1;
+            }
+        }
+    }
+
+    hunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, delete_file, init_repo_with_commit};
+    use std::path::Path;
+
+    fn branch_from_main(repo: &Repository, name: &str) {
+        let main = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch(name, &main, false).unwrap();
+    }
+
+    #[test]
+    fn test_clean_merge() {
+        let (dir, repo) = init_repo_with_commit();
+        branch_from_main(&repo, "feature");
+        commit_file(&repo, "feature", "feature.txt", "feature\n", "edit");
+        commit_file(&repo, "main", "main.txt", "main\n", "edit");
+
+        let check = check_mergeability(dir.path().to_str().unwrap(), "main", "feature").unwrap();
+        assert!(check.mergeable);
+        assert!(check.conflicts.is_empty());
+        assert!(check.merge_base.is_some());
+    }
+
+    #[test]
+    fn test_content_conflict_reports_hunks() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "main", "code.rs", "a\nb\nc\nd\n", "edit");
+        branch_from_main(&repo, "feature");
+        commit_file(&repo, "feature", "code.rs", "a\nB-feature\nc\nd\n", "edit");
+        commit_file(
+            &repo,
+            "main",
+            "code.rs",
+            "a\nB-main\nB-main2\nc\nd\n",
+            "edit",
+        );
+
+        let check = check_mergeability(dir.path().to_str().unwrap(), "main", "feature").unwrap();
+        assert!(!check.mergeable);
+        assert_eq!(check.conflicts.len(), 1);
+
+        let conflict = &check.conflicts[0];
+        assert_eq!(conflict.path, "code.rs");
+        assert_eq!(conflict.kind, ConflictKind::Content);
+        assert_eq!(
+            conflict.hunks,
+            vec![ConflictHunk {
+                ours_start: 2,
+                ours: vec!["B-main".to_string(), "B-main2".to_string()],
+                theirs_start: 2,
+                theirs: vec!["B-feature".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_modify_delete_and_add_add_conflicts() {
+        let (dir, repo) = init_repo_with_commit();
+        branch_from_main(&repo, "feature");
+        delete_file(&repo, "feature", "hello.txt", "edit");
+        commit_file(&repo, "feature", "new.txt", "feature\n", "edit");
+        commit_file(&repo, "main", "hello.txt", "changed\n", "edit");
+        commit_file(&repo, "main", "new.txt", "main\n", "edit");
+
+        let check = check_mergeability(dir.path().to_str().unwrap(), "main", "feature").unwrap();
+        assert!(!check.mergeable);
+        let kind_of = |path: &str| {
+            check
+                .conflicts
+                .iter()
+                .find(|c| c.path == path)
+                .map(|c| c.kind)
+        };
+        assert_eq!(kind_of("hello.txt"), Some(ConflictKind::ModifyDelete));
+        assert_eq!(kind_of("new.txt"), Some(ConflictKind::AddAdd));
+        let add_add = check
+            .conflicts
+            .iter()
+            .find(|c| c.path == "new.txt")
+            .unwrap();
+        assert_eq!(add_add.hunks[0].ours, vec!["main"]);
+        assert_eq!(add_add.hunks[0].theirs, vec!["feature"]);
+    }
+
+    #[test]
+    fn test_check_does_not_touch_worktree_or_refs() {
+        let (dir, repo) = init_repo_with_commit();
+        branch_from_main(&repo, "feature");
+        commit_file(&repo, "feature", "hello.txt", "feature\n", "edit");
+        let main_tip = commit_file(&repo, "main", "hello.txt", "main\n", "edit");
+
+        check_mergeability(dir.path().to_str().unwrap(), "main", "feature").unwrap();
+
+        assert_eq!(repo.head().unwrap().target(), Some(main_tip));
+        assert!(!repo.index().unwrap().has_conflicts());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "main\n"
+        );
+        assert!(!Path::new(&repo.path().join("MERGE_HEAD")).exists());
+    }
+
+    #[test]
+    fn test_unknown_revision() {
+        let (dir, _repo) = init_repo_with_commit();
+        let result = check_mergeability(dir.path().to_str().unwrap(), "main", "nope");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_conflict_markers_tracks_lines() {
+        let merged = "x\n<<<<<<< ours\na\n=======\nb1\nb2\n>>>>>>> theirs\ny\n<<<<<<< ours\nc\n=======\n>>>>>>> theirs\n";
+        let hunks = parse_conflict_markers(merged);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!((hunks[0].ours_start, hunks[0].theirs_start), (2, 2));
+        assert_eq!((hunks[1].ours_start, hunks[1].theirs_start), (4, 5));
+        assert!(hunks[1].theirs.is_empty());
+    }
+
+    #[test]
+    fn test_check_pr_mergeability_fetches_head() {
+        let (origin_dir, origin) = init_repo_with_commit();
+        branch_from_main(&origin, "feature");
+        let head = commit_file(&origin, "feature", "feature.txt", "pr\n", "edit");
+        origin
+            .reference("refs/pull/7/head", head, true, "test")
+            .unwrap();
+
+        let clone_dir = tempfile::TempDir::new().unwrap();
+        let url = format!("file://{}", origin_dir.path().display());
+        let clone = Repository::clone(&url, clone_dir.path()).unwrap();
+        drop(clone);
+
+        let pr = PrInfo {
+            number: 7,
+            title: "PR".to_string(),
+            author: "alice".to_string(),
+            state: "open".to_string(),
+            merge_commit_sha: None,
+            head_branch: "feature".to_string(),
+            head_repo: None,
+            base_branch: "main".to_string(),
+            updated_at: String::new(),
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: String::new(),
+            html_url: String::new(),
+        };
+        let check =
+            check_pr_mergeability(clone_dir.path().to_str().unwrap(), &pr, &mut |_| {}).unwrap();
+        assert!(check.mergeable);
+        assert_eq!(check.head, head.to_string());
+        let clone = Repository::open(clone_dir.path()).unwrap();
+        assert!(clone.find_reference("refs/reown/pull/7").is_err());
+    }
+}
@@ -1,11 +1,26 @@
+pub mod blame;
 pub mod branch;
+pub mod cherry_pick;
+pub mod commit;
 pub mod diff;
+pub mod journal;
+pub mod log;
+pub mod merge;
+pub mod rebase;
+pub mod remote;
+pub mod remote_url;
+pub mod stage;
+pub mod stash;
+pub mod status;
+pub mod tag;
 #[cfg(test)]
 pub mod test_utils;
 pub mod todo;
+pub mod word_diff;
 pub mod worktree;
 
 pub use branch::EnrichedBranchInfo;
+pub use remote_url::{RemoteProtocol, RemoteUrl};
 
 use anyhow::{Context, Result};
 use git2::Repository;
@@ -33,40 +48,42 @@ pub struct RepoInfo {
     pub github_owner: Option<String>,
     /// GitHub の repo 名（リモートURLからパースした場合）
     pub github_repo: Option<String>,
+    /// origin リモートの URL をパースした結果（GitHub Enterprise などを含む）
+    pub remote: Option<RemoteUrl>,
+}
+
+/// リモートの情報
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteInfo {
+    /// リモート名
+    pub name: String,
+    /// リモートの URL
+    pub url: String,
+    /// URL をパースした結果（ローカルパスなどは `None`）
+    pub parsed: Option<RemoteUrl>,
+}
+
+/// 全リモートを含むリポジトリ情報
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoRemotes {
+    #[serde(flatten)]
+    pub info: RepoInfo,
+    /// 全リモート（名前順）
+    pub remotes: Vec<RemoteInfo>,
+    /// PR の向き先になるリモート名。fork 構成でなければ origin
+    pub upstream: Option<String>,
+    /// origin が upstream の fork かどうか
+    pub is_fork: bool,
 }
 
 /// リモートURLから GitHub の owner と repo をパースする
 ///
-/// 対応フォーマット:
-/// - HTTPS: `https://github.com/owner/repo.git` or `https://github.com/owner/repo`
-/// - SSH: `git@github.com:owner/repo.git` or `git@github.com:owner/repo`
+/// github.com のリモートのみを対象とする。GitHub Enterprise などの任意のホストは
+/// [`RemoteUrl::parse`] を使う。対応フォーマットは [`RemoteUrl::parse`] を参照。
 pub fn parse_github_remote(url: &str) -> Option<(String, String)> {
-    // HTTPS format: https://github.com/owner/repo.git
-    if let Some(rest) = url
-        .strip_prefix("https://github.com/")
-        .or_else(|| url.strip_prefix("http://github.com/"))
-    {
-        let rest = rest.trim_end_matches('/');
-        let parts: Vec<&str> = rest.splitn(3, '/').collect();
-        if parts.len() >= 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-            let owner = parts[0].to_string();
-            let repo = parts[1].trim_end_matches(".git").to_string();
-            return Some((owner, repo));
-        }
-    }
-
-    // SSH format: git@github.com:owner/repo.git
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let rest = rest.trim_end_matches('/');
-        let parts: Vec<&str> = rest.splitn(3, '/').collect();
-        if parts.len() >= 2 && !parts[0].is_empty() && !parts[1].is_empty() {
-            let owner = parts[0].to_string();
-            let repo = parts[1].trim_end_matches(".git").to_string();
-            return Some((owner, repo));
-        }
-    }
-
-    None
+    RemoteUrl::parse(url)
+        .filter(|remote| remote.is_github_com() && !remote.owner.contains('/'))
+        .map(|remote| (remote.owner, remote.repo))
 }
 
 /// 指定パスのリポジトリ情報を取得する
@@ -93,6 +110,7 @@ pub fn get_repo_info(repo_path: &str) -> Result<RepoInfo> {
         .and_then(parse_github_remote)
         .map(|(o, r)| (Some(o), Some(r)))
         .unwrap_or((None, None));
+    let remote = remote_url.as_deref().and_then(RemoteUrl::parse);
 
     Ok(RepoInfo {
         path,
@@ -100,9 +118,83 @@ pub fn get_repo_info(repo_path: &str) -> Result<RepoInfo> {
         remote_url,
         github_owner,
         github_repo,
+        remote,
+    })
+}
+
+/// 全リモートを含むリポジトリ情報を取得し、fork 構成の upstream を判定する
+///
+/// upstream は次の順で決める:
+/// 1. `upstream` という名前のリモート
+/// 2. origin と同じホスト・リポジトリ名で owner が異なるリモート
+/// 3. origin（なければ唯一のリモート）
+pub fn get_repo_remotes(repo_path: &str) -> Result<RepoRemotes> {
+    let info = get_repo_info(repo_path)?;
+    let repo = open_repo(repo_path)?;
+    let remotes = list_remotes(&repo)?;
+
+    let upstream = detect_upstream(&remotes);
+    let is_fork = upstream.as_deref().is_some_and(|name| name != "origin")
+        && remotes.iter().any(|r| r.name == "origin");
+
+    Ok(RepoRemotes {
+        info,
+        remotes,
+        upstream,
+        is_fork,
     })
 }
 
+/// fork 構成で PR の向き先になるリモート名（`get_repo_remotes` の `upstream`）
+pub(crate) fn upstream_remote(repo: &Repository) -> Option<String> {
+    detect_upstream(&list_remotes(repo).ok()?)
+}
+
+fn list_remotes(repo: &Repository) -> Result<Vec<RemoteInfo>> {
+    let names = repo.remotes().context("リモート一覧の取得に失敗しました")?;
+    let mut remotes: Vec<RemoteInfo> = names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let url = repo.find_remote(name).ok()?.url()?.to_string();
+            Some(RemoteInfo {
+                name: name.to_string(),
+                parsed: RemoteUrl::parse(&url),
+                url,
+            })
+        })
+        .collect();
+    remotes.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(remotes)
+}
+
+fn detect_upstream(remotes: &[RemoteInfo]) -> Option<String> {
+    if let Some(upstream) = remotes.iter().find(|r| r.name == "upstream") {
+        return Some(upstream.name.clone());
+    }
+
+    let origin = remotes.iter().find(|r| r.name == "origin");
+    if let Some(origin_url) = origin.and_then(|o| o.parsed.as_ref()) {
+        let parent = remotes.iter().find(|r| {
+            r.name != "origin"
+                && r.parsed.as_ref().is_some_and(|p| {
+                    p.host == origin_url.host
+                        && p.repo.eq_ignore_ascii_case(&origin_url.repo)
+                        && !p.owner.eq_ignore_ascii_case(&origin_url.owner)
+                })
+        });
+        if let Some(parent) = parent {
+            return Some(parent.name.clone());
+        }
+    }
+
+    match (origin, remotes) {
+        (Some(origin), _) => Some(origin.name.clone()),
+        (None, [only]) => Some(only.name.clone()),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,6 +270,75 @@ mod tests {
         assert_eq!(info.github_repo.as_deref(), Some("testrepo"));
     }
 
+    #[test]
+    fn test_parse_github_remote_ssh_url_and_port() {
+        let (owner, repo) =
+            parse_github_remote("ssh://git@ssh.github.com:443/octocat/hello-world.git/").unwrap();
+        assert_eq!(owner, "octocat");
+        assert_eq!(repo, "hello-world");
+    }
+
+    #[test]
+    fn test_parse_github_remote_rejects_enterprise() {
+        assert!(parse_github_remote("git@ghe.example.com:octocat/hello-world.git").is_none());
+    }
+
+    #[test]
+    fn test_get_repo_info_enterprise_remote() {
+        let (dir, repo) = test_utils::init_test_repo();
+        repo.remote("origin", "git@ghe.example.com:team/service.git")
+            .unwrap();
+        let info = get_repo_info(dir.path().to_str().unwrap()).unwrap();
+        assert!(info.github_owner.is_none());
+        let remote = info.remote.unwrap();
+        assert_eq!(remote.host, "ghe.example.com");
+        assert_eq!(remote.owner, "team");
+        assert_eq!(remote.repo, "service");
+    }
+
+    #[test]
+    fn test_get_repo_remotes_detects_fork_parent() {
+        let (dir, repo) = test_utils::init_test_repo();
+        repo.remote("origin", "git@github.com:me/hello-world.git")
+            .unwrap();
+        repo.remote("parent", "https://github.com/octocat/Hello-World")
+            .unwrap();
+        repo.remote("mirror", "/srv/mirror.git").unwrap();
+
+        let remotes = get_repo_remotes(dir.path().to_str().unwrap()).unwrap();
+        let names: Vec<&str> = remotes.remotes.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["mirror", "origin", "parent"]);
+        assert!(remotes.remotes[0].parsed.is_none());
+        assert_eq!(remotes.upstream.as_deref(), Some("parent"));
+        assert!(remotes.is_fork);
+        assert_eq!(remotes.info.github_owner.as_deref(), Some("me"));
+    }
+
+    #[test]
+    fn test_get_repo_remotes_prefers_upstream_name() {
+        let (dir, repo) = test_utils::init_test_repo();
+        repo.remote("origin", "https://github.com/me/fork.git")
+            .unwrap();
+        repo.remote("upstream", "https://github.com/octocat/project.git")
+            .unwrap();
+        let remotes = get_repo_remotes(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(remotes.upstream.as_deref(), Some("upstream"));
+        assert!(remotes.is_fork);
+    }
+
+    #[test]
+    fn test_get_repo_remotes_without_fork() {
+        let (dir, repo) = test_utils::init_test_repo();
+        let path = dir.path().to_str().unwrap();
+        assert!(get_repo_remotes(path).unwrap().upstream.is_none());
+
+        repo.remote("origin", "https://github.com/octocat/hello-world.git")
+            .unwrap();
+        let remotes = get_repo_remotes(path).unwrap();
+        assert_eq!(remotes.upstream.as_deref(), Some("origin"));
+        assert!(!remotes.is_fork);
+    }
+
     #[test]
     fn test_get_repo_info_invalid_path() {
         let result = get_repo_info("/nonexistent/path");
@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use git2::{ErrorCode, Rebase, RebaseOptions, Repository, RepositoryState};
+
+use super::open_repo;
+use super::stash::conflicted_paths;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum RebaseStatus {
+    /// Every commit was replayed and the branch now points at the result.
+    Completed,
+    /// The branch already contains `onto`; nothing was rewritten.
+    UpToDate,
+    /// Replaying a commit stopped on conflicts. Resolve and stage them, then
+    /// call `continue_rebase`, or give up with `abort_rebase`.
+    Conflicts,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct RebaseResult {
+    pub status: RebaseStatus,
+    /// Number of commits committed by this call.
+    pub applied: usize,
+    /// Total number of commits being replayed.
+    pub total: usize,
+    /// Original commit whose replay stopped on conflicts.
+    pub stopped_at: Option<String>,
+    pub conflicting_paths: Vec<String>,
+    /// Branch tip after a completed rebase.
+    pub head: Option<String>,
+}
+
+/// Rebase the branch checked out in `worktree_path` onto `onto`.
+///
+/// `onto` is any revision, typically the base branch (`origin/main`). The
+/// rebase runs in the worktree itself, so it must have no uncommitted changes
+/// to tracked files. When a commit conflicts the rebase is left in progress
+/// on disk and its state is reported in the result.
+pub fn rebase_onto(worktree_path: &str, onto: &str) -> Result<RebaseResult> {
+    let repo = open_repo(worktree_path)?;
+    if repo.state() != RepositoryState::Clean {
+        anyhow::bail!("Another operation is in progress ({:?})", repo.state());
+    }
+    let head = repo.head().context("Failed to read HEAD")?;
+    if !head.is_branch() {
+        anyhow::bail!("HEAD is detached; check out a branch to rebase");
+    }
+    let dirty = tracked_changes(&repo)?;
+    if !dirty.is_empty() {
+        anyhow::bail!("Worktree has uncommitted changes: {}", dirty.join(", "));
+    }
+
+    let onto_commit = repo
+        .revparse_single(onto)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Revision '{onto}' not found"))?;
+    let head_oid = head.target().context("HEAD has no target")?;
+    if repo.graph_descendant_of(head_oid, onto_commit.id())? || head_oid == onto_commit.id() {
+        return Ok(RebaseResult {
+            status: RebaseStatus::UpToDate,
+            applied: 0,
+            total: 0,
+            stopped_at: None,
+            conflicting_paths: Vec::new(),
+            head: Some(head_oid.to_string()),
+        });
+    }
+
+    let upstream = repo.find_annotated_commit(onto_commit.id())?;
+    let mut rebase = repo
+        .rebase(None, Some(&upstream), None, Some(&mut RebaseOptions::new()))
+        .with_context(|| format!("Failed to start rebase onto '{onto}'"))?;
+    run(&repo, &mut rebase, 0)
+}
+
+/// Commit the resolved current step of an in-progress rebase and replay the
+/// remaining commits.
+///
+/// Fails if conflicts are still unresolved in the index.
+pub fn continue_rebase(worktree_path: &str) -> Result<RebaseResult> {
+    let repo = open_repo(worktree_path)?;
+    let mut rebase = open_in_progress(&repo)?;
+    let unresolved = conflicted_paths(&repo)?;
+    if !unresolved.is_empty() {
+        anyhow::bail!("Unresolved conflicts remain: {}", unresolved.join(", "));
+    }
+
+    let applied = if rebase.operation_current().is_some() {
+        commit_current(&repo, &mut rebase)?
+    } else {
+        0
+    };
+    run(&repo, &mut rebase, applied)
+}
+
+/// Abort an in-progress rebase and restore the branch to where it was.
+pub fn abort_rebase(worktree_path: &str) -> Result<()> {
+    let repo = open_repo(worktree_path)?;
+    let mut rebase = open_in_progress(&repo)?;
+    rebase.abort().context("Failed to abort rebase")
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn open_in_progress(repo: &Repository) -> Result<Rebase<'_>> {
+    repo.open_rebase(None).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            anyhow::anyhow!("No rebase in progress")
+        } else {
+            anyhow::Error::new(e).context("Failed to open rebase")
+        }
+    })
+}
+
+/// Replay the remaining operations, stopping at the first conflict.
+fn run(repo: &Repository, rebase: &mut Rebase<'_>, mut applied: usize) -> Result<RebaseResult> {
+    let total = rebase.len();
+    while let Some(op) = rebase.next() {
+        let op = op.context("Failed to apply commit during rebase")?;
+        let conflicting_paths = conflicted_paths(repo)?;
+        if !conflicting_paths.is_empty() {
+            return Ok(RebaseResult {
+                status: RebaseStatus::Conflicts,
+                applied,
+                total,
+                stopped_at: Some(op.id().to_string()),
+                conflicting_paths,
+                head: None,
+            });
+        }
+        applied += commit_current(repo, rebase)?;
+    }
+
+    rebase.finish(None).context("Failed to finish rebase")?;
+    let head = repo.head()?.target().map(|oid| oid.to_string());
+    Ok(RebaseResult {
+        status: RebaseStatus::Completed,
+        applied,
+        total,
+        stopped_at: None,
+        conflicting_paths: Vec::new(),
+        head,
+    })
+}
+
+/// Commit the current operation, keeping the original author and message.
+/// Returns 0 when the change is already upstream and the commit is dropped.
+fn commit_current(repo: &Repository, rebase: &mut Rebase<'_>) -> Result<usize> {
+    let signature = repo
+        .signature()
+        .context("user.name and user.email must be set in git config")?;
+    match rebase.commit(None, &signature, None) {
+        Ok(_) => Ok(1),
+        Err(e) if e.code() == ErrorCode::Applied => Ok(0),
+        Err(e) => Err(anyhow::Error::new(e).context("Failed to commit rebased change")),
+    }
+}
+
+/// Tracked paths with staged or unstaged changes.
+pub(crate) fn tracked_changes(repo: &Repository) -> Result<Vec<String>> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut opts))?;
+    Ok(statuses
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{
+        commit_file, commit_file_as, init_repo_with_commit, signature_at,
+    };
+    use std::fs;
+    use std::path::Path;
+
+    /// `main` one commit ahead and `feature` two commits ahead of the shared
+    /// base. With `conflict`, both sides edit hello.txt.
+    fn diverged_repo(conflict: bool) -> (tempfile::TempDir, Repository) {
+        let (dir, repo) = init_repo_with_commit();
+        {
+            let base = repo.head().unwrap().peel_to_commit().unwrap();
+            repo.branch("feature", &base, false).unwrap();
+        }
+
+        commit_file(
+            &repo,
+            "HEAD",
+            "hello.txt",
+            "hello from main\n",
+            "main change",
+        );
+
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let author = signature_at("Author", 1_000);
+        let file = if conflict { "hello.txt" } else { "feature.txt" };
+        commit_file_as(&repo, "HEAD", file, "feature 1\n", "feature 1", &author);
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "other.txt",
+            "feature 2\n",
+            "feature 2",
+            &author,
+        );
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_rebase_onto_completes() {
+        let (dir, repo) = diverged_repo(false);
+        let path = dir.path().to_str().unwrap();
+
+        let result = rebase_onto(path, "main").unwrap();
+        assert_eq!(result.status, RebaseStatus::Completed);
+        assert_eq!((result.applied, result.total), (2, 2));
+
+        let head = repo.head().unwrap();
+        assert_eq!(head.shorthand(), Some("feature"));
+        let main = repo.revparse_single("main").unwrap().id();
+        let tip = head.target().unwrap();
+        assert_eq!(result.head, Some(tip.to_string()));
+        assert!(repo.graph_descendant_of(tip, main).unwrap());
+
+        let commit = repo.find_commit(tip).unwrap();
+        assert_eq!(commit.author().name(), Some("Author"));
+        assert_eq!(commit.message(), Some("feature 2"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "hello from main\n"
+        );
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_rebase_onto_up_to_date() {
+        let (dir, _repo) = init_repo_with_commit();
+        let result = rebase_onto(dir.path().to_str().unwrap(), "main").unwrap();
+        assert_eq!(result.status, RebaseStatus::UpToDate);
+        assert_eq!(result.applied, 0);
+    }
+
+    #[test]
+    fn test_rebase_conflict_then_abort() {
+        let (dir, repo) = diverged_repo(true);
+        let path = dir.path().to_str().unwrap();
+        let before = repo.head().unwrap().target().unwrap();
+
+        let result = rebase_onto(path, "main").unwrap();
+        assert_eq!(result.status, RebaseStatus::Conflicts);
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        assert_eq!(result.applied, 0);
+        assert!(result.stopped_at.is_some());
+        assert_ne!(repo.state(), RepositoryState::Clean);
+
+        assert!(continue_rebase(path)
+            .unwrap_err()
+            .to_string()
+            .contains("hello.txt"));
+
+        abort_rebase(path).unwrap();
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target().unwrap(), before);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+    }
+
+    #[test]
+    fn test_rebase_conflict_then_continue() {
+        let (dir, repo) = diverged_repo(true);
+        let path = dir.path().to_str().unwrap();
+        rebase_onto(path, "main").unwrap();
+
+        fs::write(dir.path().join("hello.txt"), "resolved\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        index.add_path(Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = continue_rebase(path).unwrap();
+        assert_eq!(result.status, RebaseStatus::Completed);
+        assert_eq!(result.applied, 2);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().shorthand(), Some("feature"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "resolved\n"
+        );
+    }
+
+    #[test]
+    fn test_rebase_refuses_dirty_worktree() {
+        let (dir, _repo) = diverged_repo(false);
+        fs::write(dir.path().join("other.txt"), "dirty\n").unwrap();
+        let err = rebase_onto(dir.path().to_str().unwrap(), "main").unwrap_err();
+        assert!(err.to_string().contains("other.txt"));
+    }
+
+    #[test]
+    fn test_continue_and_abort_without_rebase() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        assert!(continue_rebase(path)
+            .unwrap_err()
+            .to_string()
+            .contains("No rebase in progress"));
+        assert!(abort_rebase(path).is_err());
+    }
+}
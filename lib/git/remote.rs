@@ -0,0 +1,507 @@
+use anyhow::{Context, Result};
+use git2::{
+    AutotagOption, BranchType, Cred, CredentialType, FetchOptions, PushOptions, RemoteCallbacks,
+    Repository,
+};
+use std::cell::RefCell;
+
+use super::open_repo;
+use super::remote_url::{RemoteProtocol, RemoteUrl};
+use crate::config;
+
+/// Multi-valued git config key listing GitHub Enterprise hosts that may
+/// receive the GitHub token, e.g. `git config --add reown.githubHost ghe.example.com`.
+pub const GITHUB_HOST_CONFIG: &str = "reown.githubHost";
+
+/// Progress reported while talking to a remote.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum SyncProgress {
+    /// Objects downloaded / indexed during a fetch.
+    Transfer {
+        received_objects: usize,
+        indexed_objects: usize,
+        total_objects: usize,
+        received_bytes: usize,
+    },
+    /// Objects uploaded during a push.
+    Push {
+        current: usize,
+        total: usize,
+        bytes: usize,
+    },
+    /// Text sent by the server ("remote: ..." lines).
+    Message(String),
+}
+
+/// Result of `pull_fast_forward`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum PullOutcome {
+    UpToDate,
+    FastForwarded { from: String, to: String },
+}
+
+/// Fetch `refspecs` from `remote`. An empty slice uses the remote's
+/// configured fetch refspecs.
+pub fn fetch(
+    repo_path: &str,
+    remote: &str,
+    refspecs: &[&str],
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    fetch_with_repo(&repo, remote, refspecs, progress)
+}
+
+/// Fetch the upstream of the checked-out branch and fast-forward to it.
+///
+/// Fails without touching the branch if it has diverged from its upstream
+/// or if the checkout would overwrite local changes.
+pub fn pull_fast_forward(
+    repo_path: &str,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<PullOutcome> {
+    let repo = open_repo(repo_path)?;
+    let head = repo.head().context("Failed to resolve HEAD")?;
+    if !head.is_branch() {
+        anyhow::bail!("HEAD is detached; check out a branch to pull");
+    }
+    let branch_ref = head
+        .name()
+        .context("Branch name is not valid UTF-8")?
+        .to_string();
+    let branch_name = head.shorthand().unwrap_or("").to_string();
+
+    let remote_name = repo
+        .branch_upstream_remote(&branch_ref)
+        .ok()
+        .and_then(|buf| buf.as_str().map(str::to_string))
+        .with_context(|| format!("Branch '{branch_name}' has no upstream"))?;
+    fetch_with_repo(&repo, &remote_name, &[], progress)?;
+
+    let upstream = repo
+        .find_branch(&branch_name, BranchType::Local)?
+        .upstream()
+        .with_context(|| format!("Upstream of '{branch_name}' not found after fetch"))?;
+    let upstream_commit = repo.reference_to_annotated_commit(upstream.get())?;
+
+    let (analysis, _) = repo.merge_analysis(&[&upstream_commit])?;
+    if analysis.is_up_to_date() {
+        return Ok(PullOutcome::UpToDate);
+    }
+    if !analysis.is_fast_forward() {
+        anyhow::bail!("Branch '{branch_name}' has diverged from its upstream; cannot fast-forward");
+    }
+
+    let from = head.target().context("HEAD has no target")?;
+    let to = upstream_commit.id();
+    let target = repo.find_object(to, None)?;
+    repo.checkout_tree(&target, Some(git2::build::CheckoutBuilder::new().safe()))
+        .context("Fast-forward would overwrite local changes")?;
+    repo.find_reference(&branch_ref)?
+        .set_target(to, &format!("pull: fast-forward to {to}"))?;
+
+    Ok(PullOutcome::FastForwarded {
+        from: from.to_string(),
+        to: to.to_string(),
+    })
+}
+
+/// Push local `branch` to the branch of the same name on `remote`.
+/// With `set_upstream`, the remote branch becomes the upstream of `branch`.
+pub fn push(
+    repo_path: &str,
+    remote: &str,
+    branch: &str,
+    set_upstream: bool,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let mut local = repo
+        .find_branch(branch, BranchType::Local)
+        .with_context(|| format!("Branch '{branch}' not found"))?;
+    let mut git_remote = repo
+        .find_remote(remote)
+        .with_context(|| format!("Remote '{remote}' not found"))?;
+
+    let progress = RefCell::new(progress);
+    let rejection: RefCell<Option<String>> = RefCell::new(None);
+    {
+        let mut callbacks = remote_callbacks(&repo, &progress);
+        callbacks.push_transfer_progress(|current, total, bytes| {
+            (progress.borrow_mut())(SyncProgress::Push {
+                current,
+                total,
+                bytes,
+            });
+        });
+        callbacks.push_update_reference(|refname, status| {
+            if let Some(message) = status {
+                *rejection.borrow_mut() = Some(format!("{refname}: {message}"));
+            }
+            Ok(())
+        });
+
+        let mut opts = PushOptions::new();
+        opts.remote_callbacks(callbacks);
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+        git_remote
+            .push(&[refspec.as_str()], Some(&mut opts))
+            .with_context(|| format!("Failed to push '{branch}' to '{remote}'"))?;
+    }
+
+    if let Some(message) = rejection.into_inner() {
+        anyhow::bail!("Push rejected by '{remote}': {message}");
+    }
+
+    if set_upstream {
+        local
+            .set_upstream(Some(&format!("{remote}/{branch}")))
+            .with_context(|| format!("Failed to set upstream of '{branch}'"))?;
+    }
+    Ok(())
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn fetch_with_repo(
+    repo: &Repository,
+    remote: &str,
+    refspecs: &[&str],
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<()> {
+    let mut git_remote = repo
+        .find_remote(remote)
+        .with_context(|| format!("Remote '{remote}' not found"))?;
+
+    let progress = RefCell::new(progress);
+    let mut callbacks = remote_callbacks(repo, &progress);
+    callbacks.transfer_progress(|stats| {
+        (progress.borrow_mut())(SyncProgress::Transfer {
+            received_objects: stats.received_objects(),
+            indexed_objects: stats.indexed_objects(),
+            total_objects: stats.total_objects(),
+            received_bytes: stats.received_bytes(),
+        });
+        true
+    });
+
+    let mut opts = FetchOptions::new();
+    opts.remote_callbacks(callbacks)
+        .download_tags(AutotagOption::Auto);
+    git_remote
+        .fetch(refspecs, Some(&mut opts), None)
+        .with_context(|| format!("Failed to fetch from '{remote}'"))?;
+    Ok(())
+}
+
+/// Callbacks shared by fetch and push: credentials and server messages.
+///
+/// HTTPS remotes on github.com (or a host listed in [`GITHUB_HOST_CONFIG`])
+/// get the GitHub token from the keychain; other hosts never see it. SSH
+/// remotes use the SSH agent. Each method is offered once so that a
+/// rejected credential fails instead of looping.
+fn remote_callbacks<'a>(
+    repo: &Repository,
+    progress: &'a RefCell<&mut dyn FnMut(SyncProgress)>,
+) -> RemoteCallbacks<'a> {
+    let mut callbacks = RemoteCallbacks::new();
+    let enterprise_hosts = github_enterprise_hosts(repo);
+    let mut tried_token = false;
+    let mut tried_agent = false;
+
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT)
+            && !tried_token
+            && offers_github_token(url, &enterprise_hosts)
+        {
+            tried_token = true;
+            if let Ok(token) = config::load_github_token() {
+                return Cred::userpass_plaintext("x-access-token", &token);
+            }
+        }
+        if allowed.contains(CredentialType::SSH_KEY) && !tried_agent {
+            tried_agent = true;
+            return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username_from_url.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::DEFAULT) {
+            return Cred::default();
+        }
+        Err(git2::Error::from_str(
+            "No usable credentials: log in to GitHub or add a key to the SSH agent",
+        ))
+    });
+    callbacks.sideband_progress(move |data| {
+        let text = String::from_utf8_lossy(data).trim().to_string();
+        if !text.is_empty() {
+            (progress.borrow_mut())(SyncProgress::Message(text));
+        }
+        true
+    });
+    callbacks
+}
+
+/// Whether the GitHub token may be sent to `url`: only over HTTPS, and only
+/// to github.com or one of `enterprise_hosts`.
+fn offers_github_token(url: &str, enterprise_hosts: &[String]) -> bool {
+    RemoteUrl::parse(url).is_some_and(|remote| {
+        remote.protocol == RemoteProtocol::Https
+            && (remote.is_github_com()
+                || enterprise_hosts
+                    .iter()
+                    .any(|host| host.eq_ignore_ascii_case(&remote.host)))
+    })
+}
+
+fn github_enterprise_hosts(repo: &Repository) -> Vec<String> {
+    let Ok(config) = repo.config() else {
+        return Vec::new();
+    };
+    let mut hosts = Vec::new();
+    if let Ok(entries) = config.multivar(GITHUB_HOST_CONFIG, None) {
+        let _ = entries.for_each(|entry| {
+            if let Some(host) = entry.value() {
+                hosts.push(host.trim().to_string());
+            }
+        });
+    }
+    hosts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// A working repo with `origin` pointing at a fresh bare repo, and `main`
+    /// already pushed with upstream set.
+    fn init_with_origin() -> (TempDir, Repository, TempDir) {
+        let (dir, repo) = init_repo_with_commit();
+        let bare_dir = TempDir::new().unwrap();
+        let bare = Repository::init_bare(bare_dir.path()).unwrap();
+        bare.set_head("refs/heads/main").unwrap();
+        let url = format!("file://{}", bare_dir.path().display());
+        repo.remote("origin", &url).unwrap();
+        push(
+            dir.path().to_str().unwrap(),
+            "origin",
+            "main",
+            true,
+            &mut |_| {},
+        )
+        .unwrap();
+        (dir, repo, bare_dir)
+    }
+
+    fn clone_of(bare: &TempDir) -> (TempDir, Repository) {
+        let dir = TempDir::new().unwrap();
+        let url = format!("file://{}", bare.path().display());
+        let repo = Repository::clone(&url, dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_push_sets_upstream() {
+        let (_dir, repo, bare_dir) = init_with_origin();
+        let bare = Repository::open_bare(bare_dir.path()).unwrap();
+        let local_tip = repo.head().unwrap().target().unwrap();
+        let remote_tip = bare.find_reference("refs/heads/main").unwrap().target();
+        assert_eq!(remote_tip, Some(local_tip));
+
+        let branch = repo.find_branch("main", BranchType::Local).unwrap();
+        assert_eq!(
+            branch.upstream().unwrap().name().unwrap(),
+            Some("origin/main")
+        );
+    }
+
+    #[test]
+    fn test_push_reports_progress() {
+        let (dir, repo, _bare_dir) = init_with_origin();
+        commit_file(&repo, "HEAD", "hello.txt", "pushed\n", "edit");
+
+        let mut events = Vec::new();
+        push(
+            dir.path().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |p| events.push(p),
+        )
+        .unwrap();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncProgress::Push { .. })));
+    }
+
+    #[test]
+    fn test_push_non_fast_forward_is_rejected() {
+        let (dir, repo, bare_dir) = init_with_origin();
+        let (_other_dir, other) = clone_of(&bare_dir);
+        commit_file(&other, "HEAD", "hello.txt", "from other\n", "edit");
+        push(
+            other.workdir().unwrap().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        commit_file(&repo, "HEAD", "hello.txt", "diverged\n", "edit");
+        let result = push(
+            dir.path().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |_| {},
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fetch_updates_remote_tracking_branch() {
+        let (dir, repo, bare_dir) = init_with_origin();
+        let (_other_dir, other) = clone_of(&bare_dir);
+        let new_tip = commit_file(&other, "HEAD", "hello.txt", "from other\n", "edit");
+        push(
+            other.workdir().unwrap().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let mut events = Vec::new();
+        fetch(dir.path().to_str().unwrap(), "origin", &[], &mut |p| {
+            events.push(p)
+        })
+        .unwrap();
+
+        let tracking = repo.find_reference("refs/remotes/origin/main").unwrap();
+        assert_eq!(tracking.target(), Some(new_tip));
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SyncProgress::Transfer { .. })));
+    }
+
+    #[test]
+    fn test_fetch_unknown_remote() {
+        let (dir, _repo) = init_repo_with_commit();
+        let result = fetch(dir.path().to_str().unwrap(), "nope", &[], &mut |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pull_fast_forward() {
+        let (dir, repo, bare_dir) = init_with_origin();
+        let path = dir.path().to_str().unwrap();
+        assert_eq!(
+            pull_fast_forward(path, &mut |_| {}).unwrap(),
+            PullOutcome::UpToDate
+        );
+
+        let (_other_dir, other) = clone_of(&bare_dir);
+        let new_tip = commit_file(&other, "HEAD", "hello.txt", "from other\n", "edit");
+        push(
+            other.workdir().unwrap().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |_| {},
+        )
+        .unwrap();
+
+        let old_tip = repo.head().unwrap().target().unwrap();
+        let outcome = pull_fast_forward(path, &mut |_| {}).unwrap();
+        assert_eq!(
+            outcome,
+            PullOutcome::FastForwarded {
+                from: old_tip.to_string(),
+                to: new_tip.to_string(),
+            }
+        );
+        assert_eq!(repo.head().unwrap().target(), Some(new_tip));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "from other\n"
+        );
+    }
+
+    #[test]
+    fn test_pull_diverged_fails() {
+        let (dir, repo, bare_dir) = init_with_origin();
+        let (_other_dir, other) = clone_of(&bare_dir);
+        commit_file(&other, "HEAD", "other.txt", "other\n", "edit");
+        push(
+            other.workdir().unwrap().to_str().unwrap(),
+            "origin",
+            "main",
+            false,
+            &mut |_| {},
+        )
+        .unwrap();
+        let local_tip = commit_file(&repo, "HEAD", "local.txt", "local\n", "edit");
+
+        let result = pull_fast_forward(dir.path().to_str().unwrap(), &mut |_| {});
+        assert!(result.unwrap_err().to_string().contains("diverged"));
+        assert_eq!(repo.head().unwrap().target(), Some(local_tip));
+    }
+
+    #[test]
+    fn test_pull_without_upstream() {
+        let (dir, _repo) = init_repo_with_commit();
+        let result = pull_fast_forward(dir.path().to_str().unwrap(), &mut |_| {});
+        assert!(result.unwrap_err().to_string().contains("no upstream"));
+    }
+
+    #[test]
+    fn test_github_token_only_offered_to_github_hosts() {
+        let ghe = vec!["ghe.example.com".to_string()];
+        assert!(offers_github_token(
+            "https://github.com/owner/repo.git",
+            &ghe
+        ));
+        assert!(offers_github_token(
+            "https://GHE.example.com/team/service.git",
+            &ghe
+        ));
+
+        for url in [
+            "file:///srv/git/owner/repo.git",
+            "/srv/git/owner/repo.git",
+            "https://gitlab.com/owner/repo.git",
+            "https://github.com.evil.example/owner/repo.git",
+            "https://mirror.example.com/owner/repo.git",
+            "http://github.com/owner/repo.git",
+        ] {
+            assert!(!offers_github_token(url, &ghe), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_github_enterprise_hosts_from_git_config() {
+        let (_dir, repo) = init_repo_with_commit();
+        assert!(github_enterprise_hosts(&repo).is_empty());
+
+        let mut config = repo.config().unwrap();
+        config
+            .set_multivar(GITHUB_HOST_CONFIG, "^$", "ghe.example.com")
+            .unwrap();
+        config
+            .set_multivar(GITHUB_HOST_CONFIG, "^$", "git.corp.example")
+            .unwrap();
+        assert_eq!(
+            github_enterprise_hosts(&repo),
+            vec!["ghe.example.com", "git.corp.example"]
+        );
+    }
+}
@@ -0,0 +1,692 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use git2::{build::CheckoutBuilder, ApplyLocation, Repository};
+
+use super::diff::{
+    collect_diff, DiffChunk, DiffLineInfo, FileDiff, FileKind, FileStatus, LineOrigin,
+};
+use super::open_repo;
+
+/// A hunk (or part of one) to stage, unstage or discard.
+///
+/// `header` is the `DiffChunk::header` of the hunk as returned by
+/// `diff_workdir_grouped` with default diff options. The selection is
+/// rejected if the hunk no longer exists, e.g. because the file changed
+/// after the diff was displayed.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct HunkSelection {
+    pub path: String,
+    pub header: String,
+    /// Changed lines to include. `None` selects the whole hunk.
+    #[serde(default)]
+    pub lines: Option<Vec<LineSelection>>,
+}
+
+/// Identifies one added or deleted line by the line numbers
+/// `DiffLineInfo` reports for it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LineSelection {
+    pub old_lineno: Option<u32>,
+    pub new_lineno: Option<u32>,
+}
+
+impl LineSelection {
+    fn matches(&self, line: &DiffLineInfo) -> bool {
+        self.old_lineno == line.old_lineno && self.new_lineno == line.new_lineno
+    }
+}
+
+/// Stage the selected workdir changes into the index.
+pub fn stage_hunks(repo_path: &str, selections: &[HunkSelection]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let files = unstaged_files(&repo)?;
+    apply_selections(
+        &repo,
+        &files,
+        selections,
+        Direction::Forward,
+        ApplyLocation::Index,
+    )
+}
+
+/// Remove the selected staged changes from the index, keeping them in the
+/// working directory.
+pub fn unstage_hunks(repo_path: &str, selections: &[HunkSelection]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let files = staged_files(&repo)?;
+    apply_selections(
+        &repo,
+        &files,
+        selections,
+        Direction::Reverse,
+        ApplyLocation::Index,
+    )
+}
+
+/// Revert the selected unstaged changes in the working directory.
+/// Discarded changes cannot be recovered.
+pub fn discard_hunks(repo_path: &str, selections: &[HunkSelection]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let files = unstaged_files(&repo)?;
+    apply_selections(
+        &repo,
+        &files,
+        selections,
+        Direction::Reverse,
+        ApplyLocation::WorkDir,
+    )
+}
+
+/// Stage whole files. New and modified files (including untracked ones) are
+/// added to the index; files missing from the working directory are removed
+/// from it. Works for any kind of file, unlike `stage_hunks`.
+pub fn stage_files(repo_path: &str, paths: &[String]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let mut index = repo.index().context("Failed to read index")?;
+
+    for path in paths {
+        let rel = Path::new(path);
+        let result = if workdir.join(rel).symlink_metadata().is_ok() {
+            index.add_path(rel)
+        } else if index.get_path(rel, 0).is_some() {
+            index.remove_path(rel)
+        } else {
+            anyhow::bail!("No changes to stage in '{path}'");
+        };
+        result.with_context(|| format!("Failed to stage '{path}'"))?;
+    }
+    index.write().context("Failed to write index")
+}
+
+/// Unstage whole files by resetting their index entries to HEAD. Files that
+/// were added in the index become untracked again; the working directory is
+/// left alone.
+pub fn unstage_files(repo_path: &str, paths: &[String]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let head = repo.head().and_then(|h| h.peel_to_commit());
+    match head {
+        Ok(head) => repo
+            .reset_default(Some(head.as_object()), paths.iter().map(String::as_str))
+            .context("Failed to unstage files"),
+        Err(_) => {
+            // Unborn repo: everything in the index is an addition
+            let mut index = repo.index().context("Failed to read index")?;
+            for path in paths {
+                index
+                    .remove_path(Path::new(path))
+                    .with_context(|| format!("Failed to unstage '{path}'"))?;
+            }
+            index.write().context("Failed to write index")
+        }
+    }
+}
+
+/// Discard the unstaged changes of whole files. Tracked files are restored
+/// from the index and untracked files are deleted. Discarded changes cannot
+/// be recovered.
+pub fn discard_files(repo_path: &str, paths: &[String]) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let workdir = repo
+        .workdir()
+        .context("Repository has no working directory")?;
+    let mut index = repo.index().context("Failed to read index")?;
+
+    let mut tracked = Vec::new();
+    let mut untracked = Vec::new();
+    for path in paths {
+        let rel = Path::new(path);
+        if index.get_path(rel, 0).is_some() {
+            tracked.push(path.as_str());
+        } else if workdir.join(rel).symlink_metadata().is_ok() {
+            untracked.push(workdir.join(rel));
+        } else {
+            anyhow::bail!("No changes to discard in '{path}'");
+        }
+    }
+
+    if !tracked.is_empty() {
+        let mut checkout = CheckoutBuilder::new();
+        checkout.force().disable_pathspec_match(true);
+        for path in &tracked {
+            checkout.path(path);
+        }
+        repo.checkout_index(Some(&mut index), Some(&mut checkout))
+            .context("Failed to discard changes")?;
+    }
+    for path in untracked {
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to delete '{}'", path.display()))?;
+    }
+    Ok(())
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+/// Whether the patch moves the target from the old side to the new side of
+/// the source diff, or back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Forward,
+    Reverse,
+}
+
+fn unstaged_files(repo: &Repository) -> Result<Vec<FileDiff>> {
+    let diff = repo
+        .diff_index_to_workdir(None, None)
+        .context("Failed to compute unstaged diff")?;
+    collect_diff(repo, &diff)
+}
+
+fn staged_files(repo: &Repository) -> Result<Vec<FileDiff>> {
+    let head_tree = match repo.head() {
+        Ok(head) => Some(head.peel_to_tree()?),
+        Err(_) => None, // unborn repo
+    };
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), None, None)
+        .context("Failed to compute staged diff")?;
+    collect_diff(repo, &diff)
+}
+
+fn apply_selections(
+    repo: &Repository,
+    files: &[FileDiff],
+    selections: &[HunkSelection],
+    direction: Direction,
+    location: ApplyLocation,
+) -> Result<()> {
+    for selection in selections {
+        let known = files
+            .iter()
+            .any(|f| f.new_path.as_deref().or(f.old_path.as_deref()) == Some(&selection.path));
+        if !known {
+            anyhow::bail!("No changes to apply in '{}'", selection.path);
+        }
+    }
+
+    let mut patch = String::new();
+    for file in files {
+        let path = match file.new_path.as_deref().or(file.old_path.as_deref()) {
+            Some(p) => p,
+            None => continue,
+        };
+        let file_selections: Vec<&HunkSelection> =
+            selections.iter().filter(|s| s.path == path).collect();
+        if file_selections.is_empty() {
+            continue;
+        }
+        if file.status != FileStatus::Modified || file.kind != FileKind::Text {
+            anyhow::bail!(
+                "Partial staging is only supported for modified text files; \
+                 stage, unstage or discard the whole file instead: '{path}'"
+            );
+        }
+        for selection in &file_selections {
+            if !file.chunks.iter().any(|c| c.header == selection.header) {
+                anyhow::bail!("Hunk '{}' not found in '{path}'", selection.header);
+            }
+        }
+
+        let mut hunks = String::new();
+        let mut offset: i64 = 0;
+        for chunk in &file.chunks {
+            let Some(selection) = file_selections.iter().find(|s| s.header == chunk.header) else {
+                continue;
+            };
+            if let Some(hunk) =
+                build_hunk(chunk, selection.lines.as_deref(), direction, &mut offset)?
+            {
+                hunks.push_str(&hunk);
+            }
+        }
+
+        if !hunks.is_empty() {
+            let (old, new) = (header_path("a/", path), header_path("b/", path));
+            patch.push_str(&format!("diff --git {old} {new}\n--- {old}\n+++ {new}\n"));
+            patch.push_str(&hunks);
+        }
+    }
+
+    if patch.is_empty() {
+        return Ok(());
+    }
+
+    let diff = git2::Diff::from_buffer(patch.as_bytes()).context("Failed to build patch")?;
+    repo.apply(&diff, location, None)
+        .context("Failed to apply selected changes")?;
+    Ok(())
+}
+
+/// Render one hunk of the patch, keeping only the selected changes.
+///
+/// The patch is applied to the side of the source diff it starts from
+/// (old side for `Forward`, new side for `Reverse`), so that side's lines
+/// are all kept: unselected changes that exist there become context, and
+/// unselected changes that don't are dropped. `offset` carries the line
+/// shift caused by earlier hunks of the same file.
+/// Returns `None` when no change in the hunk is selected.
+fn build_hunk(
+    chunk: &DiffChunk,
+    lines: Option<&[LineSelection]>,
+    direction: Direction,
+    offset: &mut i64,
+) -> Result<Option<String>> {
+    let (old_start, new_start) = parse_hunk_starts(&chunk.header)
+        .with_context(|| format!("Invalid hunk header '{}'", chunk.header))?;
+    let pre_start = match direction {
+        Direction::Forward => old_start,
+        Direction::Reverse => new_start,
+    };
+
+    let mut body = String::new();
+    let (mut pre_count, mut post_count) = (0i64, 0i64);
+    let mut has_change = false;
+    let mut last_emitted = false;
+
+    for line in &chunk.lines {
+        let selected = lines.is_none_or(|sel| sel.iter().any(|s| s.matches(line)));
+        let marker = match (&line.origin, direction) {
+            (LineOrigin::Context, _) => Some(' '),
+            (LineOrigin::Addition, Direction::Forward) => selected.then_some('+'),
+            (LineOrigin::Deletion, Direction::Forward) => Some(if selected { '-' } else { ' ' }),
+            (LineOrigin::Addition, Direction::Reverse) => Some(if selected { '-' } else { ' ' }),
+            (LineOrigin::Deletion, Direction::Reverse) => selected.then_some('+'),
+            (LineOrigin::Other(_), _) => {
+                // "\ No newline at end of file" belongs to the previous line
+                if last_emitted {
+                    body.push_str("\\ No newline at end of file\n");
+                }
+                continue;
+            }
+        };
+
+        let Some(marker) = marker else {
+            last_emitted = false;
+            continue;
+        };
+        match marker {
+            ' ' => {
+                pre_count += 1;
+                post_count += 1;
+            }
+            '-' => {
+                pre_count += 1;
+                has_change = true;
+            }
+            _ => {
+                post_count += 1;
+                has_change = true;
+            }
+        }
+        body.push(marker);
+        body.push_str(&line.content);
+        if !line.content.ends_with('\n') {
+            body.push('\n');
+        }
+        last_emitted = true;
+    }
+
+    if !has_change {
+        return Ok(None);
+    }
+
+    // A zero-length side points at the line before the hunk
+    let pre_first = if pre_count == 0 {
+        pre_start + 1
+    } else {
+        pre_start
+    };
+    let post_first = pre_first + *offset;
+    let post_start = if post_count == 0 {
+        post_first - 1
+    } else {
+        post_first
+    };
+    *offset += post_count - pre_count;
+
+    Ok(Some(format!(
+        "@@ -{pre_start},{pre_count} +{post_start},{post_count} @@\n{body}"
+    )))
+}
+
+/// `prefix` + `path` as written in a patch header, C-quoted like git does
+/// when the path contains spaces, quotes, control or non-ASCII characters.
+fn header_path(prefix: &str, path: &str) -> String {
+    let full = format!("{prefix}{path}");
+    let needs_quoting = full
+        .bytes()
+        .any(|b| matches!(b, b' ' | b'"' | b'\\') || !(0x20..0x7f).contains(&b));
+    if !needs_quoting {
+        return full;
+    }
+
+    let mut quoted = String::from("\"");
+    for b in full.bytes() {
+        match b {
+            b'"' => quoted.push_str("\\\""),
+            b'\\' => quoted.push_str("\\\\"),
+            b'\n' => quoted.push_str("\\n"),
+            b'\t' => quoted.push_str("\\t"),
+            0x20..=0x7e => quoted.push(b as char),
+            _ => quoted.push_str(&format!("\\{b:03o}")),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Parse the old and new start lines from `@@ -a,b +c,d @@`.
+fn parse_hunk_starts(header: &str) -> Option<(i64, i64)> {
+    let ranges = header.strip_prefix("@@ -")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(" +")?;
+    let start = |range: &str| range.split(',').next()?.parse::<i64>().ok();
+    Some((start(old)?, start(new)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{diff_workdir_grouped, DiffRequest, WorkdirChanges};
+    use crate::git::test_utils::{commit_file, init_test_repo};
+    use std::fs;
+
+    const ORIGINAL: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\n";
+
+    /// Commit `ORIGINAL` as `file.txt`, then write `modified` to the workdir.
+    fn setup(modified: &str) -> (tempfile::TempDir, git2::Repository) {
+        let (dir, repo) = init_test_repo();
+        commit_file(&repo, "HEAD", "file.txt", ORIGINAL, "add file");
+        fs::write(dir.path().join("file.txt"), modified).unwrap();
+        (dir, repo)
+    }
+
+    fn paths(files: &[FileDiff]) -> Vec<(String, FileStatus)> {
+        files
+            .iter()
+            .map(|f| {
+                let path = f.new_path.clone().or_else(|| f.old_path.clone());
+                (path.unwrap(), f.status.clone())
+            })
+            .collect()
+    }
+
+    fn changes(dir: &tempfile::TempDir) -> WorkdirChanges {
+        diff_workdir_grouped(dir.path().to_str().unwrap(), &DiffRequest::default()).unwrap()
+    }
+
+    fn index_content(repo: &git2::Repository) -> String {
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        let entry = index.get_path(std::path::Path::new("file.txt"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        String::from_utf8(blob.content().to_vec()).unwrap()
+    }
+
+    fn whole_hunk(header: &str) -> HunkSelection {
+        HunkSelection {
+            path: "file.txt".to_string(),
+            header: header.to_string(),
+            lines: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_hunk_starts() {
+        assert_eq!(parse_hunk_starts("@@ -1,3 +2,4 @@ fn main"), Some((1, 2)));
+        assert_eq!(parse_hunk_starts("@@ -5 +5 @@"), Some((5, 5)));
+        assert_eq!(parse_hunk_starts("not a header"), None);
+    }
+
+    #[test]
+    fn test_stage_single_hunk() {
+        let modified = ORIGINAL.replace("b\n", "B\n").replace("m\n", "M\n");
+        let (dir, repo) = setup(&modified);
+        let unstaged = changes(&dir).unstaged;
+        assert_eq!(unstaged[0].chunks.len(), 2);
+
+        let second = unstaged[0].chunks[1].header.clone();
+        stage_hunks(dir.path().to_str().unwrap(), &[whole_hunk(&second)]).unwrap();
+
+        assert_eq!(index_content(&repo), ORIGINAL.replace("m\n", "M\n"));
+        let after = changes(&dir);
+        assert_eq!(after.staged[0].chunks.len(), 1);
+        assert_eq!(after.unstaged[0].chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_stage_selected_lines() {
+        let modified = ORIGINAL.replace("b\n", "B\nx\n");
+        let (dir, repo) = setup(&modified);
+        let chunk = changes(&dir).unstaged[0].chunks[0].clone();
+        let added_x = chunk
+            .lines
+            .iter()
+            .find(|l| l.origin == LineOrigin::Addition && l.content == "x\n")
+            .unwrap();
+
+        let selection = HunkSelection {
+            path: "file.txt".to_string(),
+            header: chunk.header.clone(),
+            lines: Some(vec![LineSelection {
+                old_lineno: added_x.old_lineno,
+                new_lineno: added_x.new_lineno,
+            }]),
+        };
+        stage_hunks(dir.path().to_str().unwrap(), &[selection]).unwrap();
+
+        // Only the "x" line is staged; "b" -> "B" stays in the workdir
+        assert_eq!(index_content(&repo), ORIGINAL.replace("b\n", "b\nx\n"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            modified
+        );
+    }
+
+    #[test]
+    fn test_stage_second_hunk_after_partial_first() {
+        // The first hunk adds lines, so the second hunk's new start must be
+        // recomputed when only part of the first hunk is staged.
+        let modified = ORIGINAL.replace("b\n", "b\nx\ny\n").replace("m\n", "M\n");
+        let (dir, repo) = setup(&modified);
+        let file = changes(&dir).unstaged.remove(0);
+        let first = &file.chunks[0];
+        let y = first.lines.iter().find(|l| l.content == "y\n").unwrap();
+
+        let selections = vec![
+            HunkSelection {
+                path: "file.txt".to_string(),
+                header: first.header.clone(),
+                lines: Some(vec![LineSelection {
+                    old_lineno: y.old_lineno,
+                    new_lineno: y.new_lineno,
+                }]),
+            },
+            whole_hunk(&file.chunks[1].header),
+        ];
+        stage_hunks(dir.path().to_str().unwrap(), &selections).unwrap();
+
+        assert_eq!(
+            index_content(&repo),
+            ORIGINAL.replace("b\n", "b\ny\n").replace("m\n", "M\n")
+        );
+    }
+
+    #[test]
+    fn test_unstage_hunk() {
+        let modified = ORIGINAL.replace("b\n", "B\n").replace("m\n", "M\n");
+        let (dir, repo) = setup(&modified);
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+
+        let staged = changes(&dir).staged;
+        let first = staged[0].chunks[0].header.clone();
+        unstage_hunks(dir.path().to_str().unwrap(), &[whole_hunk(&first)]).unwrap();
+
+        assert_eq!(index_content(&repo), ORIGINAL.replace("m\n", "M\n"));
+        assert_eq!(
+            fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            modified
+        );
+    }
+
+    #[test]
+    fn test_unstage_selected_deletion() {
+        let modified = ORIGINAL.replace("b\nc\n", "");
+        let (dir, repo) = setup(&modified);
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+
+        let chunk = changes(&dir).staged[0].chunks[0].clone();
+        let deleted_c = chunk.lines.iter().find(|l| l.content == "c\n").unwrap();
+        let selection = HunkSelection {
+            path: "file.txt".to_string(),
+            header: chunk.header.clone(),
+            lines: Some(vec![LineSelection {
+                old_lineno: deleted_c.old_lineno,
+                new_lineno: deleted_c.new_lineno,
+            }]),
+        };
+        unstage_hunks(dir.path().to_str().unwrap(), &[selection]).unwrap();
+
+        // Deleting "c" is unstaged, deleting "b" stays staged
+        assert_eq!(index_content(&repo), ORIGINAL.replace("b\n", ""));
+    }
+
+    #[test]
+    fn test_discard_hunk() {
+        let modified = ORIGINAL.replace("b\n", "B\n").replace("m\n", "M\n");
+        let (dir, _repo) = setup(&modified);
+        let unstaged = changes(&dir).unstaged;
+        let first = unstaged[0].chunks[0].header.clone();
+
+        discard_hunks(dir.path().to_str().unwrap(), &[whole_hunk(&first)]).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            ORIGINAL.replace("m\n", "M\n")
+        );
+    }
+
+    #[test]
+    fn test_stale_hunk_header_is_rejected() {
+        let modified = ORIGINAL.replace("b\n", "B\n");
+        let (dir, _repo) = setup(&modified);
+        let result = stage_hunks(
+            dir.path().to_str().unwrap(),
+            &[whole_hunk("@@ -40,3 +40,3 @@")],
+        );
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
+    #[test]
+    fn test_unknown_path_is_rejected() {
+        let (dir, _repo) = setup(ORIGINAL);
+        let result = stage_hunks(
+            dir.path().to_str().unwrap(),
+            &[whole_hunk("@@ -1,3 +1,3 @@")],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_header_path_quoting() {
+        assert_eq!(header_path("a/", "src/lib.rs"), "a/src/lib.rs");
+        assert_eq!(header_path("a/", "my file.txt"), "\"a/my file.txt\"");
+        assert_eq!(
+            header_path("b/", "caf\u{e9}.txt"),
+            "\"b/caf\\303\\251.txt\""
+        );
+        assert_eq!(header_path("a/", "say \"hi\""), "\"a/say \\\"hi\\\"\"");
+    }
+
+    #[test]
+    fn test_stage_hunk_in_quoted_path() {
+        for name in ["my file.txt", "caf\u{e9}.txt"] {
+            let (dir, repo) = init_test_repo();
+            commit_file(&repo, "HEAD", name, ORIGINAL, "add file");
+            fs::write(dir.path().join(name), ORIGINAL.replace("b\n", "B\n")).unwrap();
+
+            let header = changes(&dir).unstaged[0].chunks[0].header.clone();
+            let selection = HunkSelection {
+                path: name.to_string(),
+                header,
+                lines: None,
+            };
+            stage_hunks(dir.path().to_str().unwrap(), &[selection]).unwrap();
+
+            let mut index = repo.index().unwrap();
+            index.read(true).unwrap();
+            let entry = index.get_path(Path::new(name), 0).unwrap();
+            let blob = repo.find_blob(entry.id).unwrap();
+            assert_eq!(blob.content(), ORIGINAL.replace("b\n", "B\n").as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_stage_and_unstage_whole_files() {
+        let (dir, repo) = setup(&ORIGINAL.replace("b\n", "B\n"));
+        commit_file(&repo, "HEAD", "gone.txt", "bye\n", "add file");
+        fs::remove_file(dir.path().join("gone.txt")).unwrap();
+        fs::write(dir.path().join("new file.bin"), [0u8, 1, 2]).unwrap();
+        let path = dir.path().to_str().unwrap();
+        let all = ["file.txt", "gone.txt", "new file.bin"].map(String::from);
+
+        stage_files(path, &all).unwrap();
+        let staged = changes(&dir);
+        assert!(staged.unstaged.is_empty());
+        assert!(staged.untracked.is_empty());
+        assert_eq!(
+            paths(&staged.staged),
+            vec![
+                ("file.txt".to_string(), FileStatus::Modified),
+                ("gone.txt".to_string(), FileStatus::Deleted),
+                ("new file.bin".to_string(), FileStatus::Added),
+            ]
+        );
+
+        unstage_files(path, &all).unwrap();
+        let unstaged = changes(&dir);
+        assert!(unstaged.staged.is_empty());
+        assert_eq!(unstaged.unstaged.len(), 2);
+        assert_eq!(unstaged.untracked.len(), 1);
+    }
+
+    #[test]
+    fn test_discard_whole_files() {
+        let (dir, repo) = setup(&ORIGINAL.replace("b\n", "B\n"));
+        commit_file(&repo, "HEAD", "gone.txt", "bye\n", "add file");
+        fs::remove_file(dir.path().join("gone.txt")).unwrap();
+        fs::write(dir.path().join("scratch.txt"), "tmp\n").unwrap();
+        let all = ["file.txt", "gone.txt", "scratch.txt"].map(String::from);
+
+        discard_files(dir.path().to_str().unwrap(), &all).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("file.txt")).unwrap(),
+            ORIGINAL
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("gone.txt")).unwrap(),
+            "bye\n"
+        );
+        assert!(!dir.path().join("scratch.txt").exists());
+        let after = changes(&dir);
+        assert!(after.unstaged.is_empty() && after.untracked.is_empty());
+    }
+
+    #[test]
+    fn test_whole_file_operations_reject_unknown_paths() {
+        let (dir, _repo) = setup(ORIGINAL);
+        let path = dir.path().to_str().unwrap();
+        let missing = ["missing.txt".to_string()];
+        assert!(stage_files(path, &missing).is_err());
+        assert!(discard_files(path, &missing).is_err());
+    }
+}
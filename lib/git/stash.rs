@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use git2::{ErrorCode, Oid, Repository, StashApplyOptions, StashFlags};
+
+use super::open_repo;
+
+/// One entry of the stash list; `index` 0 is the most recent.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct StashEntry {
+    pub index: usize,
+    pub message: String,
+    pub oid: String,
+    /// Time the stash was created, as a Unix timestamp.
+    pub time: i64,
+}
+
+/// Result of applying or popping a stash.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct StashApplyResult {
+    /// The stash was written to the worktree. `false` when it would have
+    /// overwritten local changes; nothing is touched then.
+    pub applied: bool,
+    /// Paths that blocked the apply, or that were left with conflict
+    /// markers when `applied` is true.
+    pub conflicting_paths: Vec<String>,
+}
+
+/// Stash the worktree and index changes at `repo_path`.
+///
+/// Returns `None` when there is nothing to stash.
+pub fn stash_save(
+    repo_path: &str,
+    message: Option<&str>,
+    include_untracked: bool,
+) -> Result<Option<String>> {
+    let mut repo = open_repo(repo_path)?;
+    Ok(save(&mut repo, message, include_untracked)?.map(|oid| oid.to_string()))
+}
+
+/// List stash entries, most recent first.
+pub fn list_stashes(repo_path: &str) -> Result<Vec<StashEntry>> {
+    let mut repo = open_repo(repo_path)?;
+
+    let mut raw = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        raw.push((index, message.to_string(), *oid));
+        true
+    })
+    .context("Failed to read stash list")?;
+
+    raw.into_iter()
+        .map(|(index, message, oid)| {
+            let time = repo.find_commit(oid)?.time().seconds();
+            Ok(StashEntry {
+                index,
+                message,
+                oid: oid.to_string(),
+                time,
+            })
+        })
+        .collect()
+}
+
+/// Apply the stash at `index`, keeping it in the stash list.
+pub fn stash_apply(repo_path: &str, index: usize) -> Result<StashApplyResult> {
+    let mut repo = open_repo(repo_path)?;
+    apply(&mut repo, index, false)
+}
+
+/// Apply the stash at `index` and drop it if it applied without conflicts.
+pub fn stash_pop(repo_path: &str, index: usize) -> Result<StashApplyResult> {
+    let mut repo = open_repo(repo_path)?;
+    apply(&mut repo, index, true)
+}
+
+/// Remove the stash at `index` without applying it.
+pub fn stash_drop(repo_path: &str, index: usize) -> Result<()> {
+    let mut repo = open_repo(repo_path)?;
+    repo.stash_drop(index)
+        .with_context(|| format!("Failed to drop stash@{{{index}}}"))
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+pub(crate) fn save(
+    repo: &mut Repository,
+    message: Option<&str>,
+    include_untracked: bool,
+) -> Result<Option<Oid>> {
+    let signature = repo
+        .signature()
+        .context("user.name and user.email must be set in git config")?;
+    let flags = if include_untracked {
+        StashFlags::INCLUDE_UNTRACKED
+    } else {
+        StashFlags::DEFAULT
+    };
+    match repo.stash_save2(&signature, message, Some(flags)) {
+        Ok(oid) => Ok(Some(oid)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e).context("Failed to stash changes"),
+    }
+}
+
+pub(crate) fn apply(repo: &mut Repository, index: usize, pop: bool) -> Result<StashApplyResult> {
+    let mut blocking_paths = Vec::new();
+    let outcome = {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout
+            .safe()
+            .notify_on(git2::CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path.and_then(|p| p.to_str()) {
+                    blocking_paths.push(path.to_string());
+                }
+                true
+            });
+        let mut opts = StashApplyOptions::new();
+        opts.checkout_options(checkout);
+        // Applied and dropped separately: libgit2 drops a popped stash even
+        // when it left conflict markers behind, git keeps it.
+        repo.stash_apply(index, Some(&mut opts))
+    };
+
+    match outcome {
+        Ok(()) => {}
+        Err(e) if e.code() == ErrorCode::Conflict || !blocking_paths.is_empty() => {
+            return Ok(StashApplyResult {
+                applied: false,
+                conflicting_paths: blocking_paths,
+            });
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to apply stash@{{{index}}}")),
+    }
+
+    let conflicting_paths = conflicted_paths(repo)?;
+    if pop && conflicting_paths.is_empty() {
+        repo.stash_drop(index)
+            .with_context(|| format!("Failed to drop stash@{{{index}}}"))?;
+    }
+    Ok(StashApplyResult {
+        applied: true,
+        conflicting_paths,
+    })
+}
+
+pub(crate) fn conflicted_paths(repo: &Repository) -> Result<Vec<String>> {
+    let index = repo.index().context("Failed to read index")?;
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).into_owned());
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
+    use std::fs;
+
+    #[test]
+    fn test_stash_save_and_list() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        assert_eq!(stash_save(path, None, false).unwrap(), None);
+
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        let oid = stash_save(path, Some("wip"), false).unwrap().unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "hello\n"
+        );
+
+        let stashes = list_stashes(path).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].index, 0);
+        assert_eq!(stashes[0].oid, oid);
+        assert!(stashes[0].message.contains("wip"));
+    }
+
+    #[test]
+    fn test_stash_untracked() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+        // Untracked files alone are nothing to stash unless asked for
+        assert_eq!(stash_save(path, None, false).unwrap(), None);
+        assert!(stash_save(path, None, true).unwrap().is_some());
+        assert!(!dir.path().join("new.txt").exists());
+
+        let result = stash_pop(path, 0).unwrap();
+        assert!(result.applied);
+        assert!(dir.path().join("new.txt").exists());
+    }
+
+    #[test]
+    fn test_stash_apply_keeps_entry_pop_removes_it() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("hello.txt"), "changed\n").unwrap();
+        stash_save(path, None, false).unwrap();
+
+        assert!(stash_apply(path, 0).unwrap().applied);
+        assert_eq!(list_stashes(path).unwrap().len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "changed\n"
+        );
+
+        fs::write(dir.path().join("hello.txt"), "hello\n").unwrap();
+        assert!(stash_pop(path, 0).unwrap().applied);
+        assert!(list_stashes(path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stash_apply_conflict_reports_paths() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("hello.txt"), "stashed\n").unwrap();
+        stash_save(path, None, false).unwrap();
+        fs::write(dir.path().join("hello.txt"), "local\n").unwrap();
+
+        let result = stash_pop(path, 0).unwrap();
+        assert!(!result.applied);
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        // The stash is kept and local changes untouched
+        assert_eq!(list_stashes(path).unwrap().len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("hello.txt")).unwrap(),
+            "local\n"
+        );
+    }
+
+    #[test]
+    fn test_stash_pop_with_merge_conflict_keeps_entry() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("hello.txt"), "stashed\n").unwrap();
+        stash_save(path, None, false).unwrap();
+
+        // HEAD moves on with a conflicting change to the same line
+        commit_file(&repo, "HEAD", "hello.txt", "committed\n", "edit");
+
+        let result = stash_pop(path, 0).unwrap();
+        assert!(result.applied);
+        assert_eq!(result.conflicting_paths, vec!["hello.txt"]);
+        assert_eq!(list_stashes(path).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_stash_drop() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        fs::write(dir.path().join("hello.txt"), "one\n").unwrap();
+        stash_save(path, Some("one"), false).unwrap();
+        fs::write(dir.path().join("hello.txt"), "two\n").unwrap();
+        stash_save(path, Some("two"), false).unwrap();
+
+        stash_drop(path, 1).unwrap();
+        let stashes = list_stashes(path).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.contains("two"));
+        assert!(stash_drop(path, 5).is_err());
+    }
+}
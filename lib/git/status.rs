@@ -0,0 +1,263 @@
+use anyhow::Result;
+use git2::{BranchType, Repository, RepositoryState, Status, StatusOptions};
+
+use super::branch::{default_branch, AheadBehind};
+use super::worktree::{list_worktrees, WorktreeInfo};
+
+/// Status summary of one worktree for the dashboard.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct WorktreeStatus {
+    #[serde(flatten)]
+    pub worktree: WorktreeInfo,
+    /// The worktree directory could not be opened (deleted or moved).
+    pub missing: bool,
+    /// Files with changes in the index.
+    pub staged: usize,
+    /// Tracked files with changes not yet staged.
+    pub dirty: usize,
+    pub untracked: usize,
+    /// Files with unresolved merge conflicts.
+    pub conflicted: usize,
+    /// Against the branch's upstream. `None` without an upstream.
+    pub upstream: Option<AheadBehind>,
+    /// Name of the default branch compared against, e.g. `origin/main`.
+    pub default_branch: Option<String>,
+    pub against_default: Option<AheadBehind>,
+    /// Commit time of HEAD as a Unix timestamp.
+    pub last_commit_time: Option<i64>,
+    pub merge_in_progress: bool,
+    pub rebase_in_progress: bool,
+}
+
+/// Status of every worktree returned by `list_worktrees`.
+pub fn list_worktree_statuses(repo_path: &str) -> Result<Vec<WorktreeStatus>> {
+    list_worktrees(repo_path)?
+        .into_iter()
+        .map(worktree_status)
+        .collect()
+}
+
+fn worktree_status(worktree: WorktreeInfo) -> Result<WorktreeStatus> {
+    let mut status = WorktreeStatus {
+        worktree,
+        missing: false,
+        staged: 0,
+        dirty: 0,
+        untracked: 0,
+        conflicted: 0,
+        upstream: None,
+        default_branch: None,
+        against_default: None,
+        last_commit_time: None,
+        merge_in_progress: false,
+        rebase_in_progress: false,
+    };
+
+    let repo = match Repository::open(&status.worktree.path) {
+        Ok(repo) => repo,
+        Err(_) => {
+            status.missing = true;
+            return Ok(status);
+        }
+    };
+
+    count_changes(&repo, &mut status)?;
+
+    match repo.state() {
+        RepositoryState::Merge => status.merge_in_progress = true,
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => status.rebase_in_progress = true,
+        _ => {}
+    }
+
+    let head = match repo.head().ok().and_then(|h| h.peel_to_commit().ok()) {
+        Some(commit) => commit,
+        None => return Ok(status), // unborn branch
+    };
+    status.last_commit_time = Some(head.time().seconds());
+
+    if let Some(branch) = &status.worktree.branch {
+        let upstream = repo
+            .find_branch(branch, BranchType::Local)
+            .and_then(|b| b.upstream())
+            .ok()
+            .and_then(|u| u.get().target());
+        if let Some(upstream) = upstream {
+            status.upstream = Some(AheadBehind::between(&repo, head.id(), upstream)?);
+        }
+    }
+
+    if let Some((name, tip)) = default_branch(&repo) {
+        status.against_default = Some(AheadBehind::between(&repo, head.id(), tip)?);
+        status.default_branch = Some(name);
+    }
+
+    Ok(status)
+}
+
+fn count_changes(repo: &Repository, status: &mut WorktreeStatus) -> Result<()> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .include_ignored(false);
+
+    let staged_flags = Status::INDEX_NEW
+        | Status::INDEX_MODIFIED
+        | Status::INDEX_DELETED
+        | Status::INDEX_RENAMED
+        | Status::INDEX_TYPECHANGE;
+    let dirty_flags =
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_RENAMED | Status::WT_TYPECHANGE;
+
+    for entry in repo.statuses(Some(&mut opts))?.iter() {
+        let flags = entry.status();
+        if flags.is_conflicted() {
+            status.conflicted += 1;
+            continue;
+        }
+        if flags.intersects(staged_flags) {
+            status.staged += 1;
+        }
+        if flags.intersects(dirty_flags) {
+            status.dirty += 1;
+        }
+        if flags.is_wt_new() {
+            status.untracked += 1;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file, init_repo_with_commit};
+    use crate::git::worktree::add_worktree;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn test_clean_main_worktree() {
+        let (dir, repo) = init_repo_with_commit();
+        let statuses = list_worktree_statuses(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(statuses.len(), 1);
+
+        let main = &statuses[0];
+        assert!(main.worktree.is_main);
+        assert!(!main.missing);
+        assert_eq!((main.staged, main.dirty, main.untracked), (0, 0, 0));
+        assert!(main.upstream.is_none());
+        assert_eq!(main.default_branch.as_deref(), Some("main"));
+        assert_eq!(
+            main.against_default,
+            Some(AheadBehind {
+                ahead: 0,
+                behind: 0
+            })
+        );
+        let head_time = repo.head().unwrap().peel_to_commit().unwrap().time();
+        assert_eq!(main.last_commit_time, Some(head_time.seconds()));
+        assert!(!main.merge_in_progress);
+        assert!(!main.rebase_in_progress);
+    }
+
+    #[test]
+    fn test_counts_staged_dirty_untracked() {
+        let (dir, repo) = init_repo_with_commit();
+        commit_file(&repo, "HEAD", "tracked.txt", "v1\n", "edit");
+        fs::write(dir.path().join("hello.txt"), "staged\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("hello.txt")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.path().join("tracked.txt"), "v2\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+
+        let statuses = list_worktree_statuses(dir.path().to_str().unwrap()).unwrap();
+        let main = &statuses[0];
+        assert_eq!(main.staged, 1);
+        assert_eq!(main.dirty, 1);
+        assert_eq!(main.untracked, 1);
+    }
+
+    #[test]
+    fn test_linked_worktree_ahead_of_default() {
+        let (dir, repo) = init_repo_with_commit();
+        let repo_path = dir.path().to_str().unwrap();
+        let wt_path = dir.path().join("wt-agent");
+        add_worktree(repo_path, wt_path.to_str().unwrap(), "agent").unwrap();
+
+        let wt_repo = Repository::open(&wt_path).unwrap();
+        commit_file(&wt_repo, "HEAD", "work.txt", "1\n", "edit");
+        commit_file(&wt_repo, "HEAD", "work.txt", "2\n", "edit");
+        // main moves on too
+        commit_file(&repo, "HEAD", "hello.txt", "main\n", "edit");
+
+        let statuses = list_worktree_statuses(repo_path).unwrap();
+        let agent = statuses
+            .iter()
+            .find(|s| s.worktree.name == "agent")
+            .unwrap();
+        assert_eq!(agent.default_branch.as_deref(), Some("main"));
+        assert_eq!(
+            agent.against_default,
+            Some(AheadBehind {
+                ahead: 2,
+                behind: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_upstream_ahead_behind() {
+        let (dir, repo) = init_repo_with_commit();
+        repo.remote("origin", "https://github.com/owner/repo.git")
+            .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.reference("refs/remotes/origin/main", head.id(), true, "test")
+            .unwrap();
+        repo.find_branch("main", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/main"))
+            .unwrap();
+        commit_file(&repo, "HEAD", "hello.txt", "local\n", "edit");
+
+        let statuses = list_worktree_statuses(dir.path().to_str().unwrap()).unwrap();
+        let main = &statuses[0];
+        assert_eq!(
+            main.upstream,
+            Some(AheadBehind {
+                ahead: 1,
+                behind: 0
+            })
+        );
+        // origin/main is preferred over the local main as the default branch
+        assert_eq!(main.default_branch.as_deref(), Some("origin/main"));
+    }
+
+    #[test]
+    fn test_missing_worktree_directory() {
+        let (dir, _repo) = init_repo_with_commit();
+        let repo_path = dir.path().to_str().unwrap();
+        let wt_path = dir.path().join("wt-gone");
+        add_worktree(repo_path, wt_path.to_str().unwrap(), "gone").unwrap();
+        fs::remove_dir_all(&wt_path).unwrap();
+
+        let statuses = list_worktree_statuses(repo_path).unwrap();
+        let gone = statuses.iter().find(|s| s.worktree.name == "gone").unwrap();
+        assert!(gone.missing);
+        assert!(gone.last_commit_time.is_none());
+    }
+
+    #[test]
+    fn test_merge_in_progress() {
+        let (dir, repo) = init_repo_with_commit();
+        let head = repo.head().unwrap().target().unwrap();
+        fs::write(repo.path().join("MERGE_HEAD"), format!("{head}\n")).unwrap();
+
+        let statuses = list_worktree_statuses(dir.path().to_str().unwrap()).unwrap();
+        assert!(statuses[0].merge_in_progress);
+        assert!(!statuses[0].rebase_in_progress);
+    }
+}
@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use git2::{ErrorCode, Repository};
+
+use super::diff::{diff_branches, DiffRequest, FileDiff};
+use super::log::{format_git_time, log, LogEntry, LogFilter};
+use super::open_repo;
+
+/// A tag and the commit it points to.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TagInfo {
+    pub name: String,
+    /// SHA of the tagged commit (annotated tags are peeled).
+    pub target: String,
+    /// `false` for lightweight tags.
+    pub annotated: bool,
+    /// Tag message of an annotated tag.
+    pub message: Option<String>,
+    /// Tagger name of an annotated tag.
+    pub tagger: Option<String>,
+    /// Tagger date, or the commit date for lightweight tags, in RFC 3339
+    /// format.
+    pub date: String,
+    /// `date` as a Unix timestamp.
+    pub time: i64,
+}
+
+/// Commits and changes between two tags, e.g. two releases.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ReleaseRange {
+    pub from: TagInfo,
+    pub to: TagInfo,
+    /// Commits reachable from `to` but not from `from`, newest first.
+    pub commits: Vec<LogEntry>,
+    /// Diff from the `from` tree to the `to` tree.
+    pub files: Vec<FileDiff>,
+}
+
+/// List tags pointing at commits, newest first.
+///
+/// Tags of trees or blobs are skipped.
+pub fn list_tags(repo_path: &str) -> Result<Vec<TagInfo>> {
+    let repo = open_repo(repo_path)?;
+    let names = repo.tag_names(None).context("Failed to list tags")?;
+
+    let mut tags = Vec::new();
+    for name in names.iter().flatten() {
+        let points_to_commit = repo
+            .find_reference(&format!("refs/tags/{name}"))
+            .and_then(|r| r.peel_to_commit())
+            .is_ok();
+        if points_to_commit {
+            tags.push(tag_info(&repo, name)?);
+        }
+    }
+    tags.sort_by(|a, b| b.time.cmp(&a.time).then_with(|| a.name.cmp(&b.name)));
+    Ok(tags)
+}
+
+/// Create an annotated tag `name` on `target` (a branch, tag or SHA).
+pub fn create_tag(repo_path: &str, name: &str, target: &str, message: &str) -> Result<TagInfo> {
+    let repo = open_repo(repo_path)?;
+    let signature = repo
+        .signature()
+        .context("user.name and user.email must be set in git config")?;
+    let commit = repo
+        .revparse_single(target)
+        .and_then(|obj| obj.peel_to_commit())
+        .with_context(|| format!("Revision '{target}' not found"))?;
+
+    repo.tag(name, commit.as_object(), &signature, message, false)
+        .map_err(|e| match e.code() {
+            ErrorCode::Exists => anyhow::anyhow!("Tag '{name}' already exists"),
+            _ => anyhow::Error::new(e).context(format!("Failed to create tag '{name}'")),
+        })?;
+    tag_info(&repo, name)
+}
+
+/// Delete the local tag `name`.
+pub fn delete_tag(repo_path: &str, name: &str) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    repo.tag_delete(name)
+        .with_context(|| format!("Failed to delete tag '{name}'"))
+}
+
+/// Commits and diff between the tags `from` and `to`.
+///
+/// The result can be fed to risk analysis or LLM summaries the same way as
+/// the files of a PR.
+pub fn release_range(
+    repo_path: &str,
+    from: &str,
+    to: &str,
+    request: &DiffRequest,
+) -> Result<ReleaseRange> {
+    let repo = open_repo(repo_path)?;
+    let from = tag_info(&repo, from)?;
+    let to = tag_info(&repo, to)?;
+
+    let filter = LogFilter {
+        range: Some(format!("{}..{}", from.target, to.target)),
+        ..Default::default()
+    };
+    let commits = log(repo_path, &filter)?;
+    let files = diff_branches(repo_path, &from.target, &to.target, request)?;
+
+    Ok(ReleaseRange {
+        from,
+        to,
+        commits,
+        files,
+    })
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+fn tag_info(repo: &Repository, name: &str) -> Result<TagInfo> {
+    let reference = repo
+        .find_reference(&format!("refs/tags/{name}"))
+        .with_context(|| format!("Tag '{name}' not found"))?;
+    let commit = reference
+        .peel_to_commit()
+        .with_context(|| format!("Tag '{name}' does not point to a commit"))?;
+
+    let annotated = reference.peel_to_tag().ok();
+    let tagger = annotated.as_ref().and_then(|tag| tag.tagger());
+    let when = tagger
+        .as_ref()
+        .map_or_else(|| commit.time(), |sig| sig.when());
+
+    Ok(TagInfo {
+        name: name.to_string(),
+        target: commit.id().to_string(),
+        annotated: annotated.is_some(),
+        message: annotated
+            .as_ref()
+            .and_then(|tag| tag.message())
+            .map(str::to_string),
+        tagger: tagger
+            .as_ref()
+            .and_then(|sig| sig.name())
+            .map(str::to_string),
+        date: format_git_time(when),
+        time: when.seconds(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::{commit_file_as, init_repo_with_commit, signature_at};
+
+    #[test]
+    fn test_create_and_list_tags() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        let first = commit_file_as(
+            &repo,
+            "HEAD",
+            "a.txt",
+            "one",
+            "one",
+            &signature_at("Test", 1_000_000),
+        );
+        repo.tag_lightweight("v0.1.0", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+        let second = commit_file_as(
+            &repo,
+            "HEAD",
+            "b.txt",
+            "two",
+            "two",
+            &signature_at("Test", 2_000_000),
+        );
+
+        let created = create_tag(path, "v0.2.0", "HEAD", "Release 0.2.0").unwrap();
+        assert!(created.annotated);
+        assert_eq!(created.target, second.to_string());
+        assert_eq!(created.message.as_deref(), Some("Release 0.2.0"));
+        assert_eq!(created.tagger.as_deref(), Some("Test"));
+
+        let tags = list_tags(path).unwrap();
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["v0.2.0", "v0.1.0"]);
+        assert!(!tags[1].annotated);
+        assert_eq!(tags[1].target, first.to_string());
+        assert_eq!(tags[1].time, 1_000_000);
+        assert_eq!(tags[1].date, "1970-01-12T13:46:40Z");
+    }
+
+    #[test]
+    fn test_create_tag_errors() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_tag(path, "v1", "main", "one").unwrap();
+
+        let err = create_tag(path, "v1", "main", "again").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+        assert!(create_tag(path, "v2", "missing", "msg").is_err());
+    }
+
+    #[test]
+    fn test_delete_tag() {
+        let (dir, _repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_tag(path, "v1", "main", "one").unwrap();
+
+        delete_tag(path, "v1").unwrap();
+        assert!(list_tags(path).unwrap().is_empty());
+        assert!(delete_tag(path, "v1").is_err());
+    }
+
+    #[test]
+    fn test_list_tags_skips_non_commit_targets() {
+        let (dir, repo) = init_repo_with_commit();
+        let blob = repo.blob(b"not a commit").unwrap();
+        repo.tag_lightweight("blob-tag", &repo.find_object(blob, None).unwrap(), false)
+            .unwrap();
+        assert!(list_tags(dir.path().to_str().unwrap()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_release_range() {
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        create_tag(path, "v1", "HEAD", "v1").unwrap();
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "a.txt",
+            "one",
+            "one",
+            &signature_at("Test", 1_000_000),
+        );
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "b.txt",
+            "two",
+            "two",
+            &signature_at("Test", 2_000_000),
+        );
+        create_tag(path, "v2", "HEAD", "v2").unwrap();
+        commit_file_as(
+            &repo,
+            "HEAD",
+            "c.txt",
+            "three",
+            "three",
+            &signature_at("Test", 3_000_000),
+        );
+
+        let range = release_range(path, "v1", "v2", &DiffRequest::default()).unwrap();
+        assert_eq!(range.from.name, "v1");
+        assert_eq!(range.to.name, "v2");
+        let summaries: Vec<&str> = range.commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, vec!["two", "one"]);
+        let paths: Vec<&str> = range
+            .files
+            .iter()
+            .filter_map(|f| f.new_path.as_deref())
+            .collect();
+        assert_eq!(paths, vec!["a.txt", "b.txt"]);
+
+        assert!(release_range(path, "v1", "missing", &DiffRequest::default()).is_err());
+    }
+}
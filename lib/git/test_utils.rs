@@ -1,4 +1,4 @@
-use git2::Repository;
+use git2::{Index, IndexEntry, IndexTime, Oid, Repository, Signature};
 use std::path::Path;
 use tempfile::TempDir;
 
@@ -60,3 +60,124 @@ pub fn init_repo_with_commit() -> (TempDir, Repository) {
 
     (dir, repo)
 }
+
+/// Commit `content` as `path` on top of `branch` and return the new commit.
+///
+/// With `"HEAD"` as `branch` this is `git add <path> && git commit`: the
+/// file is written to the working tree and the whole index is committed.
+/// With a branch name, the commit holds the branch tip's tree plus the
+/// change, and the working tree and index are only updated for `path`, and
+/// only when that branch is checked out.
+pub fn commit_file(
+    repo: &Repository,
+    branch: &str,
+    path: &str,
+    content: impl AsRef<[u8]>,
+    msg: &str,
+) -> Oid {
+    let sig = Signature::now("Test", "test@test.com").unwrap();
+    commit_change(repo, branch, path, Some(content.as_ref()), msg, &sig)
+}
+
+/// `commit_file` with `sig` as author and committer.
+pub fn commit_file_as(
+    repo: &Repository,
+    branch: &str,
+    path: &str,
+    content: impl AsRef<[u8]>,
+    msg: &str,
+    sig: &Signature,
+) -> Oid {
+    commit_change(repo, branch, path, Some(content.as_ref()), msg, sig)
+}
+
+/// Signature of `name` (`<name>@example.com`) at the Unix time `time`.
+pub fn signature_at(name: &str, time: i64) -> Signature<'static> {
+    let email = format!("{}@example.com", name.to_lowercase());
+    Signature::new(name, &email, &git2::Time::new(time, 0)).unwrap()
+}
+
+/// Commit the removal of `path` on top of `branch`, like `commit_file`.
+pub fn delete_file(repo: &Repository, branch: &str, path: &str, msg: &str) -> Oid {
+    let sig = Signature::now("Test", "test@test.com").unwrap();
+    commit_change(repo, branch, path, None, msg, &sig)
+}
+
+fn commit_change(
+    repo: &Repository,
+    branch: &str,
+    path: &str,
+    content: Option<&[u8]>,
+    msg: &str,
+    sig: &Signature,
+) -> Oid {
+    if branch == "HEAD" {
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        stage_change(repo, &mut index, path, content);
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        return repo
+            .commit(Some("HEAD"), sig, sig, msg, &tree, &[&parent])
+            .unwrap();
+    }
+
+    let refname = format!("refs/heads/{branch}");
+    let parent = repo
+        .find_reference(&refname)
+        .unwrap()
+        .peel_to_commit()
+        .unwrap();
+    let mut index = Index::new().unwrap();
+    index.read_tree(&parent.tree().unwrap()).unwrap();
+    match content {
+        Some(content) => {
+            let time = IndexTime::new(0, 0);
+            let entry = IndexEntry {
+                ctime: time,
+                mtime: time,
+                dev: 0,
+                ino: 0,
+                mode: 0o100644,
+                uid: 0,
+                gid: 0,
+                file_size: content.len() as u32,
+                id: repo.blob(content).unwrap(),
+                flags: 0,
+                flags_extended: 0,
+                path: path.as_bytes().to_vec(),
+            };
+            index.add(&entry).unwrap();
+        }
+        None => index.remove_path(Path::new(path)).unwrap(),
+    }
+    let tree = repo.find_tree(index.write_tree_to(repo).unwrap()).unwrap();
+    let oid = repo
+        .commit(Some(&refname), sig, sig, msg, &tree, &[&parent])
+        .unwrap();
+
+    let head = repo.head().ok().and_then(|h| h.name().map(str::to_string));
+    if repo.workdir().is_some() && head.as_deref() == Some(refname.as_str()) {
+        let mut index = repo.index().unwrap();
+        stage_change(repo, &mut index, path, content);
+    }
+    oid
+}
+
+/// Write `content` to `path` in the working tree, or remove the file when
+/// it is `None`, and stage the result.
+fn stage_change(repo: &Repository, index: &mut Index, path: &str, content: Option<&[u8]>) {
+    let file = repo.workdir().unwrap().join(path);
+    match content {
+        Some(content) => {
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(&file, content).unwrap();
+            index.add_path(Path::new(path)).unwrap();
+        }
+        None => {
+            std::fs::remove_file(&file).unwrap();
+            index.remove_path(Path::new(path)).unwrap();
+        }
+    }
+    index.write().unwrap();
+}
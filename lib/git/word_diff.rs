@@ -0,0 +1,292 @@
+use super::diff::{DiffChunk, HighlightRange, LineOrigin};
+
+/// Upper bound on `old_tokens * new_tokens` for the LCS table.
+/// Longer line pairs (minified code etc.) are left without highlights.
+const MAX_LCS_CELLS: usize = 250_000;
+
+/// Pair up deleted and added lines in `chunk` and fill in `highlights`
+/// with the ranges that actually changed.
+///
+/// A run of deletions immediately followed by a run of additions is paired
+/// line by line (1st with 1st, 2nd with 2nd, …). Surplus lines on either
+/// side are left without highlights since the whole line is new or gone.
+pub fn highlight_chunk(chunk: &mut DiffChunk) {
+    let mut i = 0;
+    while i < chunk.lines.len() {
+        if chunk.lines[i].origin != LineOrigin::Deletion {
+            i += 1;
+            continue;
+        }
+
+        let del_start = i;
+        while i < chunk.lines.len() && chunk.lines[i].origin == LineOrigin::Deletion {
+            i += 1;
+        }
+        let add_start = i;
+        while i < chunk.lines.len() && chunk.lines[i].origin == LineOrigin::Addition {
+            i += 1;
+        }
+
+        let pairs = (add_start - del_start).min(i - add_start);
+        for n in 0..pairs {
+            let (old_ranges, new_ranges) = changed_ranges(
+                &chunk.lines[del_start + n].content,
+                &chunk.lines[add_start + n].content,
+            );
+            chunk.lines[del_start + n].highlights = old_ranges;
+            chunk.lines[add_start + n].highlights = new_ranges;
+        }
+    }
+}
+
+/// Compute the changed ranges between two versions of a line.
+///
+/// Lines are split into word, whitespace and punctuation tokens and
+/// compared with an LCS. Ranges are character offsets into each line.
+/// Returns empty ranges when the lines have nothing in common, because
+/// highlighting the whole line adds no information.
+pub fn changed_ranges(old: &str, new: &str) -> (Vec<HighlightRange>, Vec<HighlightRange>) {
+    let old_tokens = tokenize(old.trim_end_matches(['\n', '\r']));
+    let new_tokens = tokenize(new.trim_end_matches(['\n', '\r']));
+
+    if old_tokens.is_empty()
+        || new_tokens.is_empty()
+        || old_tokens.len() * new_tokens.len() > MAX_LCS_CELLS
+    {
+        return (Vec::new(), Vec::new());
+    }
+
+    let (old_common, new_common) = lcs(&old_tokens, &new_tokens);
+    if !old_common.iter().any(|&c| c) {
+        return (Vec::new(), Vec::new());
+    }
+
+    (
+        collect_ranges(&old_tokens, &old_common),
+        collect_ranges(&new_tokens, &new_common),
+    )
+}
+
+// ── internals ────────────────────────────────────────────────────────────────
+
+#[derive(Debug)]
+struct Token<'a> {
+    text: &'a str,
+    /// Character offset of the first char.
+    start: usize,
+    /// Character offset one past the last char.
+    end: usize,
+}
+
+#[derive(PartialEq)]
+enum TokenKind {
+    Word,
+    Space,
+    Punct,
+}
+
+fn token_kind(c: char) -> TokenKind {
+    if c.is_alphanumeric() || c == '_' {
+        TokenKind::Word
+    } else if c.is_whitespace() {
+        TokenKind::Space
+    } else {
+        TokenKind::Punct
+    }
+}
+
+/// Split a line into runs of word chars, runs of whitespace, and single
+/// punctuation chars.
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().enumerate().peekable();
+
+    while let Some((char_idx, (byte_idx, c))) = chars.next() {
+        let kind = token_kind(c);
+        let mut end_char = char_idx + 1;
+        let mut end_byte = byte_idx + c.len_utf8();
+
+        if kind != TokenKind::Punct {
+            while let Some(&(_, (b, next))) = chars.peek() {
+                if token_kind(next) != kind {
+                    break;
+                }
+                end_char += 1;
+                end_byte = b + next.len_utf8();
+                chars.next();
+            }
+        }
+
+        tokens.push(Token {
+            text: &line[byte_idx..end_byte],
+            start: char_idx,
+            end: end_char,
+        });
+    }
+
+    tokens
+}
+
+/// Longest common subsequence over tokens. Returns, for each side,
+/// whether each token is part of the common subsequence.
+fn lcs(old: &[Token<'_>], new: &[Token<'_>]) -> (Vec<bool>, Vec<bool>) {
+    let (n, m) = (old.len(), new.len());
+    // table[i][j] = LCS length of old[i..] and new[j..]
+    let mut table = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old[i].text == new[j].text {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_common = vec![false; n];
+    let mut new_common = vec![false; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i].text == new[j].text {
+            old_common[i] = true;
+            new_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    (old_common, new_common)
+}
+
+/// Merge adjacent non-common tokens into ranges.
+fn collect_ranges(tokens: &[Token<'_>], common: &[bool]) -> Vec<HighlightRange> {
+    let mut ranges: Vec<HighlightRange> = Vec::new();
+    for (token, &is_common) in tokens.iter().zip(common) {
+        if is_common {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == token.start => last.end = token.end,
+            _ => ranges.push(HighlightRange {
+                start: token.start,
+                end: token.end,
+            }),
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::DiffLineInfo;
+
+    fn range(start: usize, end: usize) -> HighlightRange {
+        HighlightRange { start, end }
+    }
+
+    fn line(origin: LineOrigin, content: &str) -> DiffLineInfo {
+        DiffLineInfo {
+            origin,
+            old_lineno: None,
+            new_lineno: None,
+            content: content.to_string(),
+            highlights: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize_splits_words_spaces_and_punctuation() {
+        let tokens: Vec<&str> = tokenize("let x_1 = foo(a);")
+            .iter()
+            .map(|t| t.text)
+            .collect();
+        assert_eq!(
+            tokens,
+            vec!["let", " ", "x_1", " ", "=", " ", "foo", "(", "a", ")", ";"]
+        );
+    }
+
+    #[test]
+    fn test_changed_ranges_single_identifier() {
+        let (old, new) = changed_ranges("let count = 1;\n", "let total = 1;\n");
+        assert_eq!(old, vec![range(4, 9)]);
+        assert_eq!(new, vec![range(4, 9)]);
+    }
+
+    #[test]
+    fn test_changed_ranges_insertion_only() {
+        let (old, new) = changed_ranges("foo(a)\n", "foo(a, b)\n");
+        assert!(old.is_empty());
+        assert_eq!(new, vec![range(5, 8)]);
+    }
+
+    #[test]
+    fn test_changed_ranges_identical_lines() {
+        let (old, new) = changed_ranges("same\n", "same\n");
+        assert!(old.is_empty());
+        assert!(new.is_empty());
+    }
+
+    #[test]
+    fn test_changed_ranges_nothing_in_common() {
+        let (old, new) = changed_ranges("alpha\n", "beta\n");
+        assert!(old.is_empty());
+        assert!(new.is_empty());
+    }
+
+    #[test]
+    fn test_changed_ranges_uses_char_offsets() {
+        // Multi-byte characters count as one position each
+        let (old, new) = changed_ranges("名前 = 太郎\n", "名前 = 花子\n");
+        assert_eq!(old, vec![range(5, 7)]);
+        assert_eq!(new, vec![range(5, 7)]);
+    }
+
+    #[test]
+    fn test_highlight_chunk_pairs_deletions_with_additions() {
+        let mut chunk = DiffChunk {
+            header: "@@ -1,4 +1,4 @@".to_string(),
+            lines: vec![
+                line(LineOrigin::Context, "fn main() {\n"),
+                line(LineOrigin::Deletion, "    let a = 1;\n"),
+                line(LineOrigin::Deletion, "    let b = 2;\n"),
+                line(LineOrigin::Addition, "    let a = 10;\n"),
+                line(LineOrigin::Addition, "    let b = 20;\n"),
+                line(LineOrigin::Addition, "    let c = 30;\n"),
+                line(LineOrigin::Context, "}\n"),
+            ],
+        };
+
+        highlight_chunk(&mut chunk);
+
+        assert!(chunk.lines[0].highlights.is_empty());
+        assert_eq!(chunk.lines[1].highlights, vec![range(12, 13)]);
+        assert_eq!(chunk.lines[2].highlights, vec![range(12, 13)]);
+        assert_eq!(chunk.lines[3].highlights, vec![range(12, 14)]);
+        assert_eq!(chunk.lines[4].highlights, vec![range(12, 14)]);
+        // Surplus addition has no counterpart
+        assert!(chunk.lines[5].highlights.is_empty());
+        assert!(chunk.lines[6].highlights.is_empty());
+    }
+
+    #[test]
+    fn test_highlight_chunk_ignores_unpaired_runs() {
+        let mut chunk = DiffChunk {
+            header: "@@ -1,2 +1,2 @@".to_string(),
+            lines: vec![
+                line(LineOrigin::Deletion, "removed line\n"),
+                line(LineOrigin::Context, "context\n"),
+                line(LineOrigin::Addition, "added line\n"),
+            ],
+        };
+
+        highlight_chunk(&mut chunk);
+
+        assert!(chunk.lines.iter().all(|l| l.highlights.is_empty()));
+    }
+}
@@ -2,7 +2,8 @@ use anyhow::{Context, Result};
 use git2::{Repository, Worktree};
 use std::path::PathBuf;
 
-use super::open_repo;
+use super::remote::{fetch, SyncProgress};
+use super::{get_repo_info, open_repo};
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct WorktreeInfo {
@@ -12,6 +13,18 @@ pub struct WorktreeInfo {
     #[allow(dead_code)] // used in Phase 2 for worktree filtering
     pub is_main: bool,
     pub is_locked: bool,
+    /// Reason given when the worktree was locked, if any.
+    pub lock_reason: Option<String>,
+}
+
+/// Options for `remove_worktree`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RemoveWorktreeOptions {
+    /// Remove even if the worktree has uncommitted changes or is locked.
+    pub force: bool,
+    /// Delete the worktree's branch after removing it.
+    pub delete_branch: bool,
 }
 
 /// List all worktrees for the given repository path.
@@ -30,6 +43,7 @@ pub fn list_worktrees(repo_path: &str) -> Result<Vec<WorktreeInfo>> {
         branch: main_branch,
         is_main: true,
         is_locked: false,
+        lock_reason: None,
     });
 
     // Linked worktrees
@@ -39,7 +53,12 @@ pub fn list_worktrees(repo_path: &str) -> Result<Vec<WorktreeInfo>> {
         let wt_path = PathBuf::from(wt.path());
 
         let branch = worktree_branch(&wt_path);
-        let is_locked = matches!(wt.is_locked(), Ok(git2::WorktreeLockStatus::Locked(_)));
+        let (is_locked, lock_reason) = match wt.is_locked() {
+            Ok(git2::WorktreeLockStatus::Locked(reason)) => {
+                (true, reason.filter(|r| !r.is_empty()))
+            }
+            _ => (false, None),
+        };
 
         result.push(WorktreeInfo {
             name: name.to_string(),
@@ -47,6 +66,7 @@ pub fn list_worktrees(repo_path: &str) -> Result<Vec<WorktreeInfo>> {
             branch,
             is_main: false,
             is_locked,
+            lock_reason,
         });
     }
 
@@ -107,22 +127,89 @@ pub fn add_worktree_for_todo(
     let branch = generate_branch_name_for_todo(file_path, line_number);
 
     let repo = open_repo(repo_path)?;
-    let workdir = repo
-        .workdir()
-        .with_context(|| "ベアリポジトリはサポートされていません")?;
+    let (safe_branch, wt_path) = sibling_worktree_path(&repo, &branch)?;
 
-    // worktree のパスをリポジトリの親ディレクトリに配置する
-    let repo_dir_name = workdir
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("repo");
-    let parent = workdir
-        .parent()
-        .with_context(|| "リポジトリの親ディレクトリが見つかりません")?;
-    // ブランチ名の `/` を `-` に変換してパスに使う
-    let safe_branch = branch.replace('/', "-");
-    let wt_path = parent.join(format!("{repo_dir_name}-{safe_branch}"));
+    add_worktree(
+        repo_path,
+        wt_path
+            .to_str()
+            .with_context(|| "worktree パスの変換に失敗しました")?,
+        &branch,
+    )?;
+
+    Ok(WorktreeInfo {
+        name: safe_branch,
+        path: wt_path,
+        branch: Some(branch),
+        is_main: false,
+        is_locked: false,
+        lock_reason: None,
+    })
+}
+
+/// PR 番号からローカルブランチ名を生成する。
+///
+/// 形式: `pr/<番号>`
+pub fn generate_branch_name_for_pr(pr_number: u64) -> String {
+    format!("pr/{pr_number}")
+}
 
+/// PR の head を origin から取得し、専用の worktree を作成する。
+///
+/// `refs/pull/<番号>/head` を `get_repo_info` が返す origin から fetch し、
+/// `pr/<番号>` ブランチを作成して worktree に展開する。
+/// `pr/<番号>` が既にあれば fetch した head まで fast-forward し、
+/// head と分岐している場合はエラーにする。
+/// worktree のパスは `add_worktree_for_todo` と同じく `<リポジトリ名>-<ブランチ名>` になる。
+pub fn add_worktree_for_pr(
+    repo_path: &str,
+    pr_number: u64,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<WorktreeInfo> {
+    // PR の head は reown 専用の ref に取得し、ローカルブランチは別途作成する
+    let tracking_ref = fetch_pr_head(repo_path, pr_number, progress)?;
+
+    let repo = open_repo(repo_path)?;
+    let head = repo
+        .find_reference(&tracking_ref)
+        .and_then(|r| r.peel_to_commit())
+        .with_context(|| format!("PR #{pr_number} の head が見つかりません"))?;
+
+    let branch = generate_branch_name_for_pr(pr_number);
+    match repo.find_branch(&branch, git2::BranchType::Local) {
+        Ok(mut existing) => {
+            let tip = existing.get().peel_to_commit()?.id();
+            // ローカルで PR の head より先に進めたコミットはそのまま残す
+            if tip != head.id() && !repo.graph_descendant_of(tip, head.id())? {
+                if !repo.graph_descendant_of(head.id(), tip)? {
+                    anyhow::bail!(
+                        "ブランチ '{branch}' が PR #{pr_number} の head と分岐しています"
+                    );
+                }
+                if list_worktrees(repo_path)?
+                    .iter()
+                    .any(|wt| wt.branch.as_deref() == Some(branch.as_str()))
+                {
+                    anyhow::bail!(
+                        "ブランチ '{branch}' は既に worktree でチェックアウトされています"
+                    );
+                }
+                existing
+                    .get_mut()
+                    .set_target(
+                        head.id(),
+                        &format!("PR #{pr_number} の head へ fast-forward"),
+                    )
+                    .with_context(|| format!("ブランチ '{branch}' の更新に失敗しました"))?;
+            }
+        }
+        Err(_) => {
+            repo.branch(&branch, &head, false)
+                .with_context(|| format!("ブランチ '{branch}' の作成に失敗しました"))?;
+        }
+    }
+
+    let (safe_branch, wt_path) = sibling_worktree_path(&repo, &branch)?;
     add_worktree(
         repo_path,
         wt_path
@@ -137,11 +224,202 @@ pub fn add_worktree_for_todo(
         branch: Some(branch),
         is_main: false,
         is_locked: false,
+        lock_reason: None,
     })
 }
 
+/// PR の head を origin から `refs/reown/pull/<番号>` に取得し、その ref 名を返す。
+///
+/// ユーザーのリモート追跡ブランチを上書きしないよう、reown 専用の名前空間に強制 fetch する。
+pub(crate) fn fetch_pr_head(
+    repo_path: &str,
+    pr_number: u64,
+    progress: &mut dyn FnMut(SyncProgress),
+) -> Result<String> {
+    let info = get_repo_info(repo_path)?;
+    if info.remote_url.is_none() {
+        anyhow::bail!("origin リモートが設定されていません");
+    }
+
+    let tracking_ref = format!("refs/reown/pull/{pr_number}");
+    let refspec = format!("+refs/pull/{pr_number}/head:{tracking_ref}");
+    fetch(repo_path, "origin", &[refspec.as_str()], progress)
+        .with_context(|| format!("PR #{pr_number} の取得に失敗しました"))?;
+    Ok(tracking_ref)
+}
+
+/// Remove the linked worktree `name` (as listed by `list_worktrees`).
+///
+/// Refuses to remove a worktree with uncommitted changes (including
+/// untracked files) or a locked one unless `force` is set. With
+/// `delete_branch`, the branch that was checked out there is deleted too.
+pub fn remove_worktree(repo_path: &str, name: &str, options: &RemoveWorktreeOptions) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let wt = repo
+        .find_worktree(name)
+        .with_context(|| format!("Worktree '{name}' not found"))?;
+    let wt_path = PathBuf::from(wt.path());
+
+    if !options.force {
+        if let Ok(git2::WorktreeLockStatus::Locked(reason)) = wt.is_locked() {
+            match reason.filter(|r| !r.is_empty()) {
+                Some(reason) => anyhow::bail!("Worktree '{name}' is locked: {reason}"),
+                None => anyhow::bail!("Worktree '{name}' is locked"),
+            }
+        }
+    }
+
+    let wt_repo = Repository::open(&wt_path).ok();
+    let branch = wt_repo.as_ref().and_then(current_branch_name);
+    if !options.force {
+        if let Some(wt_repo) = &wt_repo {
+            let dirty = changed_paths(wt_repo)?;
+            if !dirty.is_empty() {
+                anyhow::bail!(
+                    "Worktree '{name}' has uncommitted changes: {}",
+                    dirty.join(", ")
+                );
+            }
+        }
+    }
+    drop(wt_repo);
+
+    let mut flags = git2::WorktreePruneOptions::new();
+    flags.valid(true).working_tree(true).locked(options.force);
+    wt.prune(Some(&mut flags))
+        .with_context(|| format!("Failed to remove worktree '{name}'"))?;
+
+    if options.delete_branch {
+        if let Some(branch) = branch {
+            repo.find_branch(&branch, git2::BranchType::Local)
+                .and_then(|mut b| b.delete())
+                .with_context(|| format!("Failed to delete branch '{branch}'"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Prune administrative entries of worktrees whose directory is gone.
+/// Locked worktrees are kept. Returns the names of the pruned entries.
+pub fn prune_worktrees(repo_path: &str) -> Result<Vec<String>> {
+    let repo = open_repo(repo_path)?;
+
+    let mut pruned = Vec::new();
+    for name in repo.worktrees()?.iter().flatten() {
+        let wt = repo.find_worktree(name)?;
+        if wt.is_prunable(None)? {
+            wt.prune(None)
+                .with_context(|| format!("Failed to prune worktree '{name}'"))?;
+            pruned.push(name.to_string());
+        }
+    }
+
+    Ok(pruned)
+}
+
+/// Move the linked worktree `name` to `new_path`, like `git worktree move`.
+///
+/// The directory is renamed and both links between it and the repository
+/// are rewritten: `<common dir>/worktrees/<name>/gitdir` and the worktree's
+/// own `.git` file. The worktree keeps its name. Locked worktrees and the
+/// main worktree cannot be moved, and `new_path` must not exist yet.
+pub fn move_worktree(repo_path: &str, name: &str, new_path: &str) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let wt = repo
+        .find_worktree(name)
+        .with_context(|| format!("Worktree '{name}' not found"))?;
+    if let Ok(git2::WorktreeLockStatus::Locked(_)) = wt.is_locked() {
+        anyhow::bail!("Worktree '{name}' is locked");
+    }
+
+    let old_path = PathBuf::from(wt.path());
+    let new_path = std::path::absolute(new_path)
+        .with_context(|| format!("Invalid worktree path '{new_path}'"))?;
+    if new_path.symlink_metadata().is_ok() {
+        anyhow::bail!("'{}' already exists", new_path.display());
+    }
+    std::fs::rename(&old_path, &new_path).with_context(|| {
+        format!(
+            "Failed to move worktree '{name}' to '{}'",
+            new_path.display()
+        )
+    })?;
+
+    let admin_dir = repo.commondir().join("worktrees").join(name);
+    let relink = std::fs::write(
+        admin_dir.join("gitdir"),
+        format!("{}\n", new_path.join(".git").display()),
+    )
+    .and_then(|()| {
+        std::fs::write(
+            new_path.join(".git"),
+            format!("gitdir: {}\n", admin_dir.display()),
+        )
+    });
+    if let Err(e) = relink {
+        // Put the directory back so the old links stay valid
+        let _ = std::fs::rename(&new_path, &old_path);
+        return Err(e).with_context(|| format!("Failed to update links of worktree '{name}'"));
+    }
+    Ok(())
+}
+
+/// Lock the worktree `name` so it is not pruned or removed.
+pub fn lock_worktree(repo_path: &str, name: &str, reason: Option<&str>) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let wt = repo
+        .find_worktree(name)
+        .with_context(|| format!("Worktree '{name}' not found"))?;
+    wt.lock(reason)
+        .with_context(|| format!("Failed to lock worktree '{name}'"))?;
+    Ok(())
+}
+
+/// Unlock the worktree `name`.
+pub fn unlock_worktree(repo_path: &str, name: &str) -> Result<()> {
+    let repo = open_repo(repo_path)?;
+    let wt = repo
+        .find_worktree(name)
+        .with_context(|| format!("Worktree '{name}' not found"))?;
+    wt.unlock()
+        .with_context(|| format!("Failed to unlock worktree '{name}'"))?;
+    Ok(())
+}
+
 // ── helpers ──────────────────────────────────────────────────────────────────
 
+/// Paths with staged, unstaged or untracked changes (ignored files excluded).
+fn changed_paths(repo: &Repository) -> Result<Vec<String>> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut opts))?;
+    Ok(statuses
+        .iter()
+        .filter_map(|entry| entry.path().map(str::to_string))
+        .collect())
+}
+
+/// worktree の名前とパスを決める。
+///
+/// パスはリポジトリの親ディレクトリに `<リポジトリ名>-<ブランチ名>` で配置し、
+/// ブランチ名の `/` は `-` に変換する。
+pub(crate) fn sibling_worktree_path(repo: &Repository, branch: &str) -> Result<(String, PathBuf)> {
+    let workdir = repo
+        .workdir()
+        .with_context(|| "ベアリポジトリはサポートされていません")?;
+    let repo_dir_name = workdir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("repo");
+    let parent = workdir
+        .parent()
+        .with_context(|| "リポジトリの親ディレクトリが見つかりません")?;
+    let safe_branch = branch.replace('/', "-");
+    let wt_path = parent.join(format!("{repo_dir_name}-{safe_branch}"));
+    Ok((safe_branch, wt_path))
+}
+
 fn current_branch_name(repo: &Repository) -> Option<String> {
     repo.head()
         .ok()
@@ -183,6 +461,52 @@ mod tests {
         assert_eq!(added.branch.as_deref(), Some("feature"));
     }
 
+    #[test]
+    fn test_move_worktree() {
+        let (dir, _repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        let old_path = dir.path().join("wt-feature");
+        add_worktree(repo_path, old_path.to_str().unwrap(), "feature").unwrap();
+        std::fs::write(old_path.join("work.txt"), "wip\n").unwrap();
+
+        let new_path = dir.path().join("moved").join("feature");
+        std::fs::create_dir(dir.path().join("moved")).unwrap();
+        move_worktree(repo_path, "feature", new_path.to_str().unwrap()).unwrap();
+
+        assert!(!old_path.exists());
+        assert!(new_path.join("work.txt").exists());
+        let wts = list_worktrees(repo_path).unwrap();
+        let moved = wts.iter().find(|w| w.name == "feature").unwrap();
+        assert_eq!(moved.path, new_path);
+        assert_eq!(moved.branch.as_deref(), Some("feature"));
+
+        // Both links are valid: the worktree opens and is not prunable
+        let wt_repo = Repository::open(&new_path).unwrap();
+        assert_eq!(wt_repo.workdir().unwrap(), new_path.join("").as_path());
+        assert!(prune_worktrees(repo_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_move_worktree_refusals() {
+        let (dir, _repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        let wt_path = dir.path().join("wt-feature");
+        add_worktree(repo_path, wt_path.to_str().unwrap(), "feature").unwrap();
+        let target = dir.path().join("elsewhere");
+
+        std::fs::create_dir(&target).unwrap();
+        let err = move_worktree(repo_path, "feature", target.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{err}");
+        std::fs::remove_dir(&target).unwrap();
+
+        lock_worktree(repo_path, "feature", None).unwrap();
+        let err = move_worktree(repo_path, "feature", target.to_str().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("locked"), "{err}");
+
+        assert!(move_worktree(repo_path, "(main)", target.to_str().unwrap()).is_err());
+        assert!(wt_path.exists());
+    }
+
     #[test]
     fn test_list_worktrees_invalid_path() {
         let result = list_worktrees("/tmp/nonexistent-repo-xyz");
@@ -234,4 +558,253 @@ mod tests {
         let result = add_worktree_for_todo(repo_path, "src/auth.ts", 25);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_generate_branch_name_for_pr() {
+        assert_eq!(generate_branch_name_for_pr(42), "pr/42");
+    }
+
+    /// origin に `refs/pull/7/head` を持つ bare リポジトリを用意する
+    fn init_repo_with_pr_origin() -> (tempfile::TempDir, Repository, tempfile::TempDir, git2::Oid) {
+        let (dir, repo) = init_test_repo();
+        let origin_dir = tempfile::TempDir::new().unwrap();
+        let origin = Repository::init_bare(origin_dir.path()).unwrap();
+
+        // PR の head コミットを origin に直接作成する
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        let blob = origin.blob(b"from pr\n").unwrap();
+        let mut builder = origin.treebuilder(None).unwrap();
+        builder.insert("pr.txt", blob, 0o100644).unwrap();
+        let tree = origin.find_tree(builder.write().unwrap()).unwrap();
+        let pr_head = origin
+            .commit(
+                Some("refs/pull/7/head"),
+                &sig,
+                &sig,
+                "PR change",
+                &tree,
+                &[],
+            )
+            .unwrap();
+
+        let url = format!("file://{}", origin_dir.path().display());
+        repo.remote("origin", &url).unwrap();
+        (dir, repo, origin_dir, pr_head)
+    }
+
+    #[test]
+    fn test_add_worktree_for_pr() {
+        let (dir, repo, _origin_dir, pr_head) = init_repo_with_pr_origin();
+        let repo_path = dir.path().to_str().unwrap();
+
+        let info = add_worktree_for_pr(repo_path, 7, &mut |_| {}).unwrap();
+
+        assert_eq!(info.branch.as_deref(), Some("pr/7"));
+        assert_eq!(info.name, "pr-7");
+        let repo_name = dir.path().file_name().unwrap().to_str().unwrap();
+        assert_eq!(
+            info.path,
+            dir.path()
+                .parent()
+                .unwrap()
+                .join(format!("{repo_name}-pr-7"))
+        );
+        assert!(info.path.join("pr.txt").exists());
+
+        let branch = repo.find_branch("pr/7", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(pr_head));
+    }
+
+    #[test]
+    fn test_add_worktree_for_pr_unknown_pr() {
+        let (dir, _repo, _origin_dir, _) = init_repo_with_pr_origin();
+        let result = add_worktree_for_pr(dir.path().to_str().unwrap(), 99, &mut |_| {});
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fetch_pr_head_keeps_remote_tracking_refs() {
+        let (dir, repo, _origin_dir, pr_head) = init_repo_with_pr_origin();
+        let local_head = repo.head().unwrap().target().unwrap();
+        repo.reference("refs/remotes/origin/pr/7", local_head, true, "test")
+            .unwrap();
+
+        let tracking_ref = fetch_pr_head(dir.path().to_str().unwrap(), 7, &mut |_| {}).unwrap();
+
+        assert_eq!(tracking_ref, "refs/reown/pull/7");
+        assert_eq!(repo.refname_to_id(&tracking_ref).unwrap(), pr_head);
+        assert_eq!(
+            repo.refname_to_id("refs/remotes/origin/pr/7").unwrap(),
+            local_head
+        );
+    }
+
+    #[test]
+    fn test_add_worktree_for_pr_fast_forwards_existing_branch() {
+        let (dir, repo, origin_dir, pr_head) = init_repo_with_pr_origin();
+        let repo_path = dir.path().to_str().unwrap();
+        let info = add_worktree_for_pr(repo_path, 7, &mut |_| {}).unwrap();
+        remove_worktree(repo_path, &info.name, &RemoveWorktreeOptions::default()).unwrap();
+
+        // PR に新しいコミットが push された
+        let origin = Repository::open_bare(origin_dir.path()).unwrap();
+        let parent = origin.find_commit(pr_head).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        let new_head = origin
+            .commit(
+                Some("refs/pull/7/head"),
+                &sig,
+                &sig,
+                "PR update",
+                &parent.tree().unwrap(),
+                &[&parent],
+            )
+            .unwrap();
+
+        add_worktree_for_pr(repo_path, 7, &mut |_| {}).unwrap();
+        let branch = repo.find_branch("pr/7", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(new_head));
+    }
+
+    #[test]
+    fn test_add_worktree_for_pr_refuses_diverged_branch() {
+        let (dir, repo, _origin_dir, _) = init_repo_with_pr_origin();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("pr/7", &head, false).unwrap();
+
+        let err = add_worktree_for_pr(dir.path().to_str().unwrap(), 7, &mut |_| {}).unwrap_err();
+        assert!(err.to_string().contains("分岐"), "{err}");
+        let branch = repo.find_branch("pr/7", git2::BranchType::Local).unwrap();
+        assert_eq!(branch.get().target(), Some(head.id()));
+    }
+
+    #[test]
+    fn test_add_worktree_for_pr_without_origin() {
+        let (dir, _repo) = init_test_repo();
+        let result = add_worktree_for_pr(dir.path().to_str().unwrap(), 7, &mut |_| {});
+        assert!(result.is_err());
+    }
+
+    /// Add a linked worktree on a new `branch` next to the test repo.
+    fn add_sibling(dir: &tempfile::TempDir, branch: &str) -> PathBuf {
+        let wt_path = dir.path().join(format!("wt-{branch}"));
+        add_worktree(
+            dir.path().to_str().unwrap(),
+            wt_path.to_str().unwrap(),
+            branch,
+        )
+        .unwrap();
+        wt_path
+    }
+
+    #[test]
+    fn test_remove_worktree() {
+        let (dir, repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        let wt_path = add_sibling(&dir, "feature");
+
+        remove_worktree(repo_path, "feature", &RemoveWorktreeOptions::default()).unwrap();
+
+        assert!(!wt_path.exists());
+        assert_eq!(list_worktrees(repo_path).unwrap().len(), 1);
+        // Branch is kept by default
+        assert!(repo.find_branch("feature", git2::BranchType::Local).is_ok());
+    }
+
+    #[test]
+    fn test_remove_worktree_deletes_branch() {
+        let (dir, repo) = init_test_repo();
+        add_sibling(&dir, "feature");
+
+        let options = RemoveWorktreeOptions {
+            delete_branch: true,
+            ..Default::default()
+        };
+        remove_worktree(dir.path().to_str().unwrap(), "feature", &options).unwrap();
+
+        assert!(repo
+            .find_branch("feature", git2::BranchType::Local)
+            .is_err());
+    }
+
+    #[test]
+    fn test_remove_worktree_refuses_dirty() {
+        let (dir, _repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        let wt_path = add_sibling(&dir, "feature");
+        std::fs::write(wt_path.join("scratch.txt"), "wip\n").unwrap();
+
+        let err = remove_worktree(repo_path, "feature", &RemoveWorktreeOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("uncommitted changes"));
+        assert!(err.contains("scratch.txt"));
+        assert!(wt_path.exists());
+
+        let force = RemoveWorktreeOptions {
+            force: true,
+            ..Default::default()
+        };
+        remove_worktree(repo_path, "feature", &force).unwrap();
+        assert!(!wt_path.exists());
+    }
+
+    #[test]
+    fn test_lock_and_unlock_worktree() {
+        let (dir, _repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        add_sibling(&dir, "feature");
+
+        lock_worktree(repo_path, "feature", Some("agent is running")).unwrap();
+        let wts = list_worktrees(repo_path).unwrap();
+        let wt = wts.iter().find(|w| w.name == "feature").unwrap();
+        assert!(wt.is_locked);
+        assert_eq!(wt.lock_reason.as_deref(), Some("agent is running"));
+
+        let err = remove_worktree(repo_path, "feature", &RemoveWorktreeOptions::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("locked"));
+
+        unlock_worktree(repo_path, "feature").unwrap();
+        let wts = list_worktrees(repo_path).unwrap();
+        let wt = wts.iter().find(|w| w.name == "feature").unwrap();
+        assert!(!wt.is_locked);
+        assert!(wt.lock_reason.is_none());
+    }
+
+    #[test]
+    fn test_prune_worktrees_removes_stale_entries() {
+        let (dir, _repo) = init_test_repo();
+        let repo_path = dir.path().to_str().unwrap();
+        let stale = add_sibling(&dir, "stale");
+        let locked = add_sibling(&dir, "locked");
+        add_sibling(&dir, "alive");
+        lock_worktree(repo_path, "locked", None).unwrap();
+        std::fs::remove_dir_all(&stale).unwrap();
+        std::fs::remove_dir_all(&locked).unwrap();
+
+        let pruned = prune_worktrees(repo_path).unwrap();
+
+        assert_eq!(pruned, vec!["stale".to_string()]);
+        let names: Vec<String> = list_worktrees(repo_path)
+            .unwrap()
+            .into_iter()
+            .map(|w| w.name)
+            .collect();
+        assert!(names.contains(&"locked".to_string()));
+        assert!(names.contains(&"alive".to_string()));
+        assert!(!names.contains(&"stale".to_string()));
+    }
+
+    #[test]
+    fn test_remove_unknown_worktree() {
+        let (dir, _repo) = init_test_repo();
+        let result = remove_worktree(
+            dir.path().to_str().unwrap(),
+            "nope",
+            &RemoveWorktreeOptions::default(),
+        );
+        assert!(result.is_err());
+    }
 }
@@ -7,7 +7,9 @@ pub mod types;
 pub use pull_request::CommitInfo;
 pub use pull_request::GitHubClient;
 pub use pull_request::MergeMethod;
+pub use pull_request::NewPullRequest;
 pub use pull_request::PrInfo;
 pub use pull_request::ReviewEvent;
+pub use pull_request::SignatureStatus;
 #[allow(unused_imports)]
 pub use types::PullRequest;
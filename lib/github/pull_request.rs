@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
-use crate::git::diff::{DiffChunk, DiffLineInfo, FileDiff, FileStatus, LineOrigin};
+use crate::git::diff::{
+    chunks_contain_lfs_pointer, DiffChunk, DiffLineInfo, FileDiff, FileKind, FileStatus, LineOrigin,
+};
+use crate::git::word_diff::highlight_chunk;
 
 /// GitHub API クライアント。内部で `reqwest::Client` を共有し、接続プールを再利用する。
 #[derive(Clone)]
@@ -33,6 +36,17 @@ struct SubmitReviewRequest {
     body: String,
 }
 
+/// Request body for opening a pull request.
+#[derive(Debug, Clone, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    /// Branch to merge from.
+    pub head: String,
+    /// Branch to merge into.
+    pub base: String,
+    pub body: String,
+}
+
 /// Information about a GitHub pull request.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PrInfo {
@@ -40,7 +54,14 @@ pub struct PrInfo {
     pub title: String,
     pub author: String,
     pub state: String,
+    /// Commit GitHub created when merging. Only set for merged PRs.
+    #[serde(default)]
+    pub merge_commit_sha: Option<String>,
     pub head_branch: String,
+    /// `owner/repo` of the head branch's repository. Differs from the base
+    /// repository for fork PRs; `None` when the fork has been deleted.
+    #[serde(default)]
+    pub head_repo: Option<String>,
     pub base_branch: String,
     pub updated_at: String,
     pub additions: u64,
@@ -62,6 +83,8 @@ struct GhPullRequest {
     updated_at: String,
     merged_at: Option<String>,
     #[serde(default)]
+    merge_commit_sha: Option<String>,
+    #[serde(default)]
     additions: u64,
     #[serde(default)]
     deletions: u64,
@@ -81,6 +104,13 @@ struct GhUser {
 struct GhHead {
     #[serde(rename = "ref")]
     ref_name: String,
+    #[serde(default)]
+    repo: Option<GhRepo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRepo {
+    full_name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -89,9 +119,16 @@ struct GhBase {
     ref_name: String,
 }
 
+/// Raw GitHub API response for `PUT .../pulls/{number}/update-branch`.
+#[derive(Debug, Deserialize)]
+struct GhUpdateBranchResponse {
+    message: String,
+}
+
 impl From<GhPullRequest> for PrInfo {
     fn from(pr: GhPullRequest) -> Self {
-        let state = if pr.state == "closed" && pr.merged_at.is_some() {
+        let merged = pr.state == "closed" && pr.merged_at.is_some();
+        let state = if merged {
             "merged".to_string()
         } else {
             pr.state
@@ -101,7 +138,10 @@ impl From<GhPullRequest> for PrInfo {
             title: pr.title,
             author: pr.user.login,
             state,
+            // GitHub はマージ前にもテストマージのコミットを返すため、マージ済みの場合のみ使う
+            merge_commit_sha: pr.merge_commit_sha.filter(|_| merged),
             head_branch: pr.head.ref_name,
+            head_repo: pr.head.repo.map(|r| r.full_name),
             base_branch: pr.base.ref_name,
             updated_at: pr.updated_at,
             additions: pr.additions,
@@ -113,6 +153,21 @@ impl From<GhPullRequest> for PrInfo {
     }
 }
 
+/// Signature state of a commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum SignatureStatus {
+    /// The commit carries a signature that GitHub verified.
+    Signed,
+    /// The commit carries a well-formed signature that has not been verified
+    /// (local commits). Never counts as `Signed`.
+    Unverified,
+    /// The commit carries no signature.
+    Unsigned,
+    /// The commit carries a signature that failed verification or could not
+    /// be parsed.
+    Invalid,
+}
+
 /// Information about a commit in a pull request.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CommitInfo {
@@ -121,6 +176,7 @@ pub struct CommitInfo {
     pub author: String,
     pub date: String,
     pub commit_url: String,
+    pub signature: SignatureStatus,
 }
 
 /// Raw GitHub API response for a commit in a pull request.
@@ -136,6 +192,8 @@ struct GhCommit {
 struct GhCommitDetail {
     message: String,
     author: GhCommitAuthor,
+    #[serde(default)]
+    verification: Option<GhVerification>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -144,15 +202,28 @@ struct GhCommitAuthor {
     date: String,
 }
 
+/// Signature verification payload of a commit.
+#[derive(Debug, Deserialize)]
+struct GhVerification {
+    verified: bool,
+    reason: String,
+}
+
 impl From<GhCommit> for CommitInfo {
     fn from(c: GhCommit) -> Self {
         let author = c.author.map(|u| u.login).unwrap_or(c.commit.author.name);
+        let signature = match c.commit.verification {
+            Some(v) if v.verified => SignatureStatus::Signed,
+            Some(v) if v.reason != "unsigned" => SignatureStatus::Invalid,
+            _ => SignatureStatus::Unsigned,
+        };
         Self {
             sha: c.sha,
             message: c.commit.message,
             author,
             date: c.commit.author.date,
             commit_url: c.html_url,
+            signature,
         }
     }
 }
@@ -218,6 +289,7 @@ fn parse_patch(patch: &str) -> Vec<DiffChunk> {
                 old_lineno: None,
                 new_lineno: None,
                 content: format!("{content}\n"),
+                highlights: Vec::new(),
             });
         }
     }
@@ -247,6 +319,7 @@ fn parse_patch(patch: &str) -> Vec<DiffChunk> {
                 }
             }
         }
+        highlight_chunk(chunk);
     }
 
     chunks
@@ -286,14 +359,21 @@ impl GhPullRequestFile {
             "removed" => FileStatus::Deleted,
             "modified" | "changed" => FileStatus::Modified,
             "renamed" => FileStatus::Renamed,
+            "copied" => FileStatus::Copied,
             _ => FileStatus::Other,
         };
 
         let chunks = self.patch.as_deref().map(parse_patch).unwrap_or_default();
+        // GitHub が返す情報だけではバイナリを判別できないため、LFS ポインタのみ検出する
+        let kind = if chunks_contain_lfs_pointer(&chunks) {
+            FileKind::LfsPointer
+        } else {
+            FileKind::Text
+        };
 
         let old_path = match &status {
             FileStatus::Added => None,
-            FileStatus::Renamed => self.previous_filename.clone(),
+            FileStatus::Renamed | FileStatus::Copied => self.previous_filename.clone(),
             _ => Some(self.filename.clone()),
         };
 
@@ -306,6 +386,11 @@ impl GhPullRequestFile {
             old_path,
             new_path,
             status,
+            similarity: None,
+            kind,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks,
         }
     }
@@ -606,6 +691,67 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Open a pull request.
+    ///
+    /// Calls `POST /repos/{owner}/{repo}/pulls`. The head branch must already
+    /// be pushed.
+    pub async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        request: &NewPullRequest,
+        token: &str,
+    ) -> Result<PrInfo> {
+        self.create_pull_request_with_base_url(
+            "https://api.github.com",
+            owner,
+            repo,
+            request,
+            token,
+        )
+        .await
+    }
+
+    async fn create_pull_request_with_base_url(
+        &self,
+        base_url: &str,
+        owner: &str,
+        repo: &str,
+        request: &NewPullRequest,
+        token: &str,
+    ) -> Result<PrInfo> {
+        let url = format!("{base_url}/repos/{owner}/{repo}/pulls");
+
+        let response = self
+            .http
+            .post(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("User-Agent", "reown")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(request)
+            .send()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to create pull request from '{}' in {owner}/{repo}",
+                    request.head
+                )
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("GitHub API returned {status}: {body}");
+        }
+
+        let pr: GhPullRequest = response
+            .json()
+            .await
+            .context("Failed to parse created pull request")?;
+        Ok(pr.into())
+    }
+
     /// Add labels to a pull request (via the Issues API).
     ///
     /// Calls `POST /repos/{owner}/{repo}/issues/{issue_number}/labels` with
@@ -665,6 +811,74 @@ impl GitHubClient {
         Ok(())
     }
 
+    /// Merge the base branch into a pull request's head branch on GitHub.
+    ///
+    /// Calls `PUT /repos/{owner}/{repo}/pulls/{pr_number}/update-branch`.
+    /// With `expected_head_sha`, GitHub rejects the update (422) if the head
+    /// has moved since. The update runs asynchronously; the returned string
+    /// is GitHub's status message.
+    pub async fn update_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        expected_head_sha: Option<&str>,
+        token: &str,
+    ) -> Result<String> {
+        self.update_branch_with_base_url(
+            "https://api.github.com",
+            owner,
+            repo,
+            pr_number,
+            expected_head_sha,
+            token,
+        )
+        .await
+    }
+
+    async fn update_branch_with_base_url(
+        &self,
+        base_url: &str,
+        owner: &str,
+        repo: &str,
+        pr_number: u64,
+        expected_head_sha: Option<&str>,
+        token: &str,
+    ) -> Result<String> {
+        let url = format!("{base_url}/repos/{owner}/{repo}/pulls/{pr_number}/update-branch");
+
+        let body = match expected_head_sha {
+            Some(sha) => serde_json::json!({ "expected_head_sha": sha }),
+            None => serde_json::json!({}),
+        };
+
+        let response = self
+            .http
+            .put(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {token}"))
+            .header("User-Agent", "reown")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .json(&body)
+            .send()
+            .await
+            .with_context(|| {
+                format!("Failed to update branch of PR #{pr_number} in {owner}/{repo}")
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("GitHub API returned {status}: {body}");
+        }
+
+        let result: GhUpdateBranchResponse = response
+            .json()
+            .await
+            .context("Failed to parse update-branch response")?;
+        Ok(result.message)
+    }
+
     /// Enable auto-merge for a pull request.
     ///
     /// Uses GitHub's GraphQL API to:
@@ -869,7 +1083,7 @@ mod tests {
                 "title": "Add feature X",
                 "state": "open",
                 "user": { "login": "alice" },
-                "head": { "ref": "feature-x" },
+                "head": { "ref": "feature-x", "repo": { "full_name": "alice/repo" } },
                 "base": { "ref": "main" },
                 "updated_at": "2025-01-15T10:30:00Z",
                 "additions": 100,
@@ -904,6 +1118,7 @@ mod tests {
         assert_eq!(prs[0].author, "alice");
         assert_eq!(prs[0].state, "open");
         assert_eq!(prs[0].head_branch, "feature-x");
+        assert_eq!(prs[0].head_repo.as_deref(), Some("alice/repo"));
         assert_eq!(prs[0].base_branch, "main");
         assert_eq!(prs[0].updated_at, "2025-01-15T10:30:00Z");
         assert_eq!(prs[0].additions, 100);
@@ -917,6 +1132,7 @@ mod tests {
         assert_eq!(prs[1].author, "bob");
         assert_eq!(prs[1].state, "open");
         assert_eq!(prs[1].head_branch, "fix-bug-y");
+        assert!(prs[1].head_repo.is_none());
         assert_eq!(prs[1].base_branch, "develop");
         assert_eq!(prs[1].body, "");
         assert_eq!(prs[1].html_url, "https://github.com/owner/repo/pull/43");
@@ -971,6 +1187,7 @@ mod tests {
                 "base": { "ref": "main" },
                 "updated_at": "2024-12-01T12:00:00Z",
                 "merged_at": null,
+                "merge_commit_sha": "def456",
                 "additions": 50,
                 "deletions": 50,
                 "changed_files": 10,
@@ -982,6 +1199,7 @@ mod tests {
         let prs: Vec<PrInfo> = gh_prs.into_iter().map(PrInfo::from).collect();
 
         assert_eq!(prs[0].state, "closed");
+        assert_eq!(prs[0].merge_commit_sha, None);
         assert_eq!(prs[0].author, "charlie");
         assert_eq!(prs[0].head_branch, "old-branch");
     }
@@ -999,6 +1217,7 @@ mod tests {
                 "base": { "ref": "main" },
                 "updated_at": "2025-01-20T14:00:00Z",
                 "merged_at": "2025-01-20T13:55:00Z",
+                "merge_commit_sha": "abc123",
                 "additions": 30,
                 "deletions": 5,
                 "changed_files": 3,
@@ -1012,6 +1231,7 @@ mod tests {
 
         assert_eq!(prs[0].number, 50);
         assert_eq!(prs[0].state, "merged");
+        assert_eq!(prs[0].merge_commit_sha.as_deref(), Some("abc123"));
         assert_eq!(prs[0].author, "dave");
         assert_eq!(prs[0].head_branch, "merged-branch");
     }
@@ -1240,6 +1460,10 @@ mod tests {
         );
         assert_eq!(
             make_file("copied").into_file_diff().status,
+            FileStatus::Copied
+        );
+        assert_eq!(
+            make_file("unchanged").into_file_diff().status,
             FileStatus::Other
         );
     }
@@ -1288,6 +1512,23 @@ mod tests {
 
         let diff = file.into_file_diff();
         assert!(diff.chunks.is_empty());
+        assert_eq!(diff.kind, FileKind::Text);
+    }
+
+    /// Test LFS pointer patch is reported as LfsPointer.
+    #[test]
+    fn test_lfs_pointer_file_kind() {
+        let file = GhPullRequestFile {
+            filename: "assets/video.mp4".to_string(),
+            status: "modified".to_string(),
+            previous_filename: None,
+            patch: Some(
+                "@@ -1,3 +1,3 @@\n version https://git-lfs.github.com/spec/v1\n-oid sha256:aaa\n-size 10\n+oid sha256:bbb\n+size 20".to_string(),
+            ),
+        };
+
+        let diff = file.into_file_diff();
+        assert_eq!(diff.kind, FileKind::LfsPointer);
     }
 
     /// Test ReviewEvent serializes to SCREAMING_SNAKE_CASE.
@@ -1353,6 +1594,12 @@ mod tests {
                     "author": {
                         "name": "Alice",
                         "date": "2025-01-15T10:30:00Z"
+                    },
+                    "verification": {
+                        "verified": true,
+                        "reason": "valid",
+                        "signature": "-----BEGIN PGP SIGNATURE-----\n...",
+                        "payload": "tree ..."
                     }
                 },
                 "author": { "login": "alice" },
@@ -1393,6 +1640,44 @@ mod tests {
         );
         assert_eq!(commits[1].author, "bob");
         assert_eq!(commits[1].date, "2025-01-16T08:00:00Z");
+
+        assert_eq!(commits[0].signature, SignatureStatus::Signed);
+        // No verification payload means GitHub did not report a signature
+        assert_eq!(commits[1].signature, SignatureStatus::Unsigned);
+    }
+
+    /// Test that GitHub's verification payload maps to SignatureStatus.
+    #[test]
+    fn test_parse_commit_verification() {
+        let commit = |verified: bool, reason: &str| {
+            let json = format!(
+                r#"{{
+                    "sha": "abc",
+                    "commit": {{
+                        "message": "m",
+                        "author": {{ "name": "A", "date": "2025-01-15T10:30:00Z" }},
+                        "verification": {{ "verified": {verified}, "reason": "{reason}", "signature": null, "payload": null }}
+                    }},
+                    "author": null,
+                    "html_url": ""
+                }}"#
+            );
+            CommitInfo::from(serde_json::from_str::<GhCommit>(&json).unwrap())
+        };
+
+        assert_eq!(commit(true, "valid").signature, SignatureStatus::Signed);
+        assert_eq!(
+            commit(false, "unsigned").signature,
+            SignatureStatus::Unsigned
+        );
+        assert_eq!(
+            commit(false, "bad_email").signature,
+            SignatureStatus::Invalid
+        );
+        assert_eq!(
+            commit(false, "unknown_key").signature,
+            SignatureStatus::Invalid
+        );
     }
 
     /// Test that when GitHub author is null, the commit author name is used as fallback.
@@ -1570,6 +1855,7 @@ mod tests {
             author: "alice".to_string(),
             date: "2025-01-15T10:30:00Z".to_string(),
             commit_url: "https://github.com/owner/repo/commit/abc123".to_string(),
+            signature: SignatureStatus::Signed,
         };
         let json = serde_json::to_value(&commit).unwrap();
         assert_eq!(json["sha"], "abc123");
@@ -1580,6 +1866,157 @@ mod tests {
             json["commit_url"],
             "https://github.com/owner/repo/commit/abc123"
         );
+        assert_eq!(json["signature"], "Signed");
+    }
+
+    /// Test create_pull_request posts the request and parses the created PR.
+    #[tokio::test]
+    async fn test_create_pull_request_success() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/repos/owner/repo/pulls")
+            .match_header("Authorization", "Bearer test-token")
+            .match_body(mockito::Matcher::Json(serde_json::json!({
+                "title": "Revert \"Add feature\"",
+                "head": "revert/pr-42",
+                "base": "main",
+                "body": "Reverts #42"
+            })))
+            .with_status(201)
+            .with_body(
+                r#"{
+                    "number": 43,
+                    "title": "Revert \"Add feature\"",
+                    "state": "open",
+                    "user": { "login": "alice" },
+                    "head": { "ref": "revert/pr-42" },
+                    "base": { "ref": "main" },
+                    "updated_at": "2025-01-20T14:00:00Z",
+                    "merged_at": null,
+                    "html_url": "https://github.com/owner/repo/pull/43"
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let request = NewPullRequest {
+            title: "Revert \"Add feature\"".to_string(),
+            head: "revert/pr-42".to_string(),
+            base: "main".to_string(),
+            body: "Reverts #42".to_string(),
+        };
+        let pr = GitHubClient::new()
+            .create_pull_request_with_base_url(
+                &server.url(),
+                "owner",
+                "repo",
+                &request,
+                "test-token",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(pr.number, 43);
+        assert_eq!(pr.head_branch, "revert/pr-42");
+        assert_eq!(pr.state, "open");
+        mock.assert_async().await;
+    }
+
+    /// Test create_pull_request returns error on 422 (e.g. head not pushed).
+    #[tokio::test]
+    async fn test_create_pull_request_unprocessable() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/repos/owner/repo/pulls")
+            .with_status(422)
+            .with_body(r#"{"message":"Validation Failed"}"#)
+            .create_async()
+            .await;
+
+        let request = NewPullRequest {
+            title: "t".to_string(),
+            head: "missing".to_string(),
+            base: "main".to_string(),
+            body: String::new(),
+        };
+        let result = GitHubClient::new()
+            .create_pull_request_with_base_url(&server.url(), "owner", "repo", &request, "token")
+            .await;
+
+        assert!(result.unwrap_err().to_string().contains("422"));
+        mock.assert_async().await;
+    }
+
+    /// Test update_branch sends expected_head_sha and returns GitHub's message.
+    #[tokio::test]
+    async fn test_update_branch_success() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("PUT", "/repos/owner/repo/pulls/42/update-branch")
+            .match_header("Authorization", "Bearer test-token")
+            .match_header("X-GitHub-Api-Version", "2022-11-28")
+            .match_body(mockito::Matcher::Json(
+                serde_json::json!({"expected_head_sha": "abc123"}),
+            ))
+            .with_status(202)
+            .with_body(r#"{"message":"Updating pull request branch.","url":"https://github.com/owner/repo/pull/42"}"#)
+            .create_async()
+            .await;
+
+        let message = GitHubClient::new()
+            .update_branch_with_base_url(
+                &server.url(),
+                "owner",
+                "repo",
+                42,
+                Some("abc123"),
+                "test-token",
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(message, "Updating pull request branch.");
+        mock.assert_async().await;
+    }
+
+    /// Test update_branch without expected_head_sha sends an empty body.
+    #[tokio::test]
+    async fn test_update_branch_without_expected_sha() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("PUT", "/repos/owner/repo/pulls/7/update-branch")
+            .match_body(mockito::Matcher::Json(serde_json::json!({})))
+            .with_status(202)
+            .with_body(r#"{"message":"Updating pull request branch."}"#)
+            .create_async()
+            .await;
+
+        let result = GitHubClient::new()
+            .update_branch_with_base_url(&server.url(), "owner", "repo", 7, None, "token")
+            .await;
+
+        assert!(result.is_ok());
+        mock.assert_async().await;
+    }
+
+    /// Test update_branch returns error on 422 (head moved or merge conflict).
+    #[tokio::test]
+    async fn test_update_branch_unprocessable() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("PUT", "/repos/owner/repo/pulls/3/update-branch")
+            .with_status(422)
+            .with_body(r#"{"message":"expected head sha didn't match current head ref."}"#)
+            .create_async()
+            .await;
+
+        let result = GitHubClient::new()
+            .update_branch_with_base_url(&server.url(), "owner", "repo", 3, Some("old"), "token")
+            .await;
+
+        let err_msg = result.unwrap_err().to_string();
+        assert!(err_msg.contains("422"));
+        mock.assert_async().await;
     }
 
     /// Test add_labels succeeds with a 200 response.
@@ -428,7 +428,7 @@ fn format_single_diff(diff: &FileDiff) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::git::diff::{DiffChunk, DiffLineInfo, FileStatus};
+    use crate::git::diff::{DiffChunk, DiffLineInfo, FileKind, FileStatus};
 
     fn make_metadata() -> PrMetadata {
         PrMetadata {
@@ -443,6 +443,7 @@ mod tests {
             old_lineno: None,
             new_lineno: None,
             content: content.to_string(),
+            highlights: Vec::new(),
         }
     }
 
@@ -465,6 +466,11 @@ mod tests {
             old_path: Some(path.to_string()),
             new_path: Some(path.to_string()),
             status: FileStatus::Modified,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -1,1 +1,1 @@".to_string(),
                 lines,
@@ -629,6 +635,11 @@ mod tests {
             old_path: None,
             new_path: Some("src/new.rs".to_string()),
             status: FileStatus::Added,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -0,0 +1,2 @@".to_string(),
                 lines: vec![
@@ -649,6 +660,11 @@ mod tests {
             old_path: Some("src/old.rs".to_string()),
             new_path: None,
             status: FileStatus::Deleted,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
             chunks: vec![DiffChunk {
                 header: "@@ -1,2 +0,0 @@".to_string(),
                 lines: vec![
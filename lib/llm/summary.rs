@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 
 use crate::git::diff::FileDiff;
+use crate::git::tag::ReleaseRange;
 use crate::github::pull_request::{GitHubClient, PrInfo};
 use crate::llm::client::LlmClient;
 use crate::llm::prompts::{Language, PrMetadata, PromptBuilder};
@@ -55,51 +56,14 @@ pub async fn summarize_pr(
         body: pr.body.clone(),
     };
 
-    let builder = PromptBuilder::default();
-
-    // 全体要約を生成
-    let summary_prompts = builder.build_summary_prompt(&diffs, &metadata, Language::Japanese);
-    let mut overall_parts = Vec::new();
-    for prompt in &summary_prompts {
-        let response = llm_client
-            .chat(prompt)
-            .await
-            .context("要約生成のLLM呼び出しに失敗しました")?;
-        overall_parts.push(response);
-    }
-    let overall_response = overall_parts.join("\n\n");
-
-    // 全体要約レスポンスから構造化データを抽出
-    let overall_summary = overall_response.clone();
-    let reason = extract_reason(&overall_response);
-
-    // ファイル単位のサマリーを生成
-    let mut file_summaries = Vec::new();
-    for diff in &diffs {
-        let path = diff
-            .new_path
-            .as_deref()
-            .or(diff.old_path.as_deref())
-            .unwrap_or("(unknown)")
-            .to_string();
-
-        let file_prompt = builder.build_file_summary_prompt(diff, Language::Japanese);
-        let file_response = llm_client
-            .chat(&file_prompt)
-            .await
-            .with_context(|| format!("{path} のファイルサマリー生成に失敗しました"))?;
-
-        file_summaries.push(FileSummary {
-            path,
-            summary: file_response,
-        });
-    }
+    summarize_diffs(&metadata, &diffs, llm_client).await
+}
 
-    Ok(PrSummary {
-        overall_summary,
-        reason,
-        file_summaries,
-    })
+/// リリース範囲（2つのタグ間）の差分からAI要約を生成する
+///
+/// PRタイトルの代わりに `from..to`、本文の代わりに範囲内のコミット一覧を渡す
+pub async fn summarize_release(range: &ReleaseRange, llm_client: &LlmClient) -> Result<PrSummary> {
+    summarize_diffs(&release_metadata(range), &range.files, llm_client).await
 }
 
 /// PRタイトル・本文と実際の差分の整合性をチェックする
@@ -165,6 +129,73 @@ async fn fetch_pr_data(
     Ok((pr, diffs))
 }
 
+/// 差分とメタデータから全体要約・ファイル単位のサマリーを生成する
+async fn summarize_diffs(
+    metadata: &PrMetadata,
+    diffs: &[FileDiff],
+    llm_client: &LlmClient,
+) -> Result<PrSummary> {
+    let builder = PromptBuilder::default();
+
+    // 全体要約を生成
+    let summary_prompts = builder.build_summary_prompt(diffs, metadata, Language::Japanese);
+    let mut overall_parts = Vec::new();
+    for prompt in &summary_prompts {
+        let response = llm_client
+            .chat(prompt)
+            .await
+            .context("要約生成のLLM呼び出しに失敗しました")?;
+        overall_parts.push(response);
+    }
+    let overall_response = overall_parts.join("\n\n");
+
+    // 全体要約レスポンスから構造化データを抽出
+    let overall_summary = overall_response.clone();
+    let reason = extract_reason(&overall_response);
+
+    // ファイル単位のサマリーを生成
+    let mut file_summaries = Vec::new();
+    for diff in diffs {
+        let path = diff
+            .new_path
+            .as_deref()
+            .or(diff.old_path.as_deref())
+            .unwrap_or("(unknown)")
+            .to_string();
+
+        let file_prompt = builder.build_file_summary_prompt(diff, Language::Japanese);
+        let file_response = llm_client
+            .chat(&file_prompt)
+            .await
+            .with_context(|| format!("{path} のファイルサマリー生成に失敗しました"))?;
+
+        file_summaries.push(FileSummary {
+            path,
+            summary: file_response,
+        });
+    }
+
+    Ok(PrSummary {
+        overall_summary,
+        reason,
+        file_summaries,
+    })
+}
+
+/// リリース範囲をプロンプト用のメタデータに変換する
+fn release_metadata(range: &ReleaseRange) -> PrMetadata {
+    let body = range
+        .commits
+        .iter()
+        .map(|c| format!("- {} ({})", c.summary, &c.sha[..7]))
+        .collect::<Vec<_>>()
+        .join("\n");
+    PrMetadata {
+        title: format!("{}..{}", range.from.name, range.to.name),
+        body,
+    }
+}
+
 /// LLMの要約レスポンスから「なぜ変わったか」部分を抽出する
 ///
 /// 構造化された応答がない場合は要約全体をそのまま返す
@@ -362,4 +393,36 @@ mod tests {
         assert_eq!(json["path"], "lib/mod.rs");
         assert_eq!(json["summary"], "モジュール追加");
     }
+
+    #[test]
+    fn test_release_metadata() {
+        use crate::git::tag::release_range;
+        use crate::git::test_utils::init_repo_with_commit;
+
+        let (dir, repo) = init_repo_with_commit();
+        let path = dir.path().to_str().unwrap();
+        let sig = repo.signature().unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag("v1", head.as_object(), &sig, "v1", false).unwrap();
+        let oid = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "feat: add x\n\nbody",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        repo.tag_lightweight("v2", &repo.find_object(oid, None).unwrap(), false)
+            .unwrap();
+
+        let range = release_range(path, "v1", "v2", &Default::default()).unwrap();
+        let metadata = release_metadata(&range);
+        assert_eq!(metadata.title, "v1..v2");
+        assert_eq!(
+            metadata.body,
+            format!("- feat: add x ({})", &oid.to_string()[..7])
+        );
+    }
 }
@@ -0,0 +1,159 @@
+use crate::git::diff::{DiffLineInfo, FileDiff, LineOrigin};
+
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const CYAN: &str = "\x1b[36m";
+const BOLD: &str = "\x1b[1m";
+/// 行内の変更箇所は反転表示で強調する
+const REVERSE: &str = "\x1b[7m";
+const NO_REVERSE: &str = "\x1b[27m";
+
+/// FileDiff をターミナル表示用の行リストにフォーマットする。
+/// 追加行は緑、削除行は赤で表示し、`highlights` の範囲は反転表示する。
+pub fn render_file_diff(diff: &FileDiff) -> Vec<String> {
+    let path = diff
+        .new_path
+        .as_deref()
+        .or(diff.old_path.as_deref())
+        .unwrap_or("");
+    let mut out = vec![format!("{BOLD}{path}{RESET}")];
+
+    for chunk in &diff.chunks {
+        out.push(format!("{CYAN}{}{RESET}", chunk.header));
+        out.extend(chunk.lines.iter().map(render_line));
+    }
+
+    out
+}
+
+fn render_line(line: &DiffLineInfo) -> String {
+    let content = line.content.trim_end_matches(['\n', '\r']);
+    match line.origin {
+        LineOrigin::Addition => format!("{GREEN}+{}{RESET}", highlight(content, line)),
+        LineOrigin::Deletion => format!("{RED}-{}{RESET}", highlight(content, line)),
+        _ => format!(" {content}"),
+    }
+}
+
+fn highlight(content: &str, line: &DiffLineInfo) -> String {
+    if line.highlights.is_empty() {
+        return content.to_string();
+    }
+
+    let mut out = String::with_capacity(content.len());
+    let mut ranges = line.highlights.iter().peekable();
+    let mut in_range = false;
+
+    for (idx, c) in content.chars().enumerate() {
+        if in_range && ranges.peek().is_some_and(|r| idx >= r.end) {
+            out.push_str(NO_REVERSE);
+            in_range = false;
+            ranges.next();
+        }
+        if !in_range && ranges.peek().is_some_and(|r| idx >= r.start) {
+            out.push_str(REVERSE);
+            in_range = true;
+        }
+        out.push(c);
+    }
+    if in_range {
+        out.push_str(NO_REVERSE);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::{DiffChunk, FileKind, FileStatus, HighlightRange};
+
+    fn make_line(
+        origin: LineOrigin,
+        content: &str,
+        highlights: Vec<(usize, usize)>,
+    ) -> DiffLineInfo {
+        DiffLineInfo {
+            origin,
+            old_lineno: None,
+            new_lineno: None,
+            content: content.to_string(),
+            highlights: highlights
+                .into_iter()
+                .map(|(start, end)| HighlightRange { start, end })
+                .collect(),
+        }
+    }
+
+    fn make_diff(lines: Vec<DiffLineInfo>) -> FileDiff {
+        FileDiff {
+            old_path: Some("src/main.rs".to_string()),
+            new_path: Some("src/main.rs".to_string()),
+            status: FileStatus::Modified,
+            similarity: None,
+            kind: FileKind::Text,
+            old_blob: None,
+            new_blob: None,
+            submodule: None,
+            chunks: vec![DiffChunk {
+                header: "@@ -1,2 +1,2 @@".to_string(),
+                lines,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_header_and_chunk() {
+        let diff = make_diff(vec![make_line(
+            LineOrigin::Context,
+            "fn main() {\n",
+            vec![],
+        )]);
+        let lines = render_file_diff(&diff);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("src/main.rs"));
+        assert_eq!(lines[1], "\x1b[36m@@ -1,2 +1,2 @@\x1b[0m");
+        assert_eq!(lines[2], " fn main() {");
+    }
+
+    #[test]
+    fn test_render_additions_green_deletions_red() {
+        let diff = make_diff(vec![
+            make_line(LineOrigin::Deletion, "old\n", vec![]),
+            make_line(LineOrigin::Addition, "new\n", vec![]),
+        ]);
+        let lines = render_file_diff(&diff);
+        assert_eq!(lines[2], "\x1b[31m-old\x1b[0m");
+        assert_eq!(lines[3], "\x1b[32m+new\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_highlight_ranges_reversed() {
+        let diff = make_diff(vec![
+            make_line(LineOrigin::Deletion, "let count = 1;\n", vec![(4, 9)]),
+            make_line(LineOrigin::Addition, "let total = 1;\n", vec![(4, 9)]),
+        ]);
+        let lines = render_file_diff(&diff);
+        assert_eq!(lines[2], "\x1b[31m-let \x1b[7mcount\x1b[27m = 1;\x1b[0m");
+        assert_eq!(lines[3], "\x1b[32m+let \x1b[7mtotal\x1b[27m = 1;\x1b[0m");
+    }
+
+    #[test]
+    fn test_render_highlight_multiple_ranges_to_line_end() {
+        let line = make_line(LineOrigin::Addition, "a b c", vec![(0, 1), (4, 5)]);
+        assert_eq!(
+            highlight("a b c", &line),
+            "\x1b[7ma\x1b[27m b \x1b[7mc\x1b[27m"
+        );
+    }
+
+    #[test]
+    fn test_render_highlight_multibyte() {
+        let line = make_line(LineOrigin::Addition, "名前 = 花子", vec![(5, 7)]);
+        assert_eq!(
+            highlight("名前 = 花子", &line),
+            "名前 = \x1b[7m花子\x1b[27m"
+        );
+    }
+}
@@ -30,7 +30,9 @@ mod tests {
             title: format!("PR {number}"),
             author: "alice".to_string(),
             state: "open".to_string(),
+            merge_commit_sha: None,
             head_branch: "feature".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-15T10:30:00Z".to_string(),
             additions,
@@ -107,7 +109,9 @@ mod tests {
             title: format!("PR {number}"),
             author: "alice".to_string(),
             state: state.to_string(),
+            merge_commit_sha: None,
             head_branch: "feature".to_string(),
+            head_repo: None,
             base_branch: "main".to_string(),
             updated_at: "2025-01-15T10:30:00Z".to_string(),
             additions: 10,